@@ -13,7 +13,7 @@ use redis_asio::stream::{RedisStream, StreamEntry, EntryId, AckResponse, Subscri
                          RedisGroup, TouchGroupOptions, AckOptions};
 
 #[derive(Debug)]
-struct Message(String);
+struct Message(#[allow(dead_code)] String);
 
 /// Implements the trait to allow implicit conversion from RedisValue to Message
 /// via from_redis_value()
@@ -125,7 +125,10 @@ fn main() {
             tokio::spawn(ack_entry);
 
             let group = RedisGroup::new(group_name, consumer_name);
-            let options = SubscribeOptions::with_group(vec![stream_name], group);
+            // Receive the entries that were not acknowledged before the restart first,
+            // then switch to the new entries.
+            let options = SubscribeOptions::with_group(vec![stream_name], group)
+                .with_recovery();
 
             // Subscribe to a Redis stream, processes any incoming entries and sends
             // entry ids of success processed entries to the manager via the channel sender (tx).
@@ -155,7 +158,7 @@ fn read_stdin() -> String {
 
 fn ack_stream_entry(manager: RedisStream, stream: String, group: String, id_to_ack: EntryId)
                     -> impl Future<Item=RedisStream, Error=RedisError> {
-    let options = AckOptions::new(stream.clone(), group.clone(), id_to_ack.clone());
+    let options = AckOptions::new(stream.clone(), group.clone(), id_to_ack);
 
     manager.ack_entry(options)
         .map(move |(manager, response)| {
@@ -0,0 +1,42 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tokio_codec::Decoder;
+use bytes::BytesMut;
+use redis_asio::RedisCodec;
+
+// Decode all messages from the data at once and byte by byte,
+// the decoder should not panic and both ways should give the same result.
+fuzz_target!(|data: &[u8]| {
+    let mut codec = RedisCodec;
+
+    let mut buf = BytesMut::from(data);
+    let mut whole = Vec::new();
+    let whole_error = loop {
+        match codec.decode(&mut buf) {
+            Ok(Some(value)) => whole.push(value),
+            Ok(None) => break false,
+            Err(_) => break true,
+        }
+    };
+
+    let mut buf = BytesMut::new();
+    let mut by_byte = Vec::new();
+    let mut by_byte_error = false;
+    'feed: for byte in data.iter() {
+        buf.extend_from_slice(&[*byte]);
+        loop {
+            match codec.decode(&mut buf) {
+                Ok(Some(value)) => by_byte.push(value),
+                Ok(None) => break,
+                Err(_) => {
+                    by_byte_error = true;
+                    break 'feed;
+                }
+            }
+        }
+    }
+
+    assert_eq!(whole, by_byte);
+    assert_eq!(whole_error, by_byte_error);
+});
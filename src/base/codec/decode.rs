@@ -1,6 +1,5 @@
 use super::{RedisResult, RedisError, RedisErrorKind, RespInternalValue};
 use std::io::Cursor;
-use std::error::Error;
 use byteorder::ReadBytesExt;
 
 pub struct ParseResult<T> {
@@ -93,16 +92,9 @@ fn parse_bulkstring(data: &[u8]) -> RedisResult<OptParseResult<RespInternalValue
         };
 
     if value < 0 {
-        if data.len() < len_len + CRLF_LEN {
-            // message is not complete
-            return Ok(None);
-        }
-
-        match does_end_with_crlf(&data[..len_len + CRLF_LEN]) {
-            true => return Ok(Some(
-                ParseResult { value: RespInternalValue::Nil, value_src_len: len_len + CRLF_LEN })),
-            false => return Err(make_parse_error())
-        };
+        // eg "-1\r\n" there is no data and no final CRLF after the length,
+        // the same as for a Nil array
+        return Ok(Some(ParseResult { value: RespInternalValue::Nil, value_src_len: len_len }));
     }
 
     let string_len = value as usize;
@@ -116,7 +108,7 @@ fn parse_bulkstring(data: &[u8]) -> RedisResult<OptParseResult<RespInternalValue
         return Ok(None);
     }
 
-    if !data[..value_src_len].ends_with(&[CRLF.0, CRLF.1]) {
+    if !does_end_with_crlf(&data[..value_src_len]) {
         return Err(make_parse_error());
     }
 
@@ -144,7 +136,10 @@ fn parse_array(data: &[u8]) -> RedisResult<OptParseResult<RespInternalValue>> {
     let array_len = array_len as usize;
 
     let mut pos = len_len;
-    let mut result: Vec<RespInternalValue> = Vec::with_capacity(array_len);
+    // do not trust the declared length: every element takes at least one byte,
+    // so the rest of the data limits the count of elements that can be received
+    let capacity = std::cmp::min(array_len, data.len() - len_len);
+    let mut result: Vec<RespInternalValue> = Vec::with_capacity(capacity);
 
     for _ in 0..array_len {
         let ParseResult { value, value_src_len } =
@@ -154,7 +149,7 @@ fn parse_array(data: &[u8]) -> RedisResult<OptParseResult<RespInternalValue>> {
             };
 
         result.push(value);
-        pos = pos + value_src_len;
+        pos += value_src_len;
     };
 
     Ok(Some(ParseResult { value: RespInternalValue::Array(result), value_src_len: pos }))
@@ -190,7 +185,7 @@ fn parse_simple_string(data: &[u8]) -> RedisResult<OptParseResult<String>> {
         Err(err) => Err(
             RedisError::new(
                 RedisErrorKind::ParseError,
-                format!("Could not parse a status from bytes: {}", err.description()))
+                format!("Could not parse a status from bytes: {}", err))
         )
     }
 }
@@ -207,7 +202,7 @@ fn parse_simple_int(data: &[u8]) -> RedisResult<OptParseResult<i64>> {
         Err(err) => return Err(
             RedisError::new(
                 RedisErrorKind::ParseError,
-                format!("Could not parse an i64 from the {:?}, error: {}", value, err.description()),
+                format!("Could not parse an i64 from the {:?}, error: {}", value, err),
             )
         ),
     };
@@ -293,8 +288,8 @@ mod tests {
         // $ - message type identifier
         // -10\r\n - the number of bytes composing the string (a prefixed length), terminated by CRLF.
         // actual there is no string data.
-        // \r\n - a final CRLF.
-        let mut raw_data = Vec::from("$-10\r\n\r\n");
+        // there is no final CRLF after a negative length, see the RESP "Null Bulk String".
+        let mut raw_data = Vec::from("$-10\r\n");
         let expected_value_len = raw_data.len();
         raw_data.append(&mut "trash".as_bytes().to_vec());
 
@@ -306,16 +301,31 @@ mod tests {
 
         // receive an incomplete message
         assert!(parse_resp_value(Vec::from("$-10\r").as_mut_slice()).unwrap().is_none(), "expected Ok(None)");
-        // receive an incomplete message
-        assert!(parse_resp_value(Vec::from("$-10\r\n$").as_mut_slice()).unwrap().is_none(), "expected Ok(None)");
+        // receive a Nil followed by the next incomplete message
+        let ParseResult { value, value_src_len }
+            = parse_resp_value(Vec::from("$-10\r\n$").as_mut_slice()).unwrap().unwrap();
+        assert_eq!(RespInternalValue::Nil, value);
+        assert_eq!("$-10\r\n".len(), value_src_len);
         // receive incorrect message without CRLF
-        assert!(parse_resp_value(Vec::from("$-10\r\n%$").as_mut_slice()).is_err(), "expected Err");
-//    assert!(parse_bulkstring(Vec::from("-12X45\r\n").as_mut_slice()).is_err(), "expected Err");
+        assert!(parse_resp_value(Vec::from("$-10\r%$").as_mut_slice()).is_err(), "expected Err");
+    }
+
+    #[test]
+    fn test_parse_array_nil() {
+        let mut raw_data = Vec::from("*-1\r\n");
+        let expected_value_len = raw_data.len();
+        raw_data.append(&mut "trash".as_bytes().to_vec());
+
+        let ParseResult { value, value_src_len }
+            = parse_resp_value(raw_data.as_slice()).unwrap().unwrap();
+
+        assert_eq!(RespInternalValue::Nil, value);
+        assert_eq!(expected_value_len, value_src_len);
     }
 
     #[test]
     fn test_parse_array() {
-        let mut nil_value_data = Vec::from("$-1\r\n\r\n");
+        let mut nil_value_data = Vec::from("$-1\r\n");
         // "Error message"
         let mut error_value_data = Vec::from("-Error message\r\n");
         // "Status message"
@@ -382,6 +392,9 @@ mod tests {
         assert!(parse_resp_value(Vec::from("*1\r#$").as_mut_slice()).is_err(), "expected Err");
         // receive incorrect message: array's element ends without CRLF
         assert!(parse_resp_value(Vec::from("*1\r\n:12\r$").as_mut_slice()).is_err(), "expected Err");
+        // receive an incomplete message with a huge declared length
+        assert!(parse_resp_value(Vec::from("*9223372036854775807\r\n:1\r\n").as_mut_slice()).unwrap().is_none(),
+                "expected Ok(None)");
     }
 }
 
@@ -5,7 +5,7 @@ pub fn encode_resp_value(value: RespInternalValue) -> Vec<u8> {
         RespInternalValue::Nil => "$-1\r\n".as_bytes().to_vec(),
         RespInternalValue::Error(x) => format!("-{}\r\n", x).into_bytes(),
         RespInternalValue::Status(x) => format!("+{}\r\n", x).into_bytes(),
-        RespInternalValue::Int(x) => format!(":{}\r\n", x.to_string()).into_bytes(),
+        RespInternalValue::Int(x) => format!(":{}\r\n", x).into_bytes(),
         RespInternalValue::BulkString(mut x) => {
             let mut res = format!("${}\r\n", x.len()).into_bytes();
             res.append(&mut x);
@@ -40,6 +40,33 @@ impl Decoder for RedisCodec {
     }
 }
 
+/// Server side of the RESP protocol: decodes requests and encodes replies.
+/// Is used by the mock server only.
+#[cfg(any(test, feature = "mock"))]
+pub struct RedisServerCodec;
+
+#[cfg(any(test, feature = "mock"))]
+impl Encoder for RedisServerCodec {
+    type Item = RespInternalValue;
+    type Error = RedisError;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        dst.extend_from_slice(encode_resp_value(item).as_ref());
+        Ok(())
+    }
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl Decoder for RedisServerCodec {
+    type Item = RespInternalValue;
+    type Error = RedisError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // requests are parsed the same way as replies
+        RedisCodec.decode(buf)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,4 +100,89 @@ mod tests {
         let mut buf = BytesMut::from("+OK\r$".as_bytes().to_vec());
         assert!(codec.decode(&mut buf).is_err());
     }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+        use proptest::collection::vec;
+
+        fn resp_value() -> impl Strategy<Value=RespInternalValue> {
+            // statuses and errors are simple strings that cannot contain CR or LF
+            let simple_string = "[^\r\n]{0,32}";
+            let leaf = prop_oneof![
+                Just(RespInternalValue::Nil),
+                simple_string.prop_map(RespInternalValue::Error),
+                simple_string.prop_map(RespInternalValue::Status),
+                any::<i64>().prop_map(RespInternalValue::Int),
+                vec(any::<u8>(), 0..64).prop_map(RespInternalValue::BulkString),
+            ];
+            leaf.prop_recursive(4, 64, 8,
+                                |inner| vec(inner, 0..8).prop_map(RespInternalValue::Array))
+        }
+
+        proptest! {
+            #[test]
+            fn decode_encoded_value(value in resp_value()) {
+                let mut codec = RedisCodec {};
+                let mut buf = BytesMut::from(encode_resp_value(value.clone()));
+                prop_assert_eq!(Some(value), codec.decode(&mut buf).unwrap());
+                // the whole message should be consumed
+                prop_assert!(buf.is_empty());
+            }
+
+            #[test]
+            fn decode_incomplete_value(value in resp_value()) {
+                let mut codec = RedisCodec {};
+                let data = encode_resp_value(value);
+
+                // split the message at every byte boundary
+                for len in 0..data.len() {
+                    let mut buf = BytesMut::from(&data[..len]);
+                    prop_assert_eq!(None, codec.decode(&mut buf).unwrap());
+                    // an incomplete message should not be consumed
+                    prop_assert_eq!(&data[..len], buf.as_ref());
+                }
+            }
+
+            #[test]
+            fn decode_value_fed_by_byte(value in resp_value(), trash in vec(any::<u8>(), 0..8)) {
+                let mut codec = RedisCodec {};
+                let data = encode_resp_value(value.clone());
+                let mut buf = BytesMut::new();
+
+                let (last, head) = data.split_last().unwrap();
+                for byte in head.iter() {
+                    buf.extend_from_slice(&[*byte]);
+                    prop_assert_eq!(None, codec.decode(&mut buf).unwrap());
+                }
+
+                buf.extend_from_slice(&[*last]);
+                buf.extend_from_slice(&trash);
+                prop_assert_eq!(Some(value), codec.decode(&mut buf).unwrap());
+                prop_assert_eq!(&trash[..], buf.as_ref());
+            }
+
+            #[test]
+            fn decode_sequence_of_values(values in vec(resp_value(), 1..8)) {
+                let mut codec = RedisCodec {};
+                let mut buf = BytesMut::new();
+                for value in values.iter() {
+                    buf.extend_from_slice(&encode_resp_value(value.clone()));
+                }
+
+                for value in values.into_iter() {
+                    prop_assert_eq!(Some(value), codec.decode(&mut buf).unwrap());
+                }
+                prop_assert!(buf.is_empty());
+            }
+
+            #[test]
+            fn decode_arbitrary_data(data in vec(any::<u8>(), 0..256)) {
+                // the decoder should not panic on any input
+                let mut codec = RedisCodec {};
+                let mut buf = BytesMut::from(data);
+                while let Ok(Some(_)) = codec.decode(&mut buf) {}
+            }
+        }
+    }
 }
@@ -1,4 +1,5 @@
-use crate::RespInternalValue;
+use crate::{RespInternalValue, FromRedisValue};
+use std::marker::PhantomData;
 
 
 /// Make a Redis command represents array of `BulkString`s
@@ -16,6 +17,7 @@ pub fn command(cmd: &str) -> RedisCommand {
 
 /// Enumeration of base types that can be put into
 /// `RedisCommand` chain as argument.
+#[derive(Clone)]
 pub enum RedisArgument {
     Int(i64),
     String(String),
@@ -50,6 +52,72 @@ pub trait IntoRedisArgument {
     fn into_redis_argument(self) -> RedisArgument;
 }
 
+/// Trait interface of a command that declares the type of its response.
+/// The response will be converted into the `Output` by
+/// `RedisCoreConnection::send_typed()`.
+///
+/// # Example
+/// ```
+/// use redis_asio::{TypedCommand, RedisCommand, command};
+///
+/// struct DbSize;
+///
+/// impl TypedCommand for DbSize {
+///     type Output = i64;
+///
+///     fn into_command(self) -> RedisCommand {
+///         command("DBSIZE")
+///     }
+/// }
+/// ```
+pub trait TypedCommand {
+    /// Type of the response on the command
+    type Output: FromRedisValue;
+
+    fn into_command(self) -> RedisCommand;
+}
+
+/// `RedisCommand` wrapper that declares the `T` type of its response.
+///
+/// # Example
+/// ```
+/// use redis_asio::{RedisTypedCommand, command};
+///
+/// let cmd: RedisTypedCommand<Option<String>> =
+///     RedisTypedCommand::new(command("GET").arg("foo"));
+/// ```
+pub struct RedisTypedCommand<T> {
+    cmd: RedisCommand,
+    response: PhantomData<fn() -> T>,
+}
+
+impl<T: FromRedisValue> RedisTypedCommand<T> {
+    pub fn new(cmd: RedisCommand) -> RedisTypedCommand<T> {
+        RedisTypedCommand { cmd, response: PhantomData }
+    }
+
+    /// Add new argument into the wrapped `RedisCommand` and move the one back.
+    /// The argument should implement the `IntoRedisArgument` trait.
+    pub fn arg<A: IntoRedisArgument>(mut self, arg: A) -> RedisTypedCommand<T> {
+        self.cmd.arg_mut(arg);
+        self
+    }
+}
+
+impl<T: FromRedisValue> TypedCommand for RedisTypedCommand<T> {
+    type Output = T;
+
+    fn into_command(self) -> RedisCommand {
+        self.cmd
+    }
+}
+
+impl<T> Clone for RedisTypedCommand<T> {
+    fn clone(&self) -> Self {
+        RedisTypedCommand { cmd: self.cmd.clone(), response: PhantomData }
+    }
+}
+
 impl RedisCommand {
     pub(crate) fn new() -> RedisCommand {
         RedisCommand { args: Vec::new() }
@@ -121,6 +189,12 @@ impl IntoRedisArgument for Vec<u8> {
     }
 }
 
+impl IntoRedisArgument for f64 {
+    fn into_redis_argument(self) -> RedisArgument {
+        RedisArgument::String(self.to_string())
+    }
+}
+
 macro_rules! declare_to_int_argument {
     ($itype:ty) => {
         impl IntoRedisArgument for $itype {
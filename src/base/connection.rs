@@ -1,10 +1,11 @@
 use tokio_codec::Decoder;
 use tokio_tcp::TcpStream;
 use futures::{Future, Stream, Sink, Async, try_ready};
-use crate::{RedisValue, RedisCommand, RespInternalValue, RedisCodec, RedisError, RedisErrorKind};
+use crate::{RedisValue, RedisCommand, RespInternalValue, RedisCodec, RedisError, RedisErrorKind,
+            RedisResult, FromRedisValue, TypedCommand, from_redis_value};
 use std::net::SocketAddr;
 use core::marker::Send as SendMarker;
-use std::error::Error;
+use std::marker::PhantomData;
 
 
 /// Actual Redis connection converts packets from `RESP` packets into `RedisValue`
@@ -42,7 +43,7 @@ impl RedisCoreConnection {
     /// that will be available in the future.
     pub fn connect(addr: &SocketAddr) -> impl Future<Item=Self, Error=RedisError> {
         TcpStream::connect(addr)
-            .map_err(|err| RedisError::new(RedisErrorKind::ConnectionError, err.description().to_string()))
+            .map_err(|err| RedisError::new(RedisErrorKind::ConnectionError, err.to_string()))
             .map(|stream| {
                 let codec = RedisCodec;
                 let (tx, rx) = codec.framed(stream).split();
@@ -63,6 +64,94 @@ impl RedisCoreConnection {
     pub fn send(self, req: RedisCommand) -> Send {
         Send::new(self, req)
     }
+
+    /// Send request as a `RedisCommand` and return the connection back
+    /// even if the server has replied an error, e.g. to tolerate the BUSYGROUP error.
+    pub(crate) fn try_send(self, req: RedisCommand) -> TrySend {
+        TrySend { send: self.send(req) }
+    }
+
+    /// Send request as a `RedisCommand` and convert the response into `T`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::Future;
+    /// use redis_asio::{RedisCoreConnection, command};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    ///
+    /// let future = RedisCoreConnection::connect(address)
+    ///     .and_then(|con| con.query::<Option<String>>(command("GET").arg("foo")))
+    ///     .map(|(_, value)| println!("foo = {:?}", value))
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn query<T: FromRedisValue>(self, req: RedisCommand) -> Query<T> {
+        Query::new(self.send(req))
+    }
+
+    /// Send a command that declares the type of its response,
+    /// and return the response converted into the type.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::Future;
+    /// use redis_asio::RedisCoreConnection;
+    /// use redis_asio::cmds;
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    ///
+    /// let future = RedisCoreConnection::connect(address)
+    ///     .and_then(|con| con.send_typed(cmds::incr("counter")))
+    ///     .map(|(_, value): (_, i64)| println!("counter = {}", value))
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn send_typed<C: TypedCommand>(self, cmd: C) -> Query<C::Output> {
+        self.query(cmd.into_command())
+    }
+}
+
+/// The `Future<Item=(RedisCoreConnection, T), Error=RedisError>` wrapper
+/// that converts the response into `T`.
+pub struct Query<T> {
+    send: Send,
+    response: PhantomData<fn() -> T>,
+}
+
+impl<T: FromRedisValue> Query<T> {
+    fn new(send: Send) -> Query<T> {
+        Query { send, response: PhantomData }
+    }
+}
+
+impl<T: FromRedisValue> Future for Query<T> {
+    type Item = (RedisCoreConnection, T);
+    type Error = RedisError;
+
+    fn poll(&mut self) -> Result<Async<Self::Item>, Self::Error> {
+        let (con, response) = try_ready!(self.send.poll());
+        Ok(Async::Ready((con, from_redis_value(&response)?)))
+    }
+}
+
+/// The `Future<Item=(RedisCoreConnection, RedisResult<RedisValue>), Error=RedisError>` wrapper
+/// that keeps the connection if the server has replied an error.
+/// The future fails on connection errors only.
+pub(crate) struct TrySend {
+    send: Send,
+}
+
+impl Future for TrySend {
+    type Item = (RedisCoreConnection, RedisResult<RedisValue>);
+    type Error = RedisError;
+
+    fn poll(&mut self) -> Result<Async<Self::Item>, Self::Error> {
+        let (con, response) = try_ready!(self.send.poll_response());
+        Ok(Async::Ready((con, response.into_redis_value())))
+    }
 }
 
 /// The `Future<Item=(RedisCoreConnection, RedisValue), Error=RedisError>` wrapper
@@ -88,6 +177,14 @@ impl Future for Send {
     type Error = RedisError;
 
     fn poll(&mut self) -> Result<Async<Self::Item>, Self::Error> {
+        let (con, response) = try_ready!(self.poll_response());
+        Ok(Async::Ready((con, response.into_redis_value()?)))
+    }
+}
+
+impl Send {
+    /// Send the request and wait for the response that could be a RESP Error.
+    fn poll_response(&mut self) -> Result<Async<(RedisCoreConnection, RespInternalValue)>, RedisError> {
         let sender = self.sender.as_mut().unwrap();
         let receiver = self.receiver.as_mut().unwrap();
 
@@ -106,10 +203,9 @@ impl Future for Send {
 
         match try_ready!(receiver.poll()) {
             Some(response) => {
-                let redis_response = response.into_redis_value()?;
                 let con =
                     RedisCoreConnection::new(self.sender.take().unwrap(), self.receiver.take().unwrap());
-                Ok(Async::Ready((con, redis_response)))
+                Ok(Async::Ready((con, response)))
             }
             _ => Err(RedisError::new(RedisErrorKind::ConnectionError,
                                      "Connection has closed before an answer came".to_string()))
@@ -1,5 +1,4 @@
 use std::fmt;
-use std::error::Error;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum RedisErrorKind {
@@ -14,7 +13,7 @@ pub enum RedisErrorKind {
 #[derive(Debug, Clone)]
 pub struct RedisError {
     pub error: RedisErrorKind,
-    desc: String,
+    pub(crate) desc: String,
 }
 
 pub type RedisResult<T> = Result<T, RedisError>;
@@ -41,7 +40,7 @@ impl std::error::Error for RedisError {
 
 impl From<std::io::Error> for RedisError {
     fn from(err: std::io::Error) -> Self {
-        RedisError { error: RedisErrorKind::ConnectionError, desc: err.description().to_string() }
+        RedisError { error: RedisErrorKind::ConnectionError, desc: err.to_string() }
     }
 }
 
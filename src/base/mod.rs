@@ -12,5 +12,8 @@ pub use error::{RedisResult, RedisError, RedisErrorKind};
 pub use resp_value::RespInternalValue;
 pub use value::{RedisValue, FromRedisValue, from_redis_value};
 pub use codec::RedisCodec;
-pub use connection::RedisCoreConnection;
-pub use command::{command, RedisCommand, RedisArgument, IntoRedisArgument};
+#[cfg(any(test, feature = "mock"))]
+pub use codec::RedisServerCodec;
+pub use connection::{RedisCoreConnection, Query};
+pub use command::{command, RedisCommand, RedisArgument, IntoRedisArgument, TypedCommand,
+                  RedisTypedCommand};
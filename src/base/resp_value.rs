@@ -24,7 +24,7 @@ impl RespInternalValue {
             RedisValue::Array(x) =>
                 RespInternalValue::Array(
                     x.into_iter()
-                        .map(|val| RespInternalValue::from_redis_value(val))
+                        .map(RespInternalValue::from_redis_value)
                         .collect())
         }
     }
@@ -3,7 +3,7 @@ use std::error::Error;
 use std::fmt;
 use std::cmp::PartialEq;
 use std::str::FromStr;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use core::num::ParseIntError;
 use crate::base::RespInternalValue;
@@ -83,6 +83,30 @@ pub trait FromRedisValue: Sized {
     fn from_redis_u8(_: u8) -> Option<Self> {
         None
     }
+
+    /// Convert a flat array e.g. [member1, score1, member2, score2]
+    /// into the vector of pairs [(member1, score1), (member2, score2)].
+    /// Returns None if the type cannot be taken from a flat array.
+    ///
+    /// Note `Vec<(T1, T2)>` is converted either from an array of 2-element arrays
+    /// [[member1, score1], [member2, score2]] or from a flat array without nested arrays
+    /// (e.g. the ZRANGE WITHSCORES reply), the latter is a conversion error for the other types.
+    ///
+    /// # Example
+    /// ```
+    /// use redis_asio::{RedisValue, from_redis_value};
+    ///
+    /// let member = |x: &str| RedisValue::BulkString(x.as_bytes().to_vec());
+    /// let flat = RedisValue::Array(vec![member("one"), member("1"), member("two"), member("2")]);
+    /// let nested = RedisValue::Array(vec![RedisValue::Array(vec![member("one"), member("1")]),
+    ///                                     RedisValue::Array(vec![member("two"), member("2")])]);
+    /// let expected = vec![("one".to_string(), 1.0), ("two".to_string(), 2.0)];
+    /// assert_eq!(expected, from_redis_value::<Vec<(String, f64)>>(&flat).unwrap());
+    /// assert_eq!(expected, from_redis_value::<Vec<(String, f64)>>(&nested).unwrap());
+    /// ```
+    fn from_redis_flat_array(_: &[RedisValue]) -> Option<RedisResult<Vec<Self>>> {
+        None
+    }
 }
 
 /// Convert `RedisValue` into `T` value.
@@ -101,7 +125,7 @@ pub fn from_redis_value<T: FromRedisValue>(value: &RedisValue) -> RedisResult<T>
         .map_err(|err|
             RedisError::new(
                 err.error.clone(),
-                format!("Couldn't convert the Redis value: \"{:?}\". Reason: \"{}\"", value, err.description()
+                format!("Couldn't convert the Redis value: \"{:?}\". Reason: \"{}\"", value, err.desc
                 ),
             )
         )
@@ -128,13 +152,59 @@ impl FromRedisValue for String {
         match value {
             RedisValue::Status(x) => Ok(x.clone()),
             RedisValue::BulkString(x) => {
-                String::from_utf8(x.clone()).map_err(|err| to_conversion_error(err))
+                String::from_utf8(x.clone()).map_err(to_conversion_error)
             }
             _ => Err(conversion_error_from_value(value, "String"))
         }
     }
 }
 
+impl FromRedisValue for bool {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        match value {
+            // e.g. EXPIRE replies 1 if the timeout was set and 0 else
+            RedisValue::Int(x) => Ok(*x != 0),
+            // e.g. SET NX replies OK if the key was set and Nil else
+            RedisValue::Ok => Ok(true),
+            RedisValue::Nil => Ok(false),
+            _ => Err(conversion_error_from_value(value, "bool"))
+        }
+    }
+}
+
+impl FromRedisValue for f64 {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        match value {
+            RedisValue::Int(x) => Ok(*x as f64),
+            RedisValue::BulkString(x) => {
+                String::from_utf8(x.clone())
+                    .map_err(to_conversion_error)?
+                    .parse::<f64>()
+                    .map_err(|_| conversion_error_from_value(x, "f64"))
+            }
+            _ => Err(conversion_error_from_value(value, "f64"))
+        }
+    }
+}
+
+impl FromRedisValue for () {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        match value {
+            RedisValue::Ok => Ok(()),
+            _ => Err(conversion_error_from_value(value, "()"))
+        }
+    }
+}
+
+impl<T: FromRedisValue> FromRedisValue for Option<T> {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        match value {
+            RedisValue::Nil => Ok(None),
+            _ => Ok(Some(from_redis_value(value)?))
+        }
+    }
+}
+
 impl<T: FromRedisValue> FromRedisValue for Vec<T> {
     fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
         match value {
@@ -149,6 +219,15 @@ impl<T: FromRedisValue> FromRedisValue for Vec<T> {
                 Ok(result)
             }
             RedisValue::Array(x) => {
+                // the array is flat if there are no nested arrays,
+                // a malformed nested array should fail instead of being reinterpreted
+                let is_flat = !x.iter().any(|val| matches!(val, RedisValue::Array(_)));
+                if is_flat {
+                    if let Some(result) = T::from_redis_flat_array(x) {
+                        return result;
+                    }
+                }
+
                 let mut result: Vec<T> = Vec::with_capacity(x.len());
                 for val in x.iter() {
                     match from_redis_value(val) {
@@ -192,10 +271,25 @@ impl<K: FromRedisValue + Eq + Hash, V: FromRedisValue> FromRedisValue for HashMa
     }
 }
 
+impl<T: FromRedisValue + Eq + Hash> FromRedisValue for HashSet<T> {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        match value {
+            RedisValue::Array(x) => {
+                let mut result = HashSet::with_capacity(x.len());
+                for val in x.iter() {
+                    result.insert(from_redis_value(val)?);
+                }
+                Ok(result)
+            }
+            _ => Err(conversion_error_from_value(value, "HashSet"))
+        }
+    }
+}
+
 // TODO make macro and implement that for (T, ..., T)
 impl<T1, T2> FromRedisValue for (T1, T2)
-    where T1: FromRedisValue + fmt::Debug,
-          T2: FromRedisValue + fmt::Debug {
+    where T1: FromRedisValue,
+          T2: FromRedisValue {
     fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
         let values: Vec<RedisValue> = from_redis_value(value)?;
         if values.len() != 2 {
@@ -211,11 +305,51 @@ impl<T1, T2> FromRedisValue for (T1, T2)
 
         Ok((first, second))
     }
+
+    fn from_redis_flat_array(values: &[RedisValue]) -> Option<RedisResult<Vec<Self>>> {
+        const PAIR_LEN: usize = 2;
+
+        let chunks = values.chunks_exact(PAIR_LEN);
+        if !chunks.remainder().is_empty() {
+            return Some(Err(conversion_error_from_value(&values, "Vec of pairs")));
+        }
+
+        let convert = || -> RedisResult<Vec<Self>> {
+            let mut result = Vec::with_capacity(values.len() / PAIR_LEN);
+            for chunk in chunks {
+                result.push((from_redis_value(&chunk[0])?, from_redis_value(&chunk[1])?));
+            }
+            Ok(result)
+        };
+        Some(convert())
+    }
+}
+
+impl<T1, T2, T3> FromRedisValue for (T1, T2, T3)
+    where T1: FromRedisValue,
+          T2: FromRedisValue,
+          T3: FromRedisValue {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        let values: Vec<RedisValue> = from_redis_value(value)?;
+        if values.len() != 3 {
+            return Err(
+                RedisError::new(
+                    RedisErrorKind::ParseError,
+                    format!("Couldn't convert the Redis value: \"{:?}\" to tuple of 3 elements",
+                            values)));
+        }
+
+        let first: T1 = from_redis_value(&values[0])?;
+        let second: T2 = from_redis_value(&values[1])?;
+        let third: T3 = from_redis_value(&values[2])?;
+
+        Ok((first, second, third))
+    }
 }
 
 fn to_conversion_error<T>(err: T) -> RedisError
     where T: Error {
-    RedisError::new(RedisErrorKind::IncorrectConversion, err.description().to_string())
+    RedisError::new(RedisErrorKind::IncorrectConversion, err.to_string())
 }
 
 fn conversion_error_from_value<T>(src_value: &T, dst_type: &str) -> RedisError
@@ -325,7 +459,7 @@ mod tests {
 
     #[test]
     fn test_from_int_value() {
-        let src: i64 = std::i64::MAX - 5;
+        let src: i64 = i64::MAX - 5;
         let val = RedisValue::Int(src);
         assert_eq!(src as i8, from_redis_value::<i8>(&val).unwrap());
         assert_eq!(src as u8, from_redis_value::<u8>(&val).unwrap());
@@ -333,7 +467,7 @@ mod tests {
         assert_eq!(src as u16, from_redis_value::<u16>(&val).unwrap());
         assert_eq!(src as i32, from_redis_value::<i32>(&val).unwrap());
         assert_eq!(src as u32, from_redis_value::<u32>(&val).unwrap());
-        assert_eq!(src as i64, from_redis_value::<i64>(&val).unwrap());
+        assert_eq!(src, from_redis_value::<i64>(&val).unwrap());
         assert_eq!(src as u64, from_redis_value::<u64>(&val).unwrap());
         assert!(from_redis_value::<String>(&val).is_err(), "expected Err");
         assert!(from_redis_value::<Vec<i64>>(&val).is_err(), "expected Err");
@@ -357,6 +491,71 @@ mod tests {
         assert_eq!(string_data.into_bytes(), from_redis_value::<Vec<u8>>(&val2).unwrap());
     }
 
+    #[test]
+    fn test_from_bool_value() {
+        assert!(from_redis_value::<bool>(&RedisValue::Int(1)).unwrap());
+        assert!(!from_redis_value::<bool>(&RedisValue::Int(0)).unwrap());
+        assert!(from_redis_value::<bool>(&RedisValue::Ok).unwrap());
+        assert!(!from_redis_value::<bool>(&RedisValue::Nil).unwrap());
+        assert!(from_redis_value::<bool>(&RedisValue::BulkString(b"1".to_vec())).is_err(), "expected Err");
+    }
+
+    #[test]
+    fn test_from_float_value() {
+        assert_eq!(10.5, from_redis_value::<f64>(&RedisValue::BulkString(b"10.5".to_vec())).unwrap());
+        assert_eq!(3.0, from_redis_value::<f64>(&RedisValue::Int(3)).unwrap());
+        assert!(from_redis_value::<f64>(&RedisValue::BulkString(b"1x".to_vec())).is_err(), "expected Err");
+        assert!(from_redis_value::<f64>(&RedisValue::Nil).is_err(), "expected Err");
+    }
+
+    #[test]
+    fn test_from_pairs_value() {
+        let flat = RedisValue::Array(
+            vec![RedisValue::BulkString(b"one".to_vec()), RedisValue::BulkString(b"1".to_vec()),
+                 RedisValue::BulkString(b"two".to_vec()), RedisValue::BulkString(b"2.5".to_vec())]);
+        let nested = RedisValue::Array(
+            vec![RedisValue::Array(vec![RedisValue::BulkString(b"one".to_vec()), RedisValue::Int(1)]),
+                 RedisValue::Array(vec![RedisValue::BulkString(b"two".to_vec()), RedisValue::Int(2)])]);
+
+        assert_eq!(vec![("one".to_string(), 1.0), ("two".to_string(), 2.5)],
+                   from_redis_value::<Vec<(String, f64)>>(&flat).unwrap());
+        assert_eq!(vec![("one".to_string(), 1), ("two".to_string(), 2)],
+                   from_redis_value::<Vec<(String, i64)>>(&nested).unwrap());
+        // the nested shape of the same pairs is converted as before
+        let nested = RedisValue::Array(
+            vec![RedisValue::Array(vec![RedisValue::BulkString(b"one".to_vec()), RedisValue::BulkString(b"1".to_vec())]),
+                 RedisValue::Array(vec![RedisValue::BulkString(b"two".to_vec()), RedisValue::BulkString(b"2.5".to_vec())])]);
+        assert_eq!(from_redis_value::<Vec<(String, f64)>>(&flat).unwrap(),
+                   from_redis_value::<Vec<(String, f64)>>(&nested).unwrap());
+        assert!(from_redis_value::<Vec<(String, f64)>>(
+            &RedisValue::Array(vec![RedisValue::BulkString(b"one".to_vec())])).is_err(), "expected Err");
+        // a malformed nested element is not reinterpreted as a flat array
+        let malformed = RedisValue::Array(
+            vec![RedisValue::Array(vec![RedisValue::BulkString(b"one".to_vec()), RedisValue::Int(1)]),
+                 RedisValue::Nil]);
+        assert!(from_redis_value::<Vec<(RedisValue, RedisValue)>>(&malformed).is_err(), "expected Err");
+
+        let mut set = HashSet::new();
+        set.insert("one".to_string());
+        set.insert("two".to_string());
+        let value = RedisValue::Array(
+            vec![RedisValue::BulkString(b"one".to_vec()), RedisValue::BulkString(b"two".to_vec()),
+                 RedisValue::BulkString(b"one".to_vec())]);
+        assert_eq!(set, from_redis_value::<HashSet<String>>(&value).unwrap());
+    }
+
+    #[test]
+    fn test_from_optional_value() {
+        assert_eq!(None, from_redis_value::<Option<i64>>(&RedisValue::Nil).unwrap());
+        assert_eq!(Some(5), from_redis_value::<Option<i64>>(&RedisValue::Int(5)).unwrap());
+        assert_eq!(Some(b"data".to_vec()),
+                   from_redis_value::<Option<Vec<u8>>>(&RedisValue::BulkString(b"data".to_vec())).unwrap());
+        assert!(from_redis_value::<Option<i64>>(&RedisValue::Ok).is_err(), "expected Err");
+
+        assert_eq!((), from_redis_value::<()>(&RedisValue::Ok).unwrap());
+        assert!(from_redis_value::<()>(&RedisValue::Nil).is_err(), "expected Err");
+    }
+
     #[test]
     fn test_from_array_value() {
         let data
@@ -0,0 +1,392 @@
+use super::{strings, keys, hashes, lists, sets, sorted_sets, SetOptions, ExpireCondition, Ttl,
+            KeyType, ListSide, ZAddOptions, ZRangeOptions};
+use crate::{RedisCoreConnection, TypedCommand, FromRedisValue, IntoRedisArgument, Query};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::time::Duration;
+
+
+/// Trait interface provides methods that send high-level commands
+/// and return the typed responses.
+/// Each method consumes the connection and returns it back within the `Query` future,
+/// so a connection blocked by BLPOP/BLMOVE/BZPOPMIN etc. cannot be used by other callers
+/// until the response comes.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::net::SocketAddr;
+/// use futures::Future;
+/// use redis_asio::RedisCoreConnection;
+/// use redis_asio::cmds::{Commands, SetOptions, SetExpiry, SetCondition};
+///
+/// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+/// let options = SetOptions::new()
+///     .with_condition(SetCondition::NotExists)
+///     .with_expiry(SetExpiry::Seconds(60));
+///
+/// let future = RedisCoreConnection::connect(address)
+///     .and_then(move |con| con.set_with("lock", "owner", options))
+///     .and_then(|(con, is_set)| {
+///         println!("the lock is acquired: {}", is_set);
+///         con.incr_by("counter", 10)
+///     })
+///     .map(|(_, counter)| println!("counter = {}", counter))
+///     .map_err(|err| eprintln!("something went wrong: {}", err));
+/// tokio::run(future);
+/// ```
+pub trait Commands: Sized {
+    /// Send a command that declares the type of its response.
+    fn send_command<C: TypedCommand>(self, cmd: C) -> Query<C::Output>;
+
+    /// GET key
+    fn get<K: IntoRedisArgument>(self, key: K) -> Query<Option<Vec<u8>>> {
+        self.send_command(strings::get(key))
+    }
+
+    /// SET key value
+    fn set<K, V>(self, key: K, value: V) -> Query<()>
+        where K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        self.send_command(strings::set(key, value))
+    }
+
+    /// SET key value [NX|XX] [EX|PX|EXAT|PXAT|KEEPTTL],
+    /// the response is false if the key was not set because of the condition.
+    fn set_with<K, V>(self, key: K, value: V, options: SetOptions) -> Query<bool>
+        where K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        self.send_command(strings::set_with(key, value, options))
+    }
+
+    /// SET key value [NX|XX] GET [EX|PX|EXAT|PXAT|KEEPTTL],
+    /// the response is the old value.
+    fn set_get<K, V>(self, key: K, value: V, options: SetOptions) -> Query<Option<Vec<u8>>>
+        where K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        self.send_command(strings::set_get(key, value, options))
+    }
+
+    /// MGET key [key ...]
+    fn mget<K: IntoRedisArgument>(self, keys: Vec<K>) -> Query<Vec<Option<Vec<u8>>>> {
+        self.send_command(strings::mget(keys))
+    }
+
+    /// MSET key value [key value ...]
+    fn mset<K, V>(self, key_values: Vec<(K, V)>) -> Query<()>
+        where K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        self.send_command(strings::mset(key_values))
+    }
+
+    /// INCR key
+    fn incr<K: IntoRedisArgument>(self, key: K) -> Query<i64> {
+        self.send_command(strings::incr(key))
+    }
+
+    /// INCRBY key increment
+    fn incr_by<K: IntoRedisArgument>(self, key: K, increment: i64) -> Query<i64> {
+        self.send_command(strings::incr_by(key, increment))
+    }
+
+    /// INCRBYFLOAT key increment
+    fn incr_by_float<K: IntoRedisArgument>(self, key: K, increment: f64) -> Query<f64> {
+        self.send_command(strings::incr_by_float(key, increment))
+    }
+
+    /// DEL key [key ...]
+    fn del<K: IntoRedisArgument>(self, keys: Vec<K>) -> Query<i64> {
+        self.send_command(keys::del(keys))
+    }
+
+    /// UNLINK key [key ...]
+    fn unlink<K: IntoRedisArgument>(self, keys: Vec<K>) -> Query<i64> {
+        self.send_command(keys::unlink(keys))
+    }
+
+    /// EXISTS key [key ...]
+    fn exists<K: IntoRedisArgument>(self, keys: Vec<K>) -> Query<i64> {
+        self.send_command(keys::exists(keys))
+    }
+
+    /// EXPIRE key seconds
+    fn expire<K: IntoRedisArgument>(self, key: K, seconds: u64) -> Query<bool> {
+        self.send_command(keys::expire(key, seconds))
+    }
+
+    /// EXPIRE key seconds NX|XX|GT|LT
+    fn expire_with<K>(self, key: K, seconds: u64, condition: ExpireCondition) -> Query<bool>
+        where K: IntoRedisArgument {
+        self.send_command(keys::expire_with(key, seconds, condition))
+    }
+
+    /// PEXPIRE key milliseconds
+    fn pexpire<K: IntoRedisArgument>(self, key: K, milliseconds: u64) -> Query<bool> {
+        self.send_command(keys::pexpire(key, milliseconds))
+    }
+
+    /// PEXPIRE key milliseconds NX|XX|GT|LT
+    fn pexpire_with<K>(self, key: K, milliseconds: u64, condition: ExpireCondition) -> Query<bool>
+        where K: IntoRedisArgument {
+        self.send_command(keys::pexpire_with(key, milliseconds, condition))
+    }
+
+    /// TTL key, the response is in seconds
+    fn ttl<K: IntoRedisArgument>(self, key: K) -> Query<Ttl> {
+        self.send_command(keys::ttl(key))
+    }
+
+    /// PTTL key, the response is in milliseconds
+    fn pttl<K: IntoRedisArgument>(self, key: K) -> Query<Ttl> {
+        self.send_command(keys::pttl(key))
+    }
+
+    /// PERSIST key
+    fn persist<K: IntoRedisArgument>(self, key: K) -> Query<bool> {
+        self.send_command(keys::persist(key))
+    }
+
+    /// RENAME key newkey
+    fn rename<K, N>(self, key: K, new_key: N) -> Query<()>
+        where K: IntoRedisArgument,
+              N: IntoRedisArgument {
+        self.send_command(keys::rename(key, new_key))
+    }
+
+    /// TYPE key
+    fn key_type<K: IntoRedisArgument>(self, key: K) -> Query<KeyType> {
+        self.send_command(keys::key_type(key))
+    }
+
+    /// HSET key field value [field value ...]
+    fn hset<K, F, V>(self, key: K, field_values: Vec<(F, V)>) -> Query<i64>
+        where K: IntoRedisArgument,
+              F: IntoRedisArgument,
+              V: IntoRedisArgument {
+        self.send_command(hashes::hset(key, field_values))
+    }
+
+    /// HGET key field
+    fn hget<K, F, T>(self, key: K, field: F) -> Query<Option<T>>
+        where K: IntoRedisArgument,
+              F: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(hashes::hget(key, field))
+    }
+
+    /// HGETALL key
+    fn hgetall<K, F, V>(self, key: K) -> Query<HashMap<F, V>>
+        where K: IntoRedisArgument,
+              F: FromRedisValue + Eq + Hash,
+              V: FromRedisValue {
+        self.send_command(hashes::hgetall(key))
+    }
+
+    /// HDEL key field [field ...]
+    fn hdel<K, F>(self, key: K, fields: Vec<F>) -> Query<i64>
+        where K: IntoRedisArgument,
+              F: IntoRedisArgument {
+        self.send_command(hashes::hdel(key, fields))
+    }
+
+    /// HINCRBY key field increment
+    fn hincr_by<K, F>(self, key: K, field: F, increment: i64) -> Query<i64>
+        where K: IntoRedisArgument,
+              F: IntoRedisArgument {
+        self.send_command(hashes::hincr_by(key, field, increment))
+    }
+
+    /// LPUSH key element [element ...]
+    fn lpush<K, V>(self, key: K, elements: Vec<V>) -> Query<i64>
+        where K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        self.send_command(lists::lpush(key, elements))
+    }
+
+    /// RPUSH key element [element ...]
+    fn rpush<K, V>(self, key: K, elements: Vec<V>) -> Query<i64>
+        where K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        self.send_command(lists::rpush(key, elements))
+    }
+
+    /// LPOP key
+    fn lpop<K, T>(self, key: K) -> Query<Option<T>>
+        where K: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(lists::lpop(key))
+    }
+
+    /// RPOP key
+    fn rpop<K, T>(self, key: K) -> Query<Option<T>>
+        where K: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(lists::rpop(key))
+    }
+
+    /// LRANGE key start stop
+    fn lrange<K, T>(self, key: K, start: i64, stop: i64) -> Query<Vec<T>>
+        where K: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(lists::lrange(key, start, stop))
+    }
+
+    /// LLEN key
+    fn llen<K: IntoRedisArgument>(self, key: K) -> Query<i64> {
+        self.send_command(lists::llen(key))
+    }
+
+    /// LMOVE source destination LEFT|RIGHT LEFT|RIGHT
+    fn lmove<S, D, T>(self, source: S, destination: D, from: ListSide, to: ListSide)
+                      -> Query<Option<T>>
+        where S: IntoRedisArgument,
+              D: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(lists::lmove(source, destination, from, to))
+    }
+
+    /// LREM key count element
+    fn lrem<K, V>(self, key: K, count: i64, element: V) -> Query<i64>
+        where K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        self.send_command(lists::lrem(key, count, element))
+    }
+
+    /// BLPOP key [key ...] timeout
+    fn blpop<K, T>(self, keys: Vec<K>, timeout: Duration) -> Query<Option<(String, T)>>
+        where K: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(lists::blpop(keys, timeout))
+    }
+
+    /// BRPOP key [key ...] timeout
+    fn brpop<K, T>(self, keys: Vec<K>, timeout: Duration) -> Query<Option<(String, T)>>
+        where K: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(lists::brpop(keys, timeout))
+    }
+
+    /// BLMOVE source destination LEFT|RIGHT LEFT|RIGHT timeout
+    fn blmove<S, D, T>(self, source: S, destination: D, from: ListSide, to: ListSide,
+                       timeout: Duration) -> Query<Option<T>>
+        where S: IntoRedisArgument,
+              D: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(lists::blmove(source, destination, from, to, timeout))
+    }
+
+    /// SADD key member [member ...]
+    fn sadd<K, M>(self, key: K, members: Vec<M>) -> Query<i64>
+        where K: IntoRedisArgument,
+              M: IntoRedisArgument {
+        self.send_command(sets::sadd(key, members))
+    }
+
+    /// SREM key member [member ...]
+    fn srem<K, M>(self, key: K, members: Vec<M>) -> Query<i64>
+        where K: IntoRedisArgument,
+              M: IntoRedisArgument {
+        self.send_command(sets::srem(key, members))
+    }
+
+    /// SMEMBERS key
+    fn smembers<K, T>(self, key: K) -> Query<HashSet<T>>
+        where K: IntoRedisArgument,
+              T: FromRedisValue + Eq + Hash {
+        self.send_command(sets::smembers(key))
+    }
+
+    /// SISMEMBER key member
+    fn sismember<K, M>(self, key: K, member: M) -> Query<bool>
+        where K: IntoRedisArgument,
+              M: IntoRedisArgument {
+        self.send_command(sets::sismember(key, member))
+    }
+
+    /// SINTER key [key ...]
+    fn sinter<K, T>(self, keys: Vec<K>) -> Query<HashSet<T>>
+        where K: IntoRedisArgument,
+              T: FromRedisValue + Eq + Hash {
+        self.send_command(sets::sinter(keys))
+    }
+
+    /// SCARD key
+    fn scard<K: IntoRedisArgument>(self, key: K) -> Query<i64> {
+        self.send_command(sets::scard(key))
+    }
+
+    /// ZADD key score member [score member ...]
+    fn zadd<K, M>(self, key: K, members: Vec<(f64, M)>) -> Query<i64>
+        where K: IntoRedisArgument,
+              M: IntoRedisArgument {
+        self.send_command(sorted_sets::zadd(key, members))
+    }
+
+    /// ZADD key [NX|XX] [GT|LT] [CH] score member [score member ...]
+    fn zadd_with<K, M>(self, key: K, members: Vec<(f64, M)>, options: ZAddOptions) -> Query<i64>
+        where K: IntoRedisArgument,
+              M: IntoRedisArgument {
+        self.send_command(sorted_sets::zadd_with(key, members, options))
+    }
+
+    /// ZADD key [NX|XX] [GT|LT] [CH] INCR increment member,
+    /// the response is the new score of the member.
+    fn zadd_incr<K, M>(self, key: K, increment: f64, member: M, options: ZAddOptions)
+                       -> Query<Option<f64>>
+        where K: IntoRedisArgument,
+              M: IntoRedisArgument {
+        self.send_command(sorted_sets::zadd_incr(key, increment, member, options))
+    }
+
+    /// ZRANGE key start stop [BYSCORE|BYLEX] [REV] [LIMIT offset count]
+    fn zrange<K, T>(self, key: K, options: ZRangeOptions) -> Query<Vec<T>>
+        where K: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(sorted_sets::zrange(key, options))
+    }
+
+    /// ZRANGE key start stop [BYSCORE|BYLEX] [REV] [LIMIT offset count] WITHSCORES
+    fn zrange_with_scores<K, T>(self, key: K, options: ZRangeOptions) -> Query<Vec<(T, f64)>>
+        where K: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(sorted_sets::zrange_with_scores(key, options))
+    }
+
+    /// ZPOPMIN key count
+    fn zpopmin<K, T>(self, key: K, count: u64) -> Query<Vec<(T, f64)>>
+        where K: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(sorted_sets::zpopmin(key, count))
+    }
+
+    /// BZPOPMIN key [key ...] timeout
+    fn bzpopmin<K, T>(self, keys: Vec<K>, timeout: Duration) -> Query<Option<(String, T, f64)>>
+        where K: IntoRedisArgument,
+              T: FromRedisValue {
+        self.send_command(sorted_sets::bzpopmin(keys, timeout))
+    }
+
+    /// ZSCORE key member
+    fn zscore<K, M>(self, key: K, member: M) -> Query<Option<f64>>
+        where K: IntoRedisArgument,
+              M: IntoRedisArgument {
+        self.send_command(sorted_sets::zscore(key, member))
+    }
+
+    /// ZREM key member [member ...]
+    fn zrem<K, M>(self, key: K, members: Vec<M>) -> Query<i64>
+        where K: IntoRedisArgument,
+              M: IntoRedisArgument {
+        self.send_command(sorted_sets::zrem(key, members))
+    }
+
+    /// ZCARD key
+    fn zcard<K: IntoRedisArgument>(self, key: K) -> Query<i64> {
+        self.send_command(sorted_sets::zcard(key))
+    }
+}
+
+impl Commands for RedisCoreConnection {
+    fn send_command<C: TypedCommand>(self, cmd: C) -> Query<C::Output> {
+        self.send_typed(cmd)
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{RedisTypedCommand, FromRedisValue, IntoRedisArgument, command};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+
+/// Make "HSET key field value [field value ...]" command,
+/// the response is the number of fields that were added.
+pub fn hset<K, F, V>(key: K, field_values: Vec<(F, V)>) -> RedisTypedCommand<i64>
+    where K: IntoRedisArgument,
+          F: IntoRedisArgument,
+          V: IntoRedisArgument {
+    let mut cmd = command("HSET").arg(key);
+    for (field, value) in field_values.into_iter() {
+        cmd.arg_mut(field);
+        cmd.arg_mut(value);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "HGET key field" command,
+/// the response is None if the field or the key does not exist.
+pub fn hget<K, F, T>(key: K, field: F) -> RedisTypedCommand<Option<T>>
+    where K: IntoRedisArgument,
+          F: IntoRedisArgument,
+          T: FromRedisValue {
+    RedisTypedCommand::new(command("HGET").arg(key).arg(field))
+}
+
+/// Make "HGETALL key" command,
+/// the response is empty if the key does not exist.
+pub fn hgetall<K, F, V>(key: K) -> RedisTypedCommand<HashMap<F, V>>
+    where K: IntoRedisArgument,
+          F: FromRedisValue + Eq + Hash,
+          V: FromRedisValue {
+    RedisTypedCommand::new(command("HGETALL").arg(key))
+}
+
+/// Make "HDEL key field [field ...]" command,
+/// the response is the number of fields that were removed.
+pub fn hdel<K, F>(key: K, fields: Vec<F>) -> RedisTypedCommand<i64>
+    where K: IntoRedisArgument,
+          F: IntoRedisArgument {
+    let mut cmd = command("HDEL").arg(key);
+    for field in fields.into_iter() {
+        cmd.arg_mut(field);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "HINCRBY key field increment" command,
+/// the response is the value of the field after the increment.
+pub fn hincr_by<K, F>(key: K, field: F, increment: i64) -> RedisTypedCommand<i64>
+    where K: IntoRedisArgument,
+          F: IntoRedisArgument {
+    RedisTypedCommand::new(command("HINCRBY").arg(key).arg(field).arg(increment))
+}
@@ -0,0 +1,163 @@
+use crate::{RedisTypedCommand, RedisValue, RedisResult, RedisError, RedisErrorKind,
+            FromRedisValue, IntoRedisArgument, command, from_redis_value};
+
+
+/// Condition of the EXPIRE/PEXPIRE commands.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ExpireCondition {
+    /// NX - set expiry only when the key has no expiry
+    NoExpiry,
+    /// XX - set expiry only when the key has an existing expiry
+    HasExpiry,
+    /// GT - set expiry only when the new expiry is greater than current one
+    Greater,
+    /// LT - set expiry only when the new expiry is less than current one
+    Less,
+}
+
+/// Structure that wraps a response on TTL/PTTL request.
+/// The time unit of the `Expires` value depends on the command:
+/// seconds for TTL and milliseconds for PTTL.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Ttl {
+    /// The key does not exist
+    NotExists,
+    /// The key exists but has no associated expire
+    Persistent,
+    /// Remaining time to live of the key
+    Expires(u64),
+}
+
+/// Structure that wraps a response on TYPE request.
+#[derive(Clone, PartialEq, Debug)]
+pub enum KeyType {
+    /// The key does not exist
+    None,
+    String,
+    List,
+    Set,
+    ZSet,
+    Hash,
+    Stream,
+    /// Type of a module key
+    Other(String),
+}
+
+/// Make "DEL key [key ...]" command, the response is the number of removed keys.
+pub fn del<K: IntoRedisArgument>(keys: Vec<K>) -> RedisTypedCommand<i64> {
+    let mut cmd = command("DEL");
+    for key in keys.into_iter() {
+        cmd.arg_mut(key);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "UNLINK key [key ...]" command, the response is the number of unlinked keys.
+pub fn unlink<K: IntoRedisArgument>(keys: Vec<K>) -> RedisTypedCommand<i64> {
+    let mut cmd = command("UNLINK");
+    for key in keys.into_iter() {
+        cmd.arg_mut(key);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "EXISTS key [key ...]" command, the response is the number of existing keys.
+pub fn exists<K: IntoRedisArgument>(keys: Vec<K>) -> RedisTypedCommand<i64> {
+    let mut cmd = command("EXISTS");
+    for key in keys.into_iter() {
+        cmd.arg_mut(key);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "EXPIRE key seconds" command,
+/// the response is false if the key does not exist.
+pub fn expire<K: IntoRedisArgument>(key: K, seconds: u64) -> RedisTypedCommand<bool> {
+    RedisTypedCommand::new(command("EXPIRE").arg(key).arg(seconds))
+}
+
+/// Make "EXPIRE key seconds NX|XX|GT|LT" command,
+/// the response is false if the key does not exist or the condition is not met.
+pub fn expire_with<K>(key: K, seconds: u64, condition: ExpireCondition) -> RedisTypedCommand<bool>
+    where K: IntoRedisArgument {
+    RedisTypedCommand::new(command("EXPIRE").arg(key).arg(seconds).arg(condition_arg(condition)))
+}
+
+/// Make "PEXPIRE key milliseconds" command,
+/// the response is false if the key does not exist.
+pub fn pexpire<K: IntoRedisArgument>(key: K, milliseconds: u64) -> RedisTypedCommand<bool> {
+    RedisTypedCommand::new(command("PEXPIRE").arg(key).arg(milliseconds))
+}
+
+/// Make "PEXPIRE key milliseconds NX|XX|GT|LT" command,
+/// the response is false if the key does not exist or the condition is not met.
+pub fn pexpire_with<K>(key: K, milliseconds: u64, condition: ExpireCondition) -> RedisTypedCommand<bool>
+    where K: IntoRedisArgument {
+    RedisTypedCommand::new(
+        command("PEXPIRE").arg(key).arg(milliseconds).arg(condition_arg(condition)))
+}
+
+/// Make "TTL key" command, the response is in seconds.
+pub fn ttl<K: IntoRedisArgument>(key: K) -> RedisTypedCommand<Ttl> {
+    RedisTypedCommand::new(command("TTL").arg(key))
+}
+
+/// Make "PTTL key" command, the response is in milliseconds.
+pub fn pttl<K: IntoRedisArgument>(key: K) -> RedisTypedCommand<Ttl> {
+    RedisTypedCommand::new(command("PTTL").arg(key))
+}
+
+/// Make "PERSIST key" command,
+/// the response is false if the key does not exist or has no associated timeout.
+pub fn persist<K: IntoRedisArgument>(key: K) -> RedisTypedCommand<bool> {
+    RedisTypedCommand::new(command("PERSIST").arg(key))
+}
+
+/// Make "RENAME key newkey" command.
+pub fn rename<K, N>(key: K, new_key: N) -> RedisTypedCommand<()>
+    where K: IntoRedisArgument,
+          N: IntoRedisArgument {
+    RedisTypedCommand::new(command("RENAME").arg(key).arg(new_key))
+}
+
+/// Make "TYPE key" command.
+pub fn key_type<K: IntoRedisArgument>(key: K) -> RedisTypedCommand<KeyType> {
+    RedisTypedCommand::new(command("TYPE").arg(key))
+}
+
+fn condition_arg(condition: ExpireCondition) -> &'static str {
+    match condition {
+        ExpireCondition::NoExpiry => "NX",
+        ExpireCondition::HasExpiry => "XX",
+        ExpireCondition::Greater => "GT",
+        ExpireCondition::Less => "LT",
+    }
+}
+
+impl FromRedisValue for Ttl {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        match value {
+            RedisValue::Int(-2) => Ok(Ttl::NotExists),
+            RedisValue::Int(-1) => Ok(Ttl::Persistent),
+            RedisValue::Int(x) if *x >= 0 => Ok(Ttl::Expires(*x as u64)),
+            _ => Err(RedisError::new(RedisErrorKind::ParseError,
+                                     format!("Couldn't parse a TTL from {:?}", value)))
+        }
+    }
+}
+
+impl FromRedisValue for KeyType {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        let key_type: String = from_redis_value(value)?;
+        Ok(match key_type.as_str() {
+            "none" => KeyType::None,
+            "string" => KeyType::String,
+            "list" => KeyType::List,
+            "set" => KeyType::Set,
+            "zset" => KeyType::ZSet,
+            "hash" => KeyType::Hash,
+            "stream" => KeyType::Stream,
+            _ => KeyType::Other(key_type),
+        })
+    }
+}
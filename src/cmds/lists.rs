@@ -0,0 +1,143 @@
+use crate::{RedisTypedCommand, FromRedisValue, IntoRedisArgument, command};
+use std::time::Duration;
+
+
+/// Side of a list used by the LMOVE command.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ListSide {
+    /// LEFT - the head of the list
+    Left,
+    /// RIGHT - the tail of the list
+    Right,
+}
+
+/// Make "LPUSH key element [element ...]" command,
+/// the response is the length of the list after the push.
+pub fn lpush<K, V>(key: K, elements: Vec<V>) -> RedisTypedCommand<i64>
+    where K: IntoRedisArgument,
+          V: IntoRedisArgument {
+    let mut cmd = command("LPUSH").arg(key);
+    for element in elements.into_iter() {
+        cmd.arg_mut(element);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "RPUSH key element [element ...]" command,
+/// the response is the length of the list after the push.
+pub fn rpush<K, V>(key: K, elements: Vec<V>) -> RedisTypedCommand<i64>
+    where K: IntoRedisArgument,
+          V: IntoRedisArgument {
+    let mut cmd = command("RPUSH").arg(key);
+    for element in elements.into_iter() {
+        cmd.arg_mut(element);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "LPOP key" command, the response is None if the list is empty.
+pub fn lpop<K, T>(key: K) -> RedisTypedCommand<Option<T>>
+    where K: IntoRedisArgument,
+          T: FromRedisValue {
+    RedisTypedCommand::new(command("LPOP").arg(key))
+}
+
+/// Make "RPOP key" command, the response is None if the list is empty.
+pub fn rpop<K, T>(key: K) -> RedisTypedCommand<Option<T>>
+    where K: IntoRedisArgument,
+          T: FromRedisValue {
+    RedisTypedCommand::new(command("RPOP").arg(key))
+}
+
+/// Make "LRANGE key start stop" command.
+/// The `start` and `stop` are zero-based indexes, negative ones are offsets from the tail.
+pub fn lrange<K, T>(key: K, start: i64, stop: i64) -> RedisTypedCommand<Vec<T>>
+    where K: IntoRedisArgument,
+          T: FromRedisValue {
+    RedisTypedCommand::new(command("LRANGE").arg(key).arg(start).arg(stop))
+}
+
+/// Make "LLEN key" command.
+pub fn llen<K: IntoRedisArgument>(key: K) -> RedisTypedCommand<i64> {
+    RedisTypedCommand::new(command("LLEN").arg(key))
+}
+
+/// Make "LMOVE source destination LEFT|RIGHT LEFT|RIGHT" command,
+/// the response is the moved element or None if the source list is empty.
+pub fn lmove<S, D, T>(source: S, destination: D, from: ListSide, to: ListSide)
+                      -> RedisTypedCommand<Option<T>>
+    where S: IntoRedisArgument,
+          D: IntoRedisArgument,
+          T: FromRedisValue {
+    RedisTypedCommand::new(
+        command("LMOVE").arg(source).arg(destination).arg(side_arg(from)).arg(side_arg(to)))
+}
+
+/// Make "BLPOP key [key ...] timeout" command,
+/// the response is the pair of the key name and the popped element
+/// or None if the timeout has expired. The zero `timeout` blocks forever.
+///
+/// Note the connection is occupied until the response comes,
+/// other requests can be sent only after the future has been resolved.
+pub fn blpop<K, T>(keys: Vec<K>, timeout: Duration) -> RedisTypedCommand<Option<(String, T)>>
+    where K: IntoRedisArgument,
+          T: FromRedisValue {
+    let mut cmd = command("BLPOP");
+    for key in keys.into_iter() {
+        cmd.arg_mut(key);
+    }
+    RedisTypedCommand::new(cmd.arg(timeout_arg(timeout)))
+}
+
+/// Make "BRPOP key [key ...] timeout" command,
+/// the response is the pair of the key name and the popped element
+/// or None if the timeout has expired. The zero `timeout` blocks forever.
+///
+/// Note the connection is occupied until the response comes,
+/// other requests can be sent only after the future has been resolved.
+pub fn brpop<K, T>(keys: Vec<K>, timeout: Duration) -> RedisTypedCommand<Option<(String, T)>>
+    where K: IntoRedisArgument,
+          T: FromRedisValue {
+    let mut cmd = command("BRPOP");
+    for key in keys.into_iter() {
+        cmd.arg_mut(key);
+    }
+    RedisTypedCommand::new(cmd.arg(timeout_arg(timeout)))
+}
+
+/// Make "BLMOVE source destination LEFT|RIGHT LEFT|RIGHT timeout" command,
+/// the response is the moved element or None if the timeout has expired.
+/// The zero `timeout` blocks forever.
+///
+/// Note the connection is occupied until the response comes,
+/// other requests can be sent only after the future has been resolved.
+pub fn blmove<S, D, T>(source: S, destination: D, from: ListSide, to: ListSide, timeout: Duration)
+                       -> RedisTypedCommand<Option<T>>
+    where S: IntoRedisArgument,
+          D: IntoRedisArgument,
+          T: FromRedisValue {
+    RedisTypedCommand::new(
+        command("BLMOVE").arg(source).arg(destination).arg(side_arg(from)).arg(side_arg(to))
+            .arg(timeout_arg(timeout)))
+}
+
+/// Make "LREM key count element" command,
+/// the response is the number of removed elements.
+/// The `count` > 0 removes elements from the head, < 0 from the tail, 0 removes all of them.
+pub fn lrem<K, V>(key: K, count: i64, element: V) -> RedisTypedCommand<i64>
+    where K: IntoRedisArgument,
+          V: IntoRedisArgument {
+    RedisTypedCommand::new(command("LREM").arg(key).arg(count).arg(element))
+}
+
+/// Timeout of the blocking commands is specified in seconds with a fractional part.
+pub(crate) fn timeout_arg(timeout: Duration) -> f64 {
+    timeout.as_secs_f64()
+}
+
+pub(crate) fn side_arg(side: ListSide) -> &'static str {
+    match side {
+        ListSide::Left => "LEFT",
+        ListSide::Right => "RIGHT",
+    }
+}
@@ -0,0 +1,248 @@
+//! Commands module that contains constructors of `RedisTypedCommand`s.
+//! Each constructor declares the type of the response on the command,
+//! so the response is converted by `RedisCoreConnection::send_typed()`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::net::SocketAddr;
+//! use futures::Future;
+//! use redis_asio::RedisCoreConnection;
+//! use redis_asio::cmds;
+//!
+//! let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+//!
+//! let future = RedisCoreConnection::connect(address)
+//!     .and_then(|con| con.send_typed(cmds::set("foo", "bar")))
+//!     .and_then(|(con, ())| con.send_typed(cmds::get("foo")))
+//!     .map(|(_, value): (_, Option<Vec<u8>>)| {
+//!         assert_eq!(Some(b"bar".to_vec()), value);
+//!     })
+//!     .map_err(|err| eprintln!("something went wrong: {}", err));
+//! tokio::run(future);
+//! ```
+
+mod strings;
+mod keys;
+mod hashes;
+mod lists;
+mod sets;
+mod sorted_sets;
+mod commands;
+
+pub use strings::{SetOptions, SetCondition, SetExpiry, get, set, set_with, set_get, mget, mset,
+                  incr, incr_by, incr_by_float};
+pub use keys::{ExpireCondition, Ttl, KeyType, del, unlink, exists, expire, expire_with, pexpire,
+               pexpire_with, ttl, pttl, persist, rename, key_type};
+pub use hashes::{hset, hget, hgetall, hdel, hincr_by};
+pub use lists::{ListSide, lpush, rpush, lpop, rpop, lrange, llen, lmove, lrem, blpop, brpop, blmove};
+pub use sets::{sadd, srem, smembers, sismember, sinter, scard};
+pub use sorted_sets::{ZAddOptions, ZAddCondition, ZAddComparison, ZRangeOptions, ZRange, ScoreBound,
+                      LexBound, zadd, zadd_with, zadd_incr, zrange, zrange_with_scores, zpopmin,
+                      bzpopmin, zscore, zrem, zcard};
+pub use commands::Commands;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RedisCoreConnection;
+    use crate::mock::MockServer;
+    use futures::Future;
+    use tokio::runtime::Runtime;
+
+    #[test]
+    fn test_send_typed() {
+        let server = MockServer::start().unwrap();
+
+        let future = RedisCoreConnection::connect(&server.address())
+            .and_then(|con| con.send_typed(get("foo")))
+            .and_then(|(con, value)| {
+                assert_eq!(None, value);
+                con.send_typed(set("foo", "bar"))
+            })
+            .and_then(|(con, ())| con.send_typed(get("foo")))
+            .and_then(|(con, value)| {
+                assert_eq!(Some(b"bar".to_vec()), value);
+                con.send_typed(incr("counter"))
+            })
+            .and_then(|(con, value)| {
+                assert_eq!(1, value);
+                con.send_typed(exists(vec!["foo", "counter", "unknown"]))
+            })
+            .and_then(|(con, value)| {
+                assert_eq!(2, value);
+                con.send_typed(del(vec!["foo", "counter"]))
+            })
+            .map(|(_, value)| assert_eq!(2, value));
+        Runtime::new().unwrap().block_on(future).unwrap();
+    }
+
+    #[test]
+    fn test_commands() {
+        use crate::{RedisValue, command};
+
+        let server = MockServer::start().unwrap();
+        server.expect(command("SET").arg("lock").arg("Bob").arg("NX").arg("PX").arg(100),
+                      RedisValue::Nil);
+        server.expect(command("SET").arg("foo").arg("bar").arg("XX").arg("GET").arg("KEEPTTL"),
+                      RedisValue::BulkString(b"old".to_vec()));
+        server.expect(command("INCRBYFLOAT").arg("float").arg("1.5"),
+                      RedisValue::BulkString(b"2.5".to_vec()));
+        server.expect(command("EXPIRE").arg("foo").arg(10).arg("GT"), RedisValue::Int(1));
+        server.expect(command("TTL").arg("foo"), RedisValue::Int(10));
+        server.expect(command("PTTL").arg("unknown"), RedisValue::Int(-2));
+        server.expect(command("TYPE").arg("foo"), RedisValue::Status("string".to_string()));
+
+        let lock_options = SetOptions::new()
+            .with_condition(SetCondition::NotExists)
+            .with_expiry(SetExpiry::Milliseconds(100));
+        let get_options = SetOptions::new()
+            .with_condition(SetCondition::Exists)
+            .with_expiry(SetExpiry::KeepTtl);
+
+        let future = RedisCoreConnection::connect(&server.address())
+            .and_then(move |con| con.set_with("lock", "Bob", lock_options))
+            .and_then(move |(con, is_set)| {
+                assert!(!is_set);
+                con.set_get("foo", "bar", get_options)
+            })
+            .and_then(|(con, old)| {
+                assert_eq!(Some(b"old".to_vec()), old);
+                con.incr_by_float("float", 1.5)
+            })
+            .and_then(|(con, value)| {
+                assert_eq!(2.5, value);
+                con.expire_with("foo", 10, ExpireCondition::Greater)
+            })
+            .and_then(|(con, is_set)| {
+                assert!(is_set);
+                con.ttl("foo")
+            })
+            .and_then(|(con, ttl)| {
+                assert_eq!(Ttl::Expires(10), ttl);
+                con.pttl("unknown")
+            })
+            .and_then(|(con, ttl)| {
+                assert_eq!(Ttl::NotExists, ttl);
+                con.key_type("foo")
+            })
+            .map(|(_, key_type)| assert_eq!(KeyType::String, key_type));
+        Runtime::new().unwrap().block_on(future).unwrap();
+        assert!(server.is_satisfied());
+    }
+
+    #[test]
+    fn test_collections() {
+        use crate::{RedisValue, command};
+        use std::collections::HashMap;
+
+        let bulk = |value: &str| RedisValue::BulkString(value.as_bytes().to_vec());
+
+        let server = MockServer::start().unwrap();
+        server.expect(command("LMOVE").arg("queue").arg("processing").arg("RIGHT").arg("LEFT"),
+                      bulk("job"));
+        server.expect(command("SINTER").arg("a").arg("b"),
+                      RedisValue::Array(vec![bulk("x"), bulk("y")]));
+        server.expect(command("ZADD").arg("z").arg("XX").arg("GT").arg("CH").arg("1.5").arg("x"),
+                      RedisValue::Int(1));
+        server.expect(command("ZADD").arg("z").arg("NX").arg("INCR").arg("2").arg("y"),
+                      RedisValue::Nil);
+        server.expect(command("ZRANGE").arg("z").arg("+inf").arg("(1").arg("BYSCORE").arg("REV")
+                          .arg("LIMIT").arg(0).arg(2).arg("WITHSCORES"),
+                      RedisValue::Array(vec![bulk("y"), bulk("3"), bulk("x"), bulk("1.5")]));
+        server.expect(command("ZRANGE").arg("z").arg("[a").arg("-").arg("BYLEX").arg("REV"),
+                      RedisValue::Array(vec![bulk("a")]));
+        server.expect(command("ZPOPMIN").arg("z").arg(1),
+                      RedisValue::Array(vec![bulk("x"), bulk("1.5")]));
+
+        let zadd_options = ZAddOptions::new()
+            .with_condition(ZAddCondition::Exists)
+            .with_comparison(ZAddComparison::Greater)
+            .with_changed();
+        let incr_options = ZAddOptions::new().with_condition(ZAddCondition::NotExists);
+        let score_range = ZRangeOptions::by_score(ScoreBound::Exclusive(1.0), ScoreBound::PosInf)
+            .reversed()
+            .with_limit(0, 2);
+        let lex_range = ZRangeOptions::by_lex(LexBound::Min, LexBound::Inclusive(b"a".to_vec()))
+            .reversed();
+
+        let future = RedisCoreConnection::connect(&server.address())
+            .and_then(|con| con.hset("hash", vec![("a", 1), ("b", 2)]))
+            .and_then(|(con, added)| {
+                assert_eq!(2, added);
+                con.hincr_by("hash", "a", 10)
+            })
+            .and_then(|(con, value)| {
+                assert_eq!(11, value);
+                con.hgetall("hash")
+            })
+            .and_then(|(con, hash): (_, HashMap<String, i64>)| {
+                assert_eq!(2, hash.len());
+                assert_eq!(11, hash["a"]);
+                assert_eq!(2, hash["b"]);
+                con.lmove("queue", "processing", ListSide::Right, ListSide::Left)
+            })
+            .and_then(|(con, job): (_, Option<String>)| {
+                assert_eq!(Some("job".to_string()), job);
+                con.sinter(vec!["a", "b"])
+            })
+            .and_then(move |(con, members): (_, std::collections::HashSet<String>)| {
+                assert_eq!(2, members.len());
+                assert!(members.contains("x") && members.contains("y"));
+                con.zadd_with("z", vec![(1.5, "x")], zadd_options)
+            })
+            .and_then(move |(con, changed)| {
+                assert_eq!(1, changed);
+                con.zadd_incr("z", 2.0, "y", incr_options)
+            })
+            .and_then(move |(con, score)| {
+                assert_eq!(None, score);
+                con.zrange_with_scores("z", score_range)
+            })
+            .and_then(move |(con, members): (_, Vec<(String, f64)>)| {
+                assert_eq!(vec![("y".to_string(), 3.0), ("x".to_string(), 1.5)], members);
+                con.zrange("z", lex_range)
+            })
+            .and_then(|(con, members): (_, Vec<String>)| {
+                assert_eq!(vec!["a".to_string()], members);
+                con.zpopmin("z", 1)
+            })
+            .map(|(_, members): (_, Vec<(String, f64)>)|
+                assert_eq!(vec![("x".to_string(), 1.5)], members));
+        Runtime::new().unwrap().block_on(future).unwrap();
+        assert!(server.is_satisfied());
+    }
+
+    #[test]
+    fn test_blocking_commands() {
+        use std::time::Duration;
+
+        let server = MockServer::start().unwrap();
+
+        let future = RedisCoreConnection::connect(&server.address())
+            .and_then(|con| con.blpop(vec!["list"], Duration::from_millis(50)))
+            .and_then(|(con, popped): (_, Option<(String, String)>)| {
+                assert_eq!(None, popped);
+                con.rpush("list", vec!["a", "b"])
+            })
+            .and_then(|(con, _)| con.brpop(vec!["unknown", "list"], Duration::from_secs(0)))
+            .and_then(|(con, popped)| {
+                assert_eq!(Some(("list".to_string(), "b".to_string())), popped);
+                con.zadd("z", vec![(2.5, "x")])
+            })
+            .and_then(|(con, _)| con.bzpopmin(vec!["z"], Duration::from_secs(0)))
+            .map(|(_, popped)| assert_eq!(Some(("z".to_string(), "x".to_string(), 2.5)), popped));
+        Runtime::new().unwrap().block_on(future).unwrap();
+    }
+
+    #[test]
+    fn test_query_conversion_error() {
+        let server = MockServer::start().unwrap();
+
+        let future = RedisCoreConnection::connect(&server.address())
+            .and_then(|con| con.send_typed(set("foo", "bar")))
+            .and_then(|(con, ())| con.query::<i64>(crate::command("GET").arg("foo")));
+        let err = Runtime::new().unwrap().block_on(future).err().unwrap();
+        assert_eq!(crate::RedisErrorKind::IncorrectConversion, err.error);
+    }
+}
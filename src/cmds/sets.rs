@@ -0,0 +1,58 @@
+use crate::{RedisTypedCommand, FromRedisValue, IntoRedisArgument, command};
+use std::collections::HashSet;
+use std::hash::Hash;
+
+
+/// Make "SADD key member [member ...]" command,
+/// the response is the number of members that were added.
+pub fn sadd<K, M>(key: K, members: Vec<M>) -> RedisTypedCommand<i64>
+    where K: IntoRedisArgument,
+          M: IntoRedisArgument {
+    let mut cmd = command("SADD").arg(key);
+    for member in members.into_iter() {
+        cmd.arg_mut(member);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "SREM key member [member ...]" command,
+/// the response is the number of members that were removed.
+pub fn srem<K, M>(key: K, members: Vec<M>) -> RedisTypedCommand<i64>
+    where K: IntoRedisArgument,
+          M: IntoRedisArgument {
+    let mut cmd = command("SREM").arg(key);
+    for member in members.into_iter() {
+        cmd.arg_mut(member);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "SMEMBERS key" command.
+pub fn smembers<K, T>(key: K) -> RedisTypedCommand<HashSet<T>>
+    where K: IntoRedisArgument,
+          T: FromRedisValue + Eq + Hash {
+    RedisTypedCommand::new(command("SMEMBERS").arg(key))
+}
+
+/// Make "SISMEMBER key member" command.
+pub fn sismember<K, M>(key: K, member: M) -> RedisTypedCommand<bool>
+    where K: IntoRedisArgument,
+          M: IntoRedisArgument {
+    RedisTypedCommand::new(command("SISMEMBER").arg(key).arg(member))
+}
+
+/// Make "SINTER key [key ...]" command.
+pub fn sinter<K, T>(keys: Vec<K>) -> RedisTypedCommand<HashSet<T>>
+    where K: IntoRedisArgument,
+          T: FromRedisValue + Eq + Hash {
+    let mut cmd = command("SINTER");
+    for key in keys.into_iter() {
+        cmd.arg_mut(key);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "SCARD key" command.
+pub fn scard<K: IntoRedisArgument>(key: K) -> RedisTypedCommand<i64> {
+    RedisTypedCommand::new(command("SCARD").arg(key))
+}
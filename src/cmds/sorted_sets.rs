@@ -0,0 +1,324 @@
+use super::lists::timeout_arg;
+use crate::{RedisTypedCommand, RedisCommand, FromRedisValue, IntoRedisArgument, command};
+use std::time::Duration;
+
+
+/// Set of options of the ZADD command.
+///
+/// # Example
+/// ```
+/// use redis_asio::cmds::{ZAddOptions, ZAddCondition, ZAddComparison};
+///
+/// // "ZADD key XX GT CH score member"
+/// let options = ZAddOptions::new()
+///     .with_condition(ZAddCondition::Exists)
+///     .with_comparison(ZAddComparison::Greater)
+///     .with_changed();
+/// ```
+#[derive(Clone, Default)]
+pub struct ZAddOptions {
+    pub(crate) condition: Option<ZAddCondition>,
+    pub(crate) comparison: Option<ZAddComparison>,
+    pub(crate) changed: bool,
+}
+
+/// Condition of the ZADD command: NX or XX.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ZAddCondition {
+    /// NX - only add new members, don't update existing ones
+    NotExists,
+    /// XX - only update existing members, don't add new ones
+    Exists,
+}
+
+/// Score comparison of the ZADD command: GT or LT.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ZAddComparison {
+    /// GT - only update existing members if the new score is greater than current one
+    Greater,
+    /// LT - only update existing members if the new score is less than current one
+    Less,
+}
+
+/// Bound of a score interval used by "ZRANGE ... BYSCORE".
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ScoreBound {
+    Inclusive(f64),
+    /// "(score" - the score is excluded from the interval
+    Exclusive(f64),
+    /// "-inf"
+    NegInf,
+    /// "+inf"
+    PosInf,
+}
+
+/// Bound of a lexicographical interval used by "ZRANGE ... BYLEX".
+#[derive(Clone, PartialEq, Debug)]
+pub enum LexBound {
+    /// "[member"
+    Inclusive(Vec<u8>),
+    /// "(member"
+    Exclusive(Vec<u8>),
+    /// "-" - the lowest possible member
+    Min,
+    /// "+" - the highest possible member
+    Max,
+}
+
+/// Interval of a ZRANGE command.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ZRange {
+    /// Zero-based indexes, negative ones are offsets from the highest rank
+    Rank(i64, i64),
+    /// BYSCORE - interval from min score to max score
+    Score(ScoreBound, ScoreBound),
+    /// BYLEX - interval from min member to max member
+    Lex(LexBound, LexBound),
+}
+
+/// Set of options of the ZRANGE command.
+///
+/// Note the score and lexicographical intervals are always specified from min to max,
+/// they are swapped in the request if the order is reversed.
+///
+/// # Example
+/// ```
+/// use redis_asio::cmds::{ZRangeOptions, ScoreBound};
+///
+/// // "ZRANGE key +inf (10 BYSCORE REV LIMIT 0 5"
+/// let options = ZRangeOptions::by_score(ScoreBound::Exclusive(10.0), ScoreBound::PosInf)
+///     .reversed()
+///     .with_limit(0, 5);
+/// ```
+#[derive(Clone)]
+pub struct ZRangeOptions {
+    pub(crate) range: ZRange,
+    pub(crate) reversed: bool,
+    pub(crate) limit: Option<(u64, u64)>,
+}
+
+impl ZAddOptions {
+    pub fn new() -> ZAddOptions {
+        ZAddOptions::default()
+    }
+
+    pub fn with_condition(mut self, condition: ZAddCondition) -> ZAddOptions {
+        self.condition = Some(condition);
+        self
+    }
+
+    pub fn with_comparison(mut self, comparison: ZAddComparison) -> ZAddOptions {
+        self.comparison = Some(comparison);
+        self
+    }
+
+    /// CH - count the changed members instead of the added ones.
+    pub fn with_changed(mut self) -> ZAddOptions {
+        self.changed = true;
+        self
+    }
+}
+
+impl ZRangeOptions {
+    pub fn new(range: ZRange) -> ZRangeOptions {
+        ZRangeOptions { range, reversed: false, limit: None }
+    }
+
+    pub fn by_rank(start: i64, stop: i64) -> ZRangeOptions {
+        ZRangeOptions::new(ZRange::Rank(start, stop))
+    }
+
+    pub fn by_score(min: ScoreBound, max: ScoreBound) -> ZRangeOptions {
+        ZRangeOptions::new(ZRange::Score(min, max))
+    }
+
+    pub fn by_lex(min: LexBound, max: LexBound) -> ZRangeOptions {
+        ZRangeOptions::new(ZRange::Lex(min, max))
+    }
+
+    /// REV - order the members from the highest to the lowest score.
+    pub fn reversed(mut self) -> ZRangeOptions {
+        self.reversed = true;
+        self
+    }
+
+    /// LIMIT offset count - is supported by Redis for the score and lexicographical intervals only.
+    pub fn with_limit(mut self, offset: u64, count: u64) -> ZRangeOptions {
+        self.limit = Some((offset, count));
+        self
+    }
+}
+
+/// Make "ZADD key score member [score member ...]" command,
+/// the response is the number of members that were added.
+pub fn zadd<K, M>(key: K, members: Vec<(f64, M)>) -> RedisTypedCommand<i64>
+    where K: IntoRedisArgument,
+          M: IntoRedisArgument {
+    zadd_with(key, members, ZAddOptions::new())
+}
+
+/// Make "ZADD key [NX|XX] [GT|LT] [CH] score member [score member ...]" command,
+/// the response is the number of members that were added (or changed if CH is set).
+pub fn zadd_with<K, M>(key: K, members: Vec<(f64, M)>, options: ZAddOptions) -> RedisTypedCommand<i64>
+    where K: IntoRedisArgument,
+          M: IntoRedisArgument {
+    let mut cmd = zadd_cmd(key, options);
+    for (score, member) in members.into_iter() {
+        cmd.arg_mut(score);
+        cmd.arg_mut(member);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "ZADD key [NX|XX] [GT|LT] [CH] INCR increment member" command,
+/// the response is the new score of the member
+/// or None if the member was not updated because of the options.
+pub fn zadd_incr<K, M>(key: K, increment: f64, member: M, options: ZAddOptions)
+                       -> RedisTypedCommand<Option<f64>>
+    where K: IntoRedisArgument,
+          M: IntoRedisArgument {
+    RedisTypedCommand::new(zadd_cmd(key, options).arg("INCR").arg(increment).arg(member))
+}
+
+/// Make "ZRANGE key start stop [BYSCORE|BYLEX] [REV] [LIMIT offset count]" command.
+pub fn zrange<K, T>(key: K, options: ZRangeOptions) -> RedisTypedCommand<Vec<T>>
+    where K: IntoRedisArgument,
+          T: FromRedisValue {
+    RedisTypedCommand::new(zrange_cmd(key, options))
+}
+
+/// Make "ZRANGE key start stop [BYSCORE|BYLEX] [REV] [LIMIT offset count] WITHSCORES" command,
+/// the response is the list of (member, score) pairs.
+pub fn zrange_with_scores<K, T>(key: K, options: ZRangeOptions) -> RedisTypedCommand<Vec<(T, f64)>>
+    where K: IntoRedisArgument,
+          T: FromRedisValue {
+    RedisTypedCommand::new(zrange_cmd(key, options).arg("WITHSCORES"))
+}
+
+/// Make "ZPOPMIN key count" command,
+/// the response is the list of removed (member, score) pairs from the lowest score.
+pub fn zpopmin<K, T>(key: K, count: u64) -> RedisTypedCommand<Vec<(T, f64)>>
+    where K: IntoRedisArgument,
+          T: FromRedisValue {
+    RedisTypedCommand::new(command("ZPOPMIN").arg(key).arg(count))
+}
+
+/// Make "BZPOPMIN key [key ...] timeout" command,
+/// the response is the triple of the key name, the popped member and its score
+/// or None if the timeout has expired. The zero `timeout` blocks forever.
+///
+/// Note the connection is occupied until the response comes,
+/// other requests can be sent only after the future has been resolved.
+pub fn bzpopmin<K, T>(keys: Vec<K>, timeout: Duration) -> RedisTypedCommand<Option<(String, T, f64)>>
+    where K: IntoRedisArgument,
+          T: FromRedisValue {
+    let mut cmd = command("BZPOPMIN");
+    for key in keys.into_iter() {
+        cmd.arg_mut(key);
+    }
+    RedisTypedCommand::new(cmd.arg(timeout_arg(timeout)))
+}
+
+/// Make "ZSCORE key member" command,
+/// the response is None if the member or the key does not exist.
+pub fn zscore<K, M>(key: K, member: M) -> RedisTypedCommand<Option<f64>>
+    where K: IntoRedisArgument,
+          M: IntoRedisArgument {
+    RedisTypedCommand::new(command("ZSCORE").arg(key).arg(member))
+}
+
+/// Make "ZREM key member [member ...]" command,
+/// the response is the number of members that were removed.
+pub fn zrem<K, M>(key: K, members: Vec<M>) -> RedisTypedCommand<i64>
+    where K: IntoRedisArgument,
+          M: IntoRedisArgument {
+    let mut cmd = command("ZREM").arg(key);
+    for member in members.into_iter() {
+        cmd.arg_mut(member);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "ZCARD key" command.
+pub fn zcard<K: IntoRedisArgument>(key: K) -> RedisTypedCommand<i64> {
+    RedisTypedCommand::new(command("ZCARD").arg(key))
+}
+
+fn zadd_cmd<K: IntoRedisArgument>(key: K, options: ZAddOptions) -> RedisCommand {
+    let mut cmd = command("ZADD").arg(key);
+
+    match options.condition {
+        Some(ZAddCondition::NotExists) => cmd.arg_mut("NX"),
+        Some(ZAddCondition::Exists) => cmd.arg_mut("XX"),
+        _ => (),
+    }
+
+    match options.comparison {
+        Some(ZAddComparison::Greater) => cmd.arg_mut("GT"),
+        Some(ZAddComparison::Less) => cmd.arg_mut("LT"),
+        _ => (),
+    }
+
+    if options.changed {
+        cmd.arg_mut("CH");
+    }
+
+    cmd
+}
+
+fn zrange_cmd<K: IntoRedisArgument>(key: K, options: ZRangeOptions) -> RedisCommand {
+    let ZRangeOptions { range, reversed, limit } = options;
+    let mut cmd = command("ZRANGE").arg(key);
+
+    match range {
+        ZRange::Rank(start, stop) => {
+            cmd.arg_mut(start);
+            cmd.arg_mut(stop);
+        }
+        ZRange::Score(min, max) => {
+            let (start, stop) = if reversed { (max, min) } else { (min, max) };
+            cmd.arg_mut(score_arg(start));
+            cmd.arg_mut(score_arg(stop));
+            cmd.arg_mut("BYSCORE");
+        }
+        ZRange::Lex(min, max) => {
+            let (start, stop) = if reversed { (max, min) } else { (min, max) };
+            cmd.arg_mut(lex_arg(start));
+            cmd.arg_mut(lex_arg(stop));
+            cmd.arg_mut("BYLEX");
+        }
+    }
+
+    if reversed {
+        cmd.arg_mut("REV");
+    }
+
+    if let Some((offset, count)) = limit {
+        cmd.append(command("LIMIT").arg(offset).arg(count));
+    }
+
+    cmd
+}
+
+fn score_arg(bound: ScoreBound) -> String {
+    match bound {
+        ScoreBound::Inclusive(x) => x.to_string(),
+        ScoreBound::Exclusive(x) => format!("({}", x),
+        ScoreBound::NegInf => "-inf".to_string(),
+        ScoreBound::PosInf => "+inf".to_string(),
+    }
+}
+
+fn lex_arg(bound: LexBound) -> Vec<u8> {
+    let (prefix, member) = match bound {
+        LexBound::Inclusive(member) => (b'[', member),
+        LexBound::Exclusive(member) => (b'(', member),
+        LexBound::Min => return b"-".to_vec(),
+        LexBound::Max => return b"+".to_vec(),
+    };
+
+    let mut arg = Vec::with_capacity(member.len() + 1);
+    arg.push(prefix);
+    arg.extend(member);
+    arg
+}
@@ -0,0 +1,151 @@
+use crate::{RedisTypedCommand, RedisCommand, IntoRedisArgument, command};
+
+
+/// Set of options of the SET command.
+///
+/// # Example
+/// ```
+/// use redis_asio::cmds::{SetOptions, SetExpiry, SetCondition};
+///
+/// // "SET key value NX PX 100"
+/// let options = SetOptions::new()
+///     .with_condition(SetCondition::NotExists)
+///     .with_expiry(SetExpiry::Milliseconds(100));
+/// ```
+#[derive(Clone, Default)]
+pub struct SetOptions {
+    pub(crate) condition: Option<SetCondition>,
+    pub(crate) expiry: Option<SetExpiry>,
+}
+
+/// Condition of the SET command: NX or XX.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SetCondition {
+    /// NX - set the key only if it does not exist
+    NotExists,
+    /// XX - set the key only if it exists already
+    Exists,
+}
+
+/// Expiry of the key set by the SET command.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SetExpiry {
+    /// EX - expire time in seconds
+    Seconds(u64),
+    /// PX - expire time in milliseconds
+    Milliseconds(u64),
+    /// EXAT - Unix time in seconds at which the key will expire
+    UnixSeconds(u64),
+    /// PXAT - Unix time in milliseconds at which the key will expire
+    UnixMilliseconds(u64),
+    /// KEEPTTL - retain the time to live of the key
+    KeepTtl,
+}
+
+impl SetOptions {
+    pub fn new() -> SetOptions {
+        SetOptions::default()
+    }
+
+    pub fn with_condition(mut self, condition: SetCondition) -> SetOptions {
+        self.condition = Some(condition);
+        self
+    }
+
+    pub fn with_expiry(mut self, expiry: SetExpiry) -> SetOptions {
+        self.expiry = Some(expiry);
+        self
+    }
+}
+
+/// Make "GET key" command, the response is None if the key does not exist.
+pub fn get<K: IntoRedisArgument>(key: K) -> RedisTypedCommand<Option<Vec<u8>>> {
+    RedisTypedCommand::new(command("GET").arg(key))
+}
+
+/// Make "SET key value" command.
+pub fn set<K, V>(key: K, value: V) -> RedisTypedCommand<()>
+    where K: IntoRedisArgument,
+          V: IntoRedisArgument {
+    RedisTypedCommand::new(command("SET").arg(key).arg(value))
+}
+
+/// Make "SET key value [NX|XX] [EX|PX|EXAT|PXAT|KEEPTTL]" command,
+/// the response is false if the key was not set because of the `SetCondition`.
+pub fn set_with<K, V>(key: K, value: V, options: SetOptions) -> RedisTypedCommand<bool>
+    where K: IntoRedisArgument,
+          V: IntoRedisArgument {
+    RedisTypedCommand::new(set_cmd(key, value, options, false))
+}
+
+/// Make "SET key value [NX|XX] GET [EX|PX|EXAT|PXAT|KEEPTTL]" command,
+/// the response is the old value stored at the key or None if the key did not exist.
+pub fn set_get<K, V>(key: K, value: V, options: SetOptions) -> RedisTypedCommand<Option<Vec<u8>>>
+    where K: IntoRedisArgument,
+          V: IntoRedisArgument {
+    RedisTypedCommand::new(set_cmd(key, value, options, true))
+}
+
+/// Make "MGET key [key ...]" command,
+/// the response contains None for each key that does not exist.
+pub fn mget<K: IntoRedisArgument>(keys: Vec<K>) -> RedisTypedCommand<Vec<Option<Vec<u8>>>> {
+    let mut cmd = command("MGET");
+    for key in keys.into_iter() {
+        cmd.arg_mut(key);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "MSET key value [key value ...]" command.
+pub fn mset<K, V>(key_values: Vec<(K, V)>) -> RedisTypedCommand<()>
+    where K: IntoRedisArgument,
+          V: IntoRedisArgument {
+    let mut cmd = command("MSET");
+    for (key, value) in key_values.into_iter() {
+        cmd.arg_mut(key);
+        cmd.arg_mut(value);
+    }
+    RedisTypedCommand::new(cmd)
+}
+
+/// Make "INCR key" command, the response is the value after the increment.
+pub fn incr<K: IntoRedisArgument>(key: K) -> RedisTypedCommand<i64> {
+    RedisTypedCommand::new(command("INCR").arg(key))
+}
+
+/// Make "INCRBY key increment" command, the response is the value after the increment.
+pub fn incr_by<K: IntoRedisArgument>(key: K, increment: i64) -> RedisTypedCommand<i64> {
+    RedisTypedCommand::new(command("INCRBY").arg(key).arg(increment))
+}
+
+/// Make "INCRBYFLOAT key increment" command, the response is the value after the increment.
+pub fn incr_by_float<K: IntoRedisArgument>(key: K, increment: f64) -> RedisTypedCommand<f64> {
+    RedisTypedCommand::new(command("INCRBYFLOAT").arg(key).arg(increment))
+}
+
+fn set_cmd<K, V>(key: K, value: V, options: SetOptions, get_old: bool) -> RedisCommand
+    where K: IntoRedisArgument,
+          V: IntoRedisArgument {
+    let mut cmd = command("SET").arg(key).arg(value);
+
+    match options.condition {
+        Some(SetCondition::NotExists) => cmd.arg_mut("NX"),
+        Some(SetCondition::Exists) => cmd.arg_mut("XX"),
+        _ => (),
+    }
+
+    if get_old {
+        cmd.arg_mut("GET");
+    }
+
+    match options.expiry {
+        Some(SetExpiry::Seconds(x)) => cmd.append(command("EX").arg(x)),
+        Some(SetExpiry::Milliseconds(x)) => cmd.append(command("PX").arg(x)),
+        Some(SetExpiry::UnixSeconds(x)) => cmd.append(command("EXAT").arg(x)),
+        Some(SetExpiry::UnixMilliseconds(x)) => cmd.append(command("PXAT").arg(x)),
+        Some(SetExpiry::KeepTtl) => cmd.arg_mut("KEEPTTL"),
+        _ => (),
+    }
+
+    cmd
+}
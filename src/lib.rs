@@ -137,9 +137,17 @@
 
 mod base;
 pub mod stream;
+pub mod cmds;
+pub mod queue;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 
 pub use base::{RedisCoreConnection, RedisResult, RedisValue, RedisCommand, RedisError,
                RedisErrorKind, RedisArgument, FromRedisValue, IntoRedisArgument, command,
-               from_redis_value};
+               from_redis_value, TypedCommand, RedisTypedCommand, Query};
 
+#[cfg(not(feature = "fuzzing"))]
 use base::{RespInternalValue, RedisCodec};
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub use base::{RespInternalValue, RedisCodec};
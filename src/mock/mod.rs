@@ -0,0 +1,14 @@
+//! Test-support module that contains an in-process mock Redis server.
+//! It allows to test code that uses the crate without a real Redis.
+//!
+//! The module is available with the `mock` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! redis-asio = { version = "0.1", features = ["mock"] }
+//! ```
+
+mod server;
+mod storage;
+
+pub use server::MockServer;
@@ -0,0 +1,284 @@
+use super::storage::{Storage, Execution, BlockingRead};
+use crate::{RedisResult, RedisValue, RedisCommand, RedisError, RedisErrorKind, RespInternalValue};
+use crate::base::RedisServerCodec;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::runtime::Runtime;
+use tokio::timer::Delay;
+use tokio_codec::Decoder;
+use futures::{Future, Stream, Async, task};
+use std::net::SocketAddr;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+
+/// In-process Redis server that speaks RESP protocol and listens on a local TCP port.
+///
+/// The server replies on requests in the following order:
+/// 1) if the request is equal to the next scripted expectation,
+///    the scripted reply is sent (see `MockServer::expect()`),
+/// 2) else the request is executed by the small in-memory implementation
+///    of strings, hashes, lists, sorted sets and streams.
+///
+/// The server is stopped when the `MockServer` is dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use futures::Future;
+/// use redis_asio::{RedisCoreConnection, RedisValue, command};
+/// use redis_asio::mock::MockServer;
+///
+/// let server = MockServer::start().unwrap();
+/// server.expect(command("GET").arg("foo"), RedisValue::BulkString(b"bar".to_vec()));
+///
+/// let future = RedisCoreConnection::connect(&server.address())
+///     .and_then(|con| con.send(command("GET").arg("foo")))
+///     .map(|(_, response)|
+///         assert_eq!(RedisValue::BulkString(b"bar".to_vec()), response))
+///     .map_err(|err| panic!("{}", err));
+/// tokio::run(future);
+///
+/// assert!(server.is_satisfied());
+/// ```
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<State>>,
+    runtime: Option<Runtime>,
+}
+
+/// Shared state of all of the server connections.
+#[derive(Default)]
+struct State {
+    storage: Storage,
+    /// Scripted requests and the corresponding replies
+    expectations: VecDeque<(RespInternalValue, RespInternalValue)>,
+    /// Log of all of the received requests
+    received: Vec<RedisValue>,
+    /// Tasks of the connections that are blocked by XREAD, BLPOP etc.
+    blocked: Vec<task::Task>,
+}
+
+impl MockServer {
+    /// Bind a random local port and start to serve connections
+    /// within a separate runtime.
+    pub fn start() -> RedisResult<MockServer> {
+        let listener = TcpListener::bind(&"127.0.0.1:0".parse::<SocketAddr>().unwrap())?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let mut runtime = Runtime::new()?;
+        let connection_state = state.clone();
+        let server = listener.incoming()
+            .map_err(|err| eprintln!("Mock server couldn't accept a connection: {}", err))
+            .for_each(move |socket| {
+                tokio::spawn(serve(socket, connection_state.clone()));
+                Ok(())
+            });
+        runtime.spawn(server);
+
+        let runtime = Some(runtime);
+        Ok(MockServer { address, state, runtime })
+    }
+
+    /// Address the server is listening on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Reply with the `reply` on the `request` once.
+    /// Expectations are matched in the order they were added.
+    pub fn expect(&self, request: RedisCommand, reply: RedisValue) {
+        self.push_expectation(request, RespInternalValue::from_redis_value(reply));
+    }
+
+    /// Reply with the Error `message` on the `request` once.
+    /// Expectations are matched in the order they were added.
+    pub fn expect_error(&self, request: RedisCommand, message: &str) {
+        self.push_expectation(request, RespInternalValue::Error(message.to_string()));
+    }
+
+    /// Check if all of the scripted expectations have been matched.
+    pub fn is_satisfied(&self) -> bool {
+        self.state.lock().unwrap().expectations.is_empty()
+    }
+
+    /// Get all of the requests received by the server in the receiving order.
+    /// Each request is represented by an Array of BulkStrings.
+    pub fn received(&self) -> Vec<RedisValue> {
+        self.state.lock().unwrap().received.clone()
+    }
+
+    fn push_expectation(&self, request: RedisCommand, reply: RespInternalValue) {
+        self.state.lock().unwrap().expectations.push_back((request.into_resp_value(), reply));
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            let _ = runtime.shutdown_now().wait();
+        }
+    }
+}
+
+impl State {
+    fn execute(&mut self, request: RespInternalValue) -> Execution {
+        if let Ok(request) = RedisValue::from_resp_value(request.clone()) {
+            self.received.push(request);
+        }
+
+        let is_expected = match self.expectations.front() {
+            Some((expected, _)) => *expected == request,
+            _ => false,
+        };
+        if is_expected {
+            let (_, reply) = self.expectations.pop_front().unwrap();
+            return Execution::Reply(reply);
+        }
+
+        match request_to_args(request) {
+            Some(args) => self.storage.execute(&args),
+            _ => Execution::Reply(RespInternalValue::Error("ERR Protocol error".to_string())),
+        }
+    }
+
+    /// Notify the blocked connections that the storage could be changed.
+    fn wake_blocked(&mut self) {
+        for task in self.blocked.drain(..) {
+            task.notify();
+        }
+    }
+}
+
+/// Process requests of the connection one by one
+/// and send replies in the same order.
+fn serve(socket: TcpStream, state: Arc<Mutex<State>>)
+         -> impl Future<Item=(), Error=()> + Send + 'static {
+    let (replies, requests) = RedisServerCodec.framed(socket).split();
+    requests
+        .and_then(move |request| Execute::new(state.clone(), request))
+        .forward(replies)
+        .map(|_| ())
+        .map_err(|err| eprintln!("Mock server connection has failed: {}", err))
+}
+
+/// The `Future<Item=RespInternalValue, Error=RedisError>` that executes a request,
+/// and waits for new data if the request is a blocking read.
+struct Execute {
+    state: Arc<Mutex<State>>,
+    request: Option<RespInternalValue>,
+    blocked: Option<(BlockingRead, Option<Delay>)>,
+}
+
+impl Execute {
+    fn new(state: Arc<Mutex<State>>, request: RespInternalValue) -> Execute {
+        let request = Some(request);
+        let blocked = None;
+        Execute { state, request, blocked }
+    }
+}
+
+impl Future for Execute {
+    type Item = RespInternalValue;
+    type Error = RedisError;
+
+    fn poll(&mut self) -> Result<Async<Self::Item>, Self::Error> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(request) = self.request.take() {
+            match state.execute(request) {
+                Execution::Reply(reply) => {
+                    state.wake_blocked();
+                    return Ok(Async::Ready(reply));
+                }
+                Execution::Block(read) => {
+                    // the zero timeout means block forever
+                    let delay = read.timeout()
+                        .filter(|timeout| *timeout != Duration::from_millis(0))
+                        .map(|timeout| Delay::new(Instant::now() + timeout));
+                    self.blocked = Some((read, delay));
+                }
+            }
+        }
+
+        let (read, delay) = self.blocked.as_mut().unwrap();
+        if let Some(reply) = state.storage.try_read(read) {
+            // the read could change a consumer group
+            state.wake_blocked();
+            return Ok(Async::Ready(reply));
+        }
+
+        if let Some(delay) = delay {
+            let is_expired = delay.poll()
+                .map_err(|err| RedisError::new(RedisErrorKind::InternalError, err.to_string()))?
+                .is_ready();
+            if is_expired {
+                return Ok(Async::Ready(RespInternalValue::Nil));
+            }
+        }
+
+        state.blocked.push(task::current());
+        Ok(Async::NotReady)
+    }
+}
+
+fn request_to_args(request: RespInternalValue) -> Option<Vec<Vec<u8>>> {
+    match request {
+        RespInternalValue::Array(values) =>
+            values.into_iter()
+                .map(|value| match value {
+                    RespInternalValue::BulkString(arg) => Some(arg),
+                    _ => None,
+                })
+                .collect(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RedisCoreConnection, command};
+
+    #[test]
+    fn test_expectations() {
+        let server = MockServer::start().unwrap();
+        server.expect(command("GET").arg("foo"), RedisValue::Int(1));
+        server.expect_error(command("GET").arg("foo"), "ERR scripted error");
+
+        let future = RedisCoreConnection::connect(&server.address())
+            .and_then(|con| con.send(command("GET").arg("foo")))
+            .and_then(|(con, response)| {
+                assert_eq!(RedisValue::Int(1), response);
+                con.send(command("GET").arg("foo"))
+            })
+            .then(|res| match res {
+                Err(err) => {
+                    assert_eq!(RedisErrorKind::ReceiveError, err.error);
+                    Ok(())
+                }
+                Ok(_) => Err(()),
+            });
+        Runtime::new().unwrap().block_on(future).unwrap();
+
+        assert!(server.is_satisfied());
+        let request = RedisValue::Array(
+            vec![RedisValue::BulkString(b"GET".to_vec()), RedisValue::BulkString(b"foo".to_vec())]);
+        assert_eq!(vec![request.clone(), request], server.received());
+    }
+
+    #[test]
+    fn test_storage() {
+        let server = MockServer::start().unwrap();
+
+        let future = RedisCoreConnection::connect(&server.address())
+            .and_then(|con| con.send(command("SET").arg("foo").arg(123)))
+            .and_then(|(con, response)| {
+                assert_eq!(RedisValue::Ok, response);
+                con.send(command("GET").arg("foo"))
+            })
+            .map(|(_, response)| assert_eq!(RedisValue::BulkString(b"123".to_vec()), response));
+        Runtime::new().unwrap().block_on(future).unwrap();
+    }
+}
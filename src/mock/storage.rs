@@ -0,0 +1,2535 @@
+use crate::RespInternalValue;
+use std::collections::{HashMap, BTreeMap, VecDeque};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+
+/// Stream entry id as a pair: <milliseconds, sequence number>
+type Id = (u64, u64);
+
+/// Field-value pairs of a stream entry in the original order
+type Fields = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// Field-value pairs of a hash
+type HashValue = HashMap<Vec<u8>, Vec<u8>>;
+
+/// Member-score pairs of a sorted set
+type ZSetValue = HashMap<Vec<u8>, f64>;
+
+/// A reply or an error message that will be sent as a RESP Error
+type CommandResult = Result<RespInternalValue, String>;
+
+const WRONG_TYPE: &str = "WRONGTYPE Operation against a key holding the wrong kind of value";
+const SYNTAX_ERROR: &str = "ERR syntax error";
+const NOT_INTEGER: &str = "ERR value is not an integer or out of range";
+const INVALID_ID: &str = "ERR Invalid stream ID specified as stream command argument";
+const NOT_FLOAT: &str = "ERR value is not a valid float";
+
+/// Result of a request execution.
+pub(crate) enum Execution {
+    /// Reply should be sent to the client immediately
+    Reply(RespInternalValue),
+    /// There is nothing to read yet, the read should be repeated
+    /// via `Storage::try_read()` after the storage changes
+    Block(BlockingRead),
+}
+
+/// Request that waits for new data.
+pub(crate) enum BlockingRead {
+    /// XREAD/XREADGROUP with the BLOCK option
+    Stream(StreamRead),
+    /// BLPOP/BRPOP/BLMOVE/BZPOPMIN
+    Pop(PopRead),
+}
+
+impl BlockingRead {
+    /// Blocking timeout: `Some(Duration(0))` means block forever,
+    /// None means the read is not blocking.
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        match self {
+            BlockingRead::Stream(read) => read.block,
+            BlockingRead::Pop(read) => Some(read.timeout),
+        }
+    }
+}
+
+/// XREAD/XREADGROUP request that waits for new entries.
+pub(crate) struct StreamRead {
+    group: Option<(Vec<u8>, Vec<u8>)>,
+    count: Option<usize>,
+    /// BLOCK argument: `Some(Duration(0))` means block forever
+    block: Option<Duration>,
+    /// NOACK - the delivered entries are not added to the PEL
+    no_ack: bool,
+    streams: Vec<(Vec<u8>, ReadFrom)>,
+}
+
+/// Blocking pop request that waits for a non-empty key.
+pub(crate) struct PopRead {
+    keys: Vec<Vec<u8>>,
+    kind: PopKind,
+    /// The zero timeout means block forever
+    timeout: Duration,
+}
+
+enum PopKind {
+    /// BLPOP/BRPOP
+    List(Side),
+    /// BLMOVE
+    Move { destination: Vec<u8>, from: Side, to: Side },
+    /// BZPOPMIN
+    MinScore,
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Left,
+    Right,
+}
+
+enum ReadFrom {
+    /// Read entries with IDs greater than the specified
+    After(Id),
+    /// Read entries that were never delivered to other consumers of the group (">")
+    NewInGroup,
+}
+
+enum Value {
+    String(Vec<u8>),
+    Hash(HashValue),
+    List(VecDeque<Vec<u8>>),
+    ZSet(ZSetValue),
+    Stream(StreamValue),
+}
+
+#[derive(Default)]
+struct StreamValue {
+    entries: BTreeMap<Id, Fields>,
+    last_id: Id,
+    /// Count of entries added during the stream lifetime
+    entries_added: u64,
+    max_deleted_id: Id,
+    groups: HashMap<Vec<u8>, Group>,
+}
+
+struct Group {
+    last_delivered: Id,
+    /// Pending entries list: delivered but not acknowledged entries
+    pending: BTreeMap<Id, PendingEntry>,
+    /// Consumers sorted by their names like Redis does
+    consumers: BTreeMap<Vec<u8>, Consumer>,
+}
+
+struct Consumer {
+    /// Unix time of the last interaction in milliseconds
+    seen_at: u64,
+    /// Unix time of the last successful reading or claiming in milliseconds
+    active_at: Option<u64>,
+}
+
+struct PendingEntry {
+    consumer: Vec<u8>,
+    /// Unix time of the last delivery in milliseconds
+    delivered_at: u64,
+    delivery_count: u64,
+}
+
+impl StreamValue {
+    /// Evict the entries according to the trimming, returns the number of evicted entries.
+    /// The approximate trimming is exact within the mock but it respects the LIMIT.
+    fn trim(&mut self, trim: &StreamTrim) -> usize {
+        let evict: Vec<Id> = match trim.strategy {
+            TrimThreshold::MaxLen(max_len) => self.entries.keys()
+                .take(self.entries.len().saturating_sub(max_len))
+                .cloned()
+                .collect(),
+            TrimThreshold::MinId(min_id) => self.entries.range(..min_id)
+                .map(|(id, _)| *id)
+                .collect(),
+        };
+
+        let limit = trim.limit.unwrap_or(usize::MAX);
+        let evicted = evict.len().min(limit);
+        for id in evict.into_iter().take(evicted) {
+            self.entries.remove(&id);
+            self.max_deleted_id = self.max_deleted_id.max(id);
+        }
+        evicted
+    }
+
+    /// Delete the entry according to the reference policy of XACKDEL/XDELEX:
+    /// -1 if there is no such entry, 1 if the entry is deleted,
+    /// 2 if the entry is not deleted since it is still pending within a group.
+    fn delete_with_ref(&mut self, id: Id, policy: DeleteRef) -> i64 {
+        if !self.entries.contains_key(&id) {
+            return -1;
+        }
+
+        if let DeleteRef::Acked = policy {
+            if self.groups.values().any(|group| group.pending.contains_key(&id)) {
+                return 2;
+            }
+        }
+
+        self.entries.remove(&id);
+        self.max_deleted_id = self.max_deleted_id.max(id);
+        if let DeleteRef::DelRef = policy {
+            for group in self.groups.values_mut() {
+                group.pending.remove(&id);
+            }
+        }
+        1
+    }
+}
+
+impl Group {
+    /// Create the consumer if there is no such one and update its last interaction time.
+    fn see_consumer(&mut self, consumer: &[u8]) -> &mut Consumer {
+        let now = current_ms();
+        let consumer = self.consumers.entry(consumer.to_vec())
+            .or_insert(Consumer { seen_at: now, active_at: None });
+        consumer.seen_at = now;
+        consumer
+    }
+}
+
+/// In-memory keyspace that executes Redis commands.
+#[derive(Default)]
+pub(crate) struct Storage {
+    keys: HashMap<Vec<u8>, Value>,
+}
+
+impl Storage {
+    /// Execute the request represents array of `BulkString`s.
+    pub(crate) fn execute(&mut self, args: &[Vec<u8>]) -> Execution {
+        let name = match args.first() {
+            Some(x) => String::from_utf8_lossy(x).to_uppercase(),
+            _ => return Execution::Reply(RespInternalValue::Error("ERR empty command".to_string())),
+        };
+        let mut args = Args::new(&name, &args[1..]);
+
+        let result = match name.as_str() {
+            "XREAD" | "XREADGROUP" => match self.stream_read(&mut args) {
+                Ok(execution) => return execution,
+                Err(err) => Err(err),
+            },
+            "BLPOP" | "BRPOP" | "BLMOVE" | "BZPOPMIN" => match self.blocking_pop(&mut args) {
+                Ok(execution) => return execution,
+                Err(err) => Err(err),
+            },
+            _ => self.execute_command(&name, &mut args),
+        };
+
+        Execution::Reply(result.unwrap_or_else(RespInternalValue::Error))
+    }
+
+    /// Try to read data requested by a blocking command.
+    /// Returns None if there is nothing to read.
+    pub(crate) fn try_read(&mut self, read: &BlockingRead) -> Option<RespInternalValue> {
+        match read {
+            BlockingRead::Stream(read) => self.try_read_stream(read),
+            BlockingRead::Pop(read) => self.try_pop(read),
+        }
+    }
+
+    fn try_read_stream(&mut self, read: &StreamRead) -> Option<RespInternalValue> {
+        let mut result = Vec::new();
+        let mut is_history_read = false;
+
+        for (key, from) in read.streams.iter() {
+            let entries = match (from, &read.group) {
+                (ReadFrom::After(id), None) => self.read_after(key, *id, read.count),
+                (ReadFrom::NewInGroup, Some((group, consumer))) =>
+                    self.read_new_in_group(key, group, consumer, read.count, read.no_ack),
+                (ReadFrom::After(id), Some((group, consumer))) => {
+                    is_history_read = true;
+                    self.read_pending_of_consumer(key, group, consumer, *id, read.count)
+                }
+                (ReadFrom::NewInGroup, None) => unreachable!(),
+            };
+
+            // XREAD replies only streams with entries,
+            // but a history XREADGROUP replies all of the requested streams
+            if !entries.is_empty() || is_history_read {
+                result.push(RespInternalValue::Array(
+                    vec![RespInternalValue::BulkString(key.clone()),
+                         RespInternalValue::Array(entries)]));
+            }
+        }
+
+        match result.is_empty() {
+            true => None,
+            false => Some(RespInternalValue::Array(result)),
+        }
+    }
+
+    fn execute_command(&mut self, name: &str, args: &mut Args) -> CommandResult {
+        match name {
+            "PING" => self.ping(args),
+            "GET" => self.get(args),
+            "SET" => self.set(args),
+            "MGET" => self.mget(args),
+            "MSET" => self.mset(args),
+            "INCR" => self.incr_by(args, false),
+            "INCRBY" => self.incr_by(args, true),
+            "DEL" => self.del(args),
+            "EXISTS" => self.exists(args),
+            "HSET" => self.hset(args),
+            "HGET" => self.hget(args),
+            "HGETALL" => self.hgetall(args),
+            "HDEL" => self.hdel(args),
+            "HINCRBY" => self.hincrby(args),
+            "LPUSH" => self.push(args, Side::Left),
+            "RPUSH" => self.push(args, Side::Right),
+            "LPOP" => self.pop(args, Side::Left),
+            "RPOP" => self.pop(args, Side::Right),
+            "LLEN" => self.llen(args),
+            "LRANGE" => self.lrange(args),
+            "LREM" => self.lrem(args),
+            "LMOVE" => self.lmove(args),
+            "ZADD" => self.zadd(args),
+            "ZRANGE" => self.zrange(args),
+            "ZREM" => self.zrem(args),
+            "ZSCORE" => self.zscore(args),
+            "ZCARD" => self.zcard(args),
+            "ZPOPMIN" => self.zpopmin(args),
+            "XADD" => self.xadd(args),
+            "XRANGE" => self.xrange(args, false),
+            "XREVRANGE" => self.xrange(args, true),
+            "XLEN" => self.xlen(args),
+            "XACK" => self.xack(args),
+            "XDEL" => self.xdel(args),
+            "XACKDEL" => self.xackdel(args),
+            "XDELEX" => self.xdelex(args),
+            "XTRIM" => self.xtrim(args),
+            "XPENDING" => self.xpending(args),
+            "XCLAIM" => self.xclaim(args),
+            "XAUTOCLAIM" => self.xautoclaim(args),
+            "XGROUP" => self.xgroup(args),
+            "XINFO" => self.xinfo(args),
+            _ => Err(format!("ERR unknown command '{}'", name.to_lowercase())),
+        }
+    }
+
+    fn ping(&mut self, args: &mut Args) -> CommandResult {
+        let reply = match args.next_opt() {
+            Some(message) => RespInternalValue::BulkString(message.to_vec()),
+            _ => RespInternalValue::Status("PONG".to_string()),
+        };
+        args.finish()?;
+        Ok(reply)
+    }
+
+    fn get(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?;
+        args.finish()?;
+        match self.string(key)? {
+            Some(value) => Ok(RespInternalValue::BulkString(value.clone())),
+            _ => Ok(RespInternalValue::Nil),
+        }
+    }
+
+    fn set(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let value = args.next()?.to_vec();
+        if !args.is_empty() {
+            return Err(SYNTAX_ERROR.to_string());
+        }
+        self.keys.insert(key, Value::String(value));
+        Ok(ok())
+    }
+
+    fn mget(&mut self, args: &mut Args) -> CommandResult {
+        let mut result = vec![args.next()?];
+        result.extend(args.rest());
+        Ok(RespInternalValue::Array(
+            result.into_iter()
+                .map(|key| match self.keys.get(key) {
+                    Some(Value::String(value)) => RespInternalValue::BulkString(value.clone()),
+                    _ => RespInternalValue::Nil,
+                })
+                .collect()))
+    }
+
+    fn mset(&mut self, args: &mut Args) -> CommandResult {
+        let pairs = args.pairs()?;
+        for (key, value) in pairs.into_iter() {
+            self.keys.insert(key, Value::String(value));
+        }
+        Ok(ok())
+    }
+
+    fn incr_by(&mut self, args: &mut Args, with_increment: bool) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let increment = match with_increment {
+            true => args.next_i64()?,
+            false => 1,
+        };
+        args.finish()?;
+
+        let value = match self.string(&key)? {
+            Some(value) => String::from_utf8_lossy(value).parse::<i64>()
+                .map_err(|_| NOT_INTEGER.to_string())?,
+            _ => 0,
+        };
+        let value = value.checked_add(increment)
+            .ok_or_else(|| "ERR increment or decrement would overflow".to_string())?;
+        self.keys.insert(key, Value::String(value.to_string().into_bytes()));
+        Ok(RespInternalValue::Int(value))
+    }
+
+    fn del(&mut self, args: &mut Args) -> CommandResult {
+        let mut keys = vec![args.next()?];
+        keys.extend(args.rest());
+        let removed = keys.into_iter()
+            .filter(|key| self.keys.remove(*key).is_some())
+            .count();
+        Ok(RespInternalValue::Int(removed as i64))
+    }
+
+    fn exists(&mut self, args: &mut Args) -> CommandResult {
+        let mut keys = vec![args.next()?];
+        keys.extend(args.rest());
+        let exist = keys.into_iter()
+            .filter(|key| self.keys.contains_key(*key))
+            .count();
+        Ok(RespInternalValue::Int(exist as i64))
+    }
+
+    fn hset(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let pairs = args.pairs()?;
+        let hash = self.hash_or_insert(key)?;
+        let added = pairs.into_iter()
+            .filter(|(field, value)| hash.insert(field.clone(), value.clone()).is_none())
+            .count();
+        Ok(RespInternalValue::Int(added as i64))
+    }
+
+    fn hget(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?;
+        let field = args.next()?;
+        args.finish()?;
+        match self.hash(key)?.and_then(|hash| hash.get(field)) {
+            Some(value) => Ok(RespInternalValue::BulkString(value.clone())),
+            _ => Ok(RespInternalValue::Nil),
+        }
+    }
+
+    fn hgetall(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?;
+        args.finish()?;
+        let mut result = Vec::new();
+        if let Some(hash) = self.hash(key)? {
+            for (field, value) in hash.iter() {
+                result.push(RespInternalValue::BulkString(field.clone()));
+                result.push(RespInternalValue::BulkString(value.clone()));
+            }
+        }
+        Ok(RespInternalValue::Array(result))
+    }
+
+    fn hdel(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let mut fields = vec![args.next()?];
+        fields.extend(args.rest());
+        let removed = match self.hash_mut(&key)? {
+            Some(hash) => fields.into_iter().filter(|field| hash.remove(*field).is_some()).count(),
+            _ => 0,
+        };
+        self.remove_if_empty(&key);
+        Ok(RespInternalValue::Int(removed as i64))
+    }
+
+    fn hincrby(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let field = args.next()?.to_vec();
+        let increment = args.next_i64()?;
+        args.finish()?;
+
+        let hash = self.hash_or_insert(key)?;
+        let value = match hash.get(&field) {
+            Some(value) => String::from_utf8_lossy(value).parse::<i64>()
+                .map_err(|_| "ERR hash value is not an integer".to_string())?,
+            _ => 0,
+        };
+        let value = value.checked_add(increment)
+            .ok_or_else(|| "ERR increment or decrement would overflow".to_string())?;
+        hash.insert(field, value.to_string().into_bytes());
+        Ok(RespInternalValue::Int(value))
+    }
+
+    fn push(&mut self, args: &mut Args, side: Side) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let mut elements = vec![args.next()?];
+        elements.extend(args.rest());
+
+        let list = self.list_or_insert(key)?;
+        for element in elements.into_iter() {
+            match side {
+                Side::Left => list.push_front(element.to_vec()),
+                Side::Right => list.push_back(element.to_vec()),
+            }
+        }
+        Ok(RespInternalValue::Int(list.len() as i64))
+    }
+
+    fn pop(&mut self, args: &mut Args, side: Side) -> CommandResult {
+        let key = args.next()?;
+        args.finish()?;
+        match self.list_pop(key, side)? {
+            Some(element) => Ok(RespInternalValue::BulkString(element)),
+            _ => Ok(RespInternalValue::Nil),
+        }
+    }
+
+    fn llen(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?;
+        args.finish()?;
+        let len = self.list(key)?.map(|list| list.len()).unwrap_or(0);
+        Ok(RespInternalValue::Int(len as i64))
+    }
+
+    fn lrange(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?;
+        let start = args.next_i64()?;
+        let stop = args.next_i64()?;
+        args.finish()?;
+
+        let result = match self.list(key)? {
+            Some(list) => list.range(index_range(start, stop, list.len()))
+                .map(|element| RespInternalValue::BulkString(element.clone()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        Ok(RespInternalValue::Array(result))
+    }
+
+    fn lrem(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let count = args.next_i64()?;
+        let element = args.next()?;
+        args.finish()?;
+
+        let list = match self.list_mut(&key)? {
+            Some(list) => list,
+            _ => return Ok(RespInternalValue::Int(0)),
+        };
+        let limit = match count {
+            0 => usize::MAX,
+            _ => count.unsigned_abs() as usize,
+        };
+        let mut positions: Vec<usize> = match count >= 0 {
+            true => (0..list.len()).filter(|i| list[*i] == element).take(limit).collect(),
+            false => (0..list.len()).rev().filter(|i| list[*i] == element).take(limit).collect(),
+        };
+
+        // remove from the tail to keep the rest positions valid
+        positions.sort_unstable_by(|a, b| b.cmp(a));
+        for pos in positions.iter() {
+            list.remove(*pos);
+        }
+        self.remove_if_empty(&key);
+        Ok(RespInternalValue::Int(positions.len() as i64))
+    }
+
+    fn lmove(&mut self, args: &mut Args) -> CommandResult {
+        let source = args.next()?;
+        let destination = args.next()?;
+        let from = parse_side(args.next()?)?;
+        let to = parse_side(args.next()?)?;
+        args.finish()?;
+
+        match self.list_move(source, destination, from, to)? {
+            Some(element) => Ok(RespInternalValue::BulkString(element)),
+            _ => Ok(RespInternalValue::Nil),
+        }
+    }
+
+    fn zadd(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let (mut nx, mut xx, mut gt, mut lt, mut ch, mut incr) = (false, false, false, false, false, false);
+        while let Some(option) = args.peek() {
+            if is_keyword(option, "NX") {
+                nx = true;
+            } else if is_keyword(option, "XX") {
+                xx = true;
+            } else if is_keyword(option, "GT") {
+                gt = true;
+            } else if is_keyword(option, "LT") {
+                lt = true;
+            } else if is_keyword(option, "CH") {
+                ch = true;
+            } else if is_keyword(option, "INCR") {
+                incr = true;
+            } else {
+                break;
+            }
+            args.next()?;
+        }
+
+        if nx && xx {
+            return Err("ERR XX and NX options at the same time are not compatible".to_string());
+        }
+        if (gt && lt) || (nx && (gt || lt)) {
+            return Err("ERR GT, LT, and/or NX options at the same time are not compatible".to_string());
+        }
+
+        let pairs = args.pairs()?;
+        if incr && pairs.len() != 1 {
+            return Err("ERR INCR option supports a single increment-element pair".to_string());
+        }
+        let mut members = Vec::with_capacity(pairs.len());
+        for (score, member) in pairs.into_iter() {
+            members.push((parse_score(&score)?, member));
+        }
+
+        let zset = self.zset_or_insert(key.clone())?;
+        let mut added = 0;
+        let mut changed = 0;
+        let mut incr_score = None;
+        for (score, member) in members.into_iter() {
+            let current = zset.get(&member).cloned();
+            let new_score = match (incr, current) {
+                (true, Some(current)) => current + score,
+                _ => score,
+            };
+            let is_allowed = match current {
+                Some(current) => !nx && (!gt || new_score > current) && (!lt || new_score < current),
+                _ => !xx,
+            };
+            if !is_allowed {
+                continue;
+            }
+
+            match current {
+                None => {
+                    added += 1;
+                    changed += 1;
+                }
+                Some(current) if current != new_score => changed += 1,
+                _ => (),
+            }
+            incr_score = Some(new_score);
+            zset.insert(member, new_score);
+        }
+        // XX could create an empty sorted set
+        self.remove_if_empty(&key);
+
+        match (incr, incr_score) {
+            (true, Some(score)) => Ok(RespInternalValue::BulkString(score_to_bytes(score))),
+            (true, None) => Ok(RespInternalValue::Nil),
+            (false, _) if ch => Ok(RespInternalValue::Int(changed)),
+            (false, _) => Ok(RespInternalValue::Int(added)),
+        }
+    }
+
+    fn zrange(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?;
+        let start = args.next()?;
+        let stop = args.next()?;
+
+        let (mut by_score, mut rev, mut with_scores) = (false, false, false);
+        let mut limit = None;
+        while let Some(option) = args.next_opt() {
+            if is_keyword(option, "BYSCORE") {
+                by_score = true;
+            } else if is_keyword(option, "BYLEX") {
+                return Err("ERR BYLEX is not supported by the mock server".to_string());
+            } else if is_keyword(option, "REV") {
+                rev = true;
+            } else if is_keyword(option, "LIMIT") {
+                limit = Some((args.next_usize()?, args.next_i64()?));
+            } else if is_keyword(option, "WITHSCORES") {
+                with_scores = true;
+            } else {
+                return Err(SYNTAX_ERROR.to_string());
+            }
+        }
+        if limit.is_some() && !by_score {
+            return Err("ERR syntax error, LIMIT is only supported in combination with either BYSCORE or BYLEX"
+                .to_string());
+        }
+
+        let mut members = match self.zset(key)? {
+            Some(zset) => sorted_members(zset),
+            _ => Vec::new(),
+        };
+        if rev {
+            members.reverse();
+        }
+
+        let members: Vec<(Vec<u8>, f64)> = match by_score {
+            true => {
+                let (min, max) = match rev {
+                    true => (stop, start),
+                    false => (start, stop),
+                };
+                let (min, is_min_exclusive) = parse_score_bound(min)?;
+                let (max, is_max_exclusive) = parse_score_bound(max)?;
+                let (offset, count) = limit.unwrap_or((0, -1));
+                members.into_iter()
+                    .filter(|(_, score)| match is_min_exclusive {
+                        true => *score > min,
+                        false => *score >= min,
+                    })
+                    .filter(|(_, score)| match is_max_exclusive {
+                        true => *score < max,
+                        false => *score <= max,
+                    })
+                    .skip(offset)
+                    .take(if count < 0 { usize::MAX } else { count as usize })
+                    .collect()
+            }
+            false => {
+                let range = index_range(parse_i64(start)?, parse_i64(stop)?, members.len());
+                members.drain(range).collect()
+            }
+        };
+
+        Ok(scored_members_to_resp(members, with_scores))
+    }
+
+    fn zrem(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let mut members = vec![args.next()?];
+        members.extend(args.rest());
+        let removed = match self.zset_mut(&key)? {
+            Some(zset) => members.into_iter().filter(|member| zset.remove(*member).is_some()).count(),
+            _ => 0,
+        };
+        self.remove_if_empty(&key);
+        Ok(RespInternalValue::Int(removed as i64))
+    }
+
+    fn zscore(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?;
+        let member = args.next()?;
+        args.finish()?;
+        match self.zset(key)?.and_then(|zset| zset.get(member)) {
+            Some(score) => Ok(RespInternalValue::BulkString(score_to_bytes(*score))),
+            _ => Ok(RespInternalValue::Nil),
+        }
+    }
+
+    fn zcard(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?;
+        args.finish()?;
+        let len = self.zset(key)?.map(|zset| zset.len()).unwrap_or(0);
+        Ok(RespInternalValue::Int(len as i64))
+    }
+
+    fn zpopmin(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?;
+        let count = match args.is_empty() {
+            true => 1,
+            false => args.next_usize()?,
+        };
+        args.finish()?;
+        Ok(scored_members_to_resp(self.zset_pop_min(key, count)?, true))
+    }
+
+    fn blocking_pop(&mut self, args: &mut Args) -> Result<Execution, String> {
+        let (keys, kind, timeout) = match args.name() {
+            "BLMOVE" => {
+                let source = args.next()?.to_vec();
+                let destination = args.next()?.to_vec();
+                let from = parse_side(args.next()?)?;
+                let to = parse_side(args.next()?)?;
+                let timeout = args.next()?.to_vec();
+                args.finish()?;
+                (vec![source], PopKind::Move { destination, from, to }, timeout)
+            }
+            name => {
+                // the last argument is the timeout
+                let mut keys: Vec<Vec<u8>> = args.rest().map(|key| key.to_vec()).collect();
+                let timeout = match keys.len() {
+                    0 | 1 => return Err(wrong_number_of_args(name)),
+                    _ => keys.pop().unwrap(),
+                };
+                let kind = match name {
+                    "BLPOP" => PopKind::List(Side::Left),
+                    "BRPOP" => PopKind::List(Side::Right),
+                    _ => PopKind::MinScore,
+                };
+                (keys, kind, timeout)
+            }
+        };
+
+        let timeout = String::from_utf8_lossy(&timeout).parse::<f64>()
+            .map_err(|_| "ERR timeout is not a float or out of range".to_string())?;
+        if timeout < 0.0 || !timeout.is_finite() {
+            return Err("ERR timeout is negative".to_string());
+        }
+
+        let timeout = Duration::try_from_secs_f64(timeout)
+            .map_err(|_| "ERR timeout is out of range".to_string())?;
+        let read = PopRead { keys, kind, timeout };
+        match self.try_pop(&read) {
+            Some(reply) => Ok(Execution::Reply(reply)),
+            _ => Ok(Execution::Block(BlockingRead::Pop(read))),
+        }
+    }
+
+    fn try_pop(&mut self, read: &PopRead) -> Option<RespInternalValue> {
+        for key in read.keys.iter() {
+            let reply = match &read.kind {
+                PopKind::List(side) => self.list_pop(key, *side)
+                    .map(|element| element.map(|element|
+                        RespInternalValue::Array(
+                            vec![RespInternalValue::BulkString(key.clone()),
+                                 RespInternalValue::BulkString(element)]))),
+                PopKind::Move { destination, from, to } =>
+                    self.list_move(key, destination, *from, *to)
+                        .map(|element| element.map(RespInternalValue::BulkString)),
+                PopKind::MinScore => self.zset_pop_min(key, 1)
+                    .map(|members| members.into_iter().next().map(|(member, score)|
+                        RespInternalValue::Array(
+                            vec![RespInternalValue::BulkString(key.clone()),
+                                 RespInternalValue::BulkString(member),
+                                 RespInternalValue::BulkString(score_to_bytes(score))]))),
+            };
+
+            match reply {
+                Ok(Some(reply)) => return Some(reply),
+                Ok(None) => (),
+                Err(err) => return Some(RespInternalValue::Error(err)),
+            }
+        }
+        None
+    }
+
+    fn list_pop(&mut self, key: &[u8], side: Side) -> Result<Option<Vec<u8>>, String> {
+        let element = match self.list_mut(key)? {
+            Some(list) => match side {
+                Side::Left => list.pop_front(),
+                Side::Right => list.pop_back(),
+            },
+            _ => None,
+        };
+        self.remove_if_empty(key);
+        Ok(element)
+    }
+
+    fn list_move(&mut self, source: &[u8], destination: &[u8], from: Side, to: Side)
+                 -> Result<Option<Vec<u8>>, String> {
+        // check the destination type before the source is changed
+        self.list(destination)?;
+        let element = match self.list_pop(source, from)? {
+            Some(element) => element,
+            _ => return Ok(None),
+        };
+
+        let list = self.list_or_insert(destination.to_vec())?;
+        match to {
+            Side::Left => list.push_front(element.clone()),
+            Side::Right => list.push_back(element.clone()),
+        }
+        Ok(Some(element))
+    }
+
+    fn zset_pop_min(&mut self, key: &[u8], count: usize) -> Result<Vec<(Vec<u8>, f64)>, String> {
+        let popped: Vec<(Vec<u8>, f64)> = match self.zset_mut(key)? {
+            Some(zset) => {
+                let popped: Vec<(Vec<u8>, f64)> = sorted_members(zset).into_iter().take(count).collect();
+                for (member, _) in popped.iter() {
+                    zset.remove(member);
+                }
+                popped
+            }
+            _ => Vec::new(),
+        };
+        self.remove_if_empty(key);
+        Ok(popped)
+    }
+
+    fn xadd(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let mut no_make_stream = false;
+        let mut trim = None;
+        loop {
+            match args.peek() {
+                Some(option) if is_keyword(option, "NOMKSTREAM") => {
+                    args.next()?;
+                    no_make_stream = true;
+                }
+                Some(option) if is_keyword(option, "MAXLEN") || is_keyword(option, "MINID") => {
+                    args.next()?;
+                    trim = Some(parse_trim(option, args)?);
+                }
+                _ => break,
+            }
+        }
+        let id = args.next()?;
+        let fields = args.pairs()?;
+
+        if no_make_stream && self.stream(&key)?.is_none() {
+            return Ok(RespInternalValue::Nil);
+        }
+
+        let stream = self.stream_or_insert(key)?;
+        let id = match id {
+            b"*" => auto_id(stream.last_id)?,
+            _ if id.ends_with(b"-*") => auto_seq_id(stream.last_id, parse_id(&id[..id.len() - 2])?.0)?,
+            _ => check_new_id(stream.last_id, parse_id(id)?)?,
+        };
+
+        stream.entries.insert(id, fields);
+        stream.last_id = id;
+        stream.entries_added += 1;
+        if let Some(trim) = trim {
+            stream.trim(&trim);
+        }
+        Ok(RespInternalValue::BulkString(id_to_bytes(id)))
+    }
+
+    fn xtrim(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let keyword = args.next()?;
+        if !is_keyword(keyword, "MAXLEN") && !is_keyword(keyword, "MINID") {
+            return Err(SYNTAX_ERROR.to_string());
+        }
+        let trim = parse_trim(keyword, args)?;
+        args.finish()?;
+
+        let evicted = match self.stream_mut(&key)? {
+            Some(stream) => stream.trim(&trim),
+            _ => 0,
+        };
+        Ok(RespInternalValue::Int(evicted as i64))
+    }
+
+    fn xrange(&mut self, args: &mut Args, reverse: bool) -> CommandResult {
+        let key = args.next()?;
+        // XREVRANGE takes the end bound first
+        let (start, end) = match reverse {
+            false => (args.next()?, args.next()?),
+            true => {
+                let end = args.next()?;
+                (args.next()?, end)
+            }
+        };
+        let start = parse_range_bound(start, RangeBound::Start)?;
+        let end = parse_range_bound(end, RangeBound::End)?;
+        let count = match args.next_opt() {
+            Some(option) if is_keyword(option, "COUNT") => Some(args.next_usize()?),
+            Some(_) => return Err(SYNTAX_ERROR.to_string()),
+            _ => None,
+        };
+        args.finish()?;
+
+        let stream = match self.stream(key)? {
+            Some(x) => x,
+            _ => return Ok(RespInternalValue::Array(Vec::new())),
+        };
+
+        let entries = match (start, end) {
+            (Some(start), Some(end)) if start <= end => {
+                let range = stream.entries.range(start..=end);
+                let range: Box<dyn Iterator<Item=(&Id, &Fields)>> = match reverse {
+                    false => Box::new(range),
+                    true => Box::new(range.rev()),
+                };
+                range.take(count.unwrap_or(usize::MAX))
+                    .map(|(id, fields)| entry_to_resp(*id, Some(fields)))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        Ok(RespInternalValue::Array(entries))
+    }
+
+    fn xlen(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?;
+        args.finish()?;
+        let len = self.stream(key)?.map_or(0, |stream| stream.entries.len());
+        Ok(RespInternalValue::Int(len as i64))
+    }
+
+    fn xack(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let group = args.next()?.to_vec();
+        let mut ids = vec![parse_id(args.next()?)?];
+        for id in args.rest() {
+            ids.push(parse_id(id)?);
+        }
+
+        let group = match self.stream_mut(&key)?.and_then(|stream| stream.groups.get_mut(&group)) {
+            Some(x) => x,
+            _ => return Ok(RespInternalValue::Int(0)),
+        };
+        let acked = ids.iter().filter(|id| group.pending.remove(id).is_some()).count();
+        Ok(RespInternalValue::Int(acked as i64))
+    }
+
+    fn xdel(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let mut ids = vec![parse_id(args.next()?)?];
+        for id in args.rest() {
+            ids.push(parse_id(id)?);
+        }
+
+        // the deleted entries are kept within the pending entries lists like Redis does
+        let stream = match self.stream_mut(&key)? {
+            Some(x) => x,
+            _ => return Ok(RespInternalValue::Int(0)),
+        };
+        let deleted: Vec<Id> = ids.into_iter()
+            .filter(|id| stream.entries.remove(id).is_some())
+            .collect();
+        if let Some(max) = deleted.iter().max() {
+            stream.max_deleted_id = stream.max_deleted_id.max(*max);
+        }
+        Ok(RespInternalValue::Int(deleted.len() as i64))
+    }
+
+    fn xackdel(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let group = args.next()?.to_vec();
+        let (policy, ids) = parse_delete_ref_ids(args)?;
+
+        let no_group = || format!("NOGROUP No such key '{}' or consumer group '{}'",
+                                  String::from_utf8_lossy(&key), String::from_utf8_lossy(&group));
+        let stream = self.stream_mut(&key)?.ok_or_else(no_group)?;
+        let pending = &mut stream.groups.get_mut(&group).ok_or_else(no_group)?.pending;
+        for id in ids.iter() {
+            pending.remove(id);
+        }
+
+        let replies = ids.into_iter()
+            .map(|id| RespInternalValue::Int(stream.delete_with_ref(id, policy)))
+            .collect();
+        Ok(RespInternalValue::Array(replies))
+    }
+
+    fn xdelex(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let (policy, ids) = parse_delete_ref_ids(args)?;
+
+        let replies = match self.stream_mut(&key)? {
+            Some(stream) => ids.into_iter()
+                .map(|id| RespInternalValue::Int(stream.delete_with_ref(id, policy)))
+                .collect(),
+            _ => ids.iter().map(|_| RespInternalValue::Int(-1)).collect(),
+        };
+        Ok(RespInternalValue::Array(replies))
+    }
+
+    fn xpending(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let group = args.next()?.to_vec();
+        if args.is_empty() {
+            let (_, group) = self.entries_and_group(&key, &group)?;
+            return Ok(pending_summary_to_resp(group));
+        }
+
+        let min_idle = match args.peek() {
+            Some(option) if is_keyword(option, "IDLE") => {
+                args.next()?;
+                args.next_u64()?
+            }
+            _ => 0,
+        };
+        let start = parse_range_bound(args.next()?, RangeBound::Start)?;
+        let end = parse_range_bound(args.next()?, RangeBound::End)?;
+        let count = args.next_usize()?;
+        let consumer = args.next_opt().map(|consumer| consumer.to_vec());
+        args.finish()?;
+
+        let now = current_ms();
+        let (_, group) = self.entries_and_group(&key, &group)?;
+        let entries = match (start, end) {
+            (Some(start), Some(end)) if start <= end =>
+                group.pending.range(start..=end)
+                    .filter(|(_, pending)| consumer.as_ref().is_none_or(|x| *x == pending.consumer))
+                    .filter(|(_, pending)| now.saturating_sub(pending.delivered_at) >= min_idle)
+                    .take(count)
+                    .map(|(id, pending)| RespInternalValue::Array(vec![
+                        RespInternalValue::BulkString(id_to_bytes(*id)),
+                        RespInternalValue::BulkString(pending.consumer.clone()),
+                        RespInternalValue::Int(now.saturating_sub(pending.delivered_at) as i64),
+                        RespInternalValue::Int(pending.delivery_count as i64),
+                    ]))
+                    .collect(),
+            _ => Vec::new(),
+        };
+        Ok(RespInternalValue::Array(entries))
+    }
+
+    fn xclaim(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let group = args.next()?.to_vec();
+        let consumer = args.next()?.to_vec();
+        let min_idle = args.next_u64()?;
+        let mut ids = vec![parse_id(args.next()?)?];
+        while let Some(id) = args.peek().and_then(|id| parse_id(id).ok()) {
+            args.next()?;
+            ids.push(id);
+        }
+
+        let now = current_ms();
+        let mut delivered_at = now;
+        let mut retry_count = None;
+        let mut force = false;
+        let mut just_id = false;
+        while let Some(option) = args.next_opt() {
+            if is_keyword(option, "IDLE") {
+                delivered_at = now.saturating_sub(args.next_u64()?);
+            } else if is_keyword(option, "TIME") {
+                delivered_at = args.next_u64()?;
+            } else if is_keyword(option, "RETRYCOUNT") {
+                retry_count = Some(args.next_u64()?);
+            } else if is_keyword(option, "FORCE") {
+                force = true;
+            } else if is_keyword(option, "JUSTID") {
+                just_id = true;
+            } else if is_keyword(option, "LASTID") {
+                parse_id(args.next()?)?;
+            } else {
+                return Err(SYNTAX_ERROR.to_string());
+            }
+        }
+
+        let (entries, group) = self.entries_and_group(&key, &group)?;
+        group.see_consumer(&consumer);
+
+        let mut claimed = Vec::new();
+        for id in ids.into_iter() {
+            let fields = match entries.get(&id) {
+                Some(x) => x,
+                _ => {
+                    // the entry was deleted from the stream
+                    group.pending.remove(&id);
+                    continue;
+                }
+            };
+
+            let is_new = !group.pending.contains_key(&id);
+            if is_new && !force {
+                continue;
+            }
+            let pending = group.pending.entry(id).or_insert_with(|| PendingEntry {
+                consumer: consumer.clone(),
+                delivered_at: now,
+                delivery_count: 0,
+            });
+            // the forced entries are claimed regardless of the idle time
+            if !is_new && now.saturating_sub(pending.delivered_at) < min_idle {
+                continue;
+            }
+
+            pending.consumer = consumer.clone();
+            pending.delivered_at = delivered_at;
+            match retry_count {
+                Some(count) => pending.delivery_count = count,
+                None if !just_id => pending.delivery_count += 1,
+                _ => (),
+            }
+            claimed.push(claimed_to_resp(id, fields, just_id));
+        }
+
+        if !claimed.is_empty() {
+            group.see_consumer(&consumer).active_at = Some(now);
+        }
+        Ok(RespInternalValue::Array(claimed))
+    }
+
+    fn xautoclaim(&mut self, args: &mut Args) -> CommandResult {
+        const DEFAULT_COUNT: usize = 100;
+        const ATTEMPTS_FACTOR: usize = 10;
+
+        let key = args.next()?.to_vec();
+        let group = args.next()?.to_vec();
+        let consumer = args.next()?.to_vec();
+        let min_idle = args.next_u64()?;
+        let start = parse_range_bound(args.next()?, RangeBound::Start)?;
+
+        let mut count = DEFAULT_COUNT;
+        let mut just_id = false;
+        while let Some(option) = args.next_opt() {
+            if is_keyword(option, "COUNT") {
+                count = args.next_usize()?;
+            } else if is_keyword(option, "JUSTID") {
+                just_id = true;
+            } else {
+                return Err(SYNTAX_ERROR.to_string());
+            }
+        }
+
+        let now = current_ms();
+        let (entries, group) = self.entries_and_group(&key, &group)?;
+        group.see_consumer(&consumer);
+
+        let ids: Vec<Id> = match start {
+            Some(start) => group.pending.range(start..).map(|(id, _)| *id).collect(),
+            _ => Vec::new(),
+        };
+        let mut ids = ids.into_iter();
+
+        let mut claimed = Vec::new();
+        let mut deleted = Vec::new();
+        let mut attempts = count * ATTEMPTS_FACTOR;
+        while claimed.len() + deleted.len() < count && attempts > 0 {
+            attempts -= 1;
+            let id = match ids.next() {
+                Some(x) => x,
+                _ => break,
+            };
+            let fields = match entries.get(&id) {
+                Some(x) => x,
+                _ => {
+                    group.pending.remove(&id);
+                    deleted.push(RespInternalValue::BulkString(id_to_bytes(id)));
+                    continue;
+                }
+            };
+
+            let pending = match group.pending.get_mut(&id) {
+                Some(x) if now.saturating_sub(x.delivered_at) >= min_idle => x,
+                _ => continue,
+            };
+            pending.consumer = consumer.clone();
+            pending.delivered_at = now;
+            if !just_id {
+                pending.delivery_count += 1;
+            }
+            claimed.push(claimed_to_resp(id, fields, just_id));
+        }
+
+        if !claimed.is_empty() {
+            group.see_consumer(&consumer).active_at = Some(now);
+        }
+
+        // the zero cursor means the whole list has been scanned
+        let next = ids.next().unwrap_or((0, 0));
+        Ok(RespInternalValue::Array(vec![
+            RespInternalValue::BulkString(id_to_bytes(next)),
+            RespInternalValue::Array(claimed),
+            RespInternalValue::Array(deleted),
+        ]))
+    }
+
+    fn xgroup(&mut self, args: &mut Args) -> CommandResult {
+        let subcommand = String::from_utf8_lossy(args.next()?).to_uppercase();
+        match subcommand.as_str() {
+            "CREATE" => self.xgroup_create(args),
+            "DESTROY" => {
+                let (key, group) = (args.next()?.to_vec(), args.next()?.to_vec());
+                args.finish()?;
+                let stream = self.existing_stream_for_group(&key)?;
+                Ok(RespInternalValue::Int(stream.groups.remove(&group).is_some() as i64))
+            }
+            "SETID" => {
+                let (key, group) = (args.next()?.to_vec(), args.next()?.to_vec());
+                let id = args.next()?;
+                skip_entries_read(args)?;
+                let stream = self.existing_stream_for_group(&key)?;
+                let id = match id {
+                    b"$" => stream.last_id,
+                    _ => parse_id(id)?,
+                };
+                let group = group_mut(stream, &key, &group)?;
+                group.last_delivered = id;
+                Ok(ok())
+            }
+            "CREATECONSUMER" => {
+                let (key, group) = (args.next()?.to_vec(), args.next()?.to_vec());
+                let consumer = args.next()?.to_vec();
+                args.finish()?;
+                let stream = self.existing_stream_for_group(&key)?;
+                let group = group_mut(stream, &key, &group)?;
+                let is_new = !group.consumers.contains_key(&consumer);
+                group.see_consumer(&consumer);
+                Ok(RespInternalValue::Int(is_new as i64))
+            }
+            "DELCONSUMER" => {
+                let (key, group) = (args.next()?.to_vec(), args.next()?.to_vec());
+                let consumer = args.next()?.to_vec();
+                args.finish()?;
+                let stream = self.existing_stream_for_group(&key)?;
+                let group = group_mut(stream, &key, &group)?;
+                group.consumers.remove(&consumer);
+                let before = group.pending.len();
+                group.pending.retain(|_, pending| pending.consumer != consumer);
+                Ok(RespInternalValue::Int((before - group.pending.len()) as i64))
+            }
+            _ => Err(format!("ERR unknown subcommand '{}'", subcommand.to_lowercase())),
+        }
+    }
+
+    fn xinfo(&mut self, args: &mut Args) -> CommandResult {
+        const DEFAULT_FULL_COUNT: usize = 10;
+
+        let subcommand = String::from_utf8_lossy(args.next()?).to_uppercase();
+        let key = args.next()?.to_vec();
+        let no_key = || "ERR no such key".to_string();
+        match subcommand.as_str() {
+            "STREAM" => {
+                let full = match args.next_opt() {
+                    Some(option) if is_keyword(option, "FULL") => match args.next_opt() {
+                        Some(option) if is_keyword(option, "COUNT") => Some(args.next_usize()?),
+                        Some(_) => return Err(SYNTAX_ERROR.to_string()),
+                        _ => Some(DEFAULT_FULL_COUNT),
+                    },
+                    Some(_) => return Err(SYNTAX_ERROR.to_string()),
+                    _ => None,
+                };
+                args.finish()?;
+
+                let stream = self.stream(&key)?.ok_or_else(no_key)?;
+                Ok(match full {
+                    Some(count) => stream_full_info_to_resp(stream, count),
+                    _ => stream_info_to_resp(stream),
+                })
+            }
+            "GROUPS" => {
+                args.finish()?;
+                let stream = self.stream(&key)?.ok_or_else(no_key)?;
+                let groups = sorted_groups(stream).into_iter()
+                    .map(|(name, group)| info_to_resp(vec![
+                        ("name", RespInternalValue::BulkString(name.clone())),
+                        ("consumers", RespInternalValue::Int(group.consumers.len() as i64)),
+                        ("pending", RespInternalValue::Int(group.pending.len() as i64)),
+                        ("last-delivered-id", RespInternalValue::BulkString(id_to_bytes(group.last_delivered))),
+                        // the mock does not track the entries-read counter, so the lag is unknown
+                        ("entries-read", RespInternalValue::Nil),
+                        ("lag", RespInternalValue::Nil),
+                    ]))
+                    .collect();
+                Ok(RespInternalValue::Array(groups))
+            }
+            "CONSUMERS" => {
+                let group_name = args.next()?.to_vec();
+                args.finish()?;
+                let stream = self.stream(&key)?.ok_or_else(no_key)?;
+                let group = stream.groups.get(&group_name)
+                    .ok_or_else(|| format!("NOGROUP No such consumer group '{}' for key name '{}'",
+                                           String::from_utf8_lossy(&group_name), String::from_utf8_lossy(&key)))?;
+
+                let now = current_ms();
+                let consumers = group.consumers.iter()
+                    .map(|(name, consumer)| info_to_resp(vec![
+                        ("name", RespInternalValue::BulkString(name.clone())),
+                        ("pending", RespInternalValue::Int(consumer_pending(group, name).count() as i64)),
+                        ("idle", RespInternalValue::Int(now.saturating_sub(consumer.seen_at) as i64)),
+                        ("inactive", RespInternalValue::Int(
+                            consumer.active_at.map(|time| now.saturating_sub(time) as i64).unwrap_or(-1))),
+                    ]))
+                    .collect();
+                Ok(RespInternalValue::Array(consumers))
+            }
+            _ => Err(format!("ERR unknown subcommand '{}'", subcommand.to_lowercase())),
+        }
+    }
+
+    fn xgroup_create(&mut self, args: &mut Args) -> CommandResult {
+        let key = args.next()?.to_vec();
+        let group = args.next()?.to_vec();
+        let id = args.next()?.to_vec();
+
+        let mut make_stream = false;
+        while let Some(option) = args.next_opt() {
+            if is_keyword(option, "MKSTREAM") {
+                make_stream = true;
+            } else if is_keyword(option, "ENTRIESREAD") {
+                args.next_i64()?;
+            } else {
+                return Err(SYNTAX_ERROR.to_string());
+            }
+        }
+
+        let stream = match make_stream {
+            true => self.stream_or_insert(key)?,
+            false => self.existing_stream_for_group(&key)?,
+        };
+        if stream.groups.contains_key(&group) {
+            return Err("BUSYGROUP Consumer Group name already exists".to_string());
+        }
+
+        let last_delivered = match id.as_slice() {
+            b"$" => stream.last_id,
+            _ => parse_id(&id)?,
+        };
+        let group_data = Group { last_delivered, pending: BTreeMap::new(), consumers: BTreeMap::new() };
+        stream.groups.insert(group, group_data);
+        Ok(ok())
+    }
+
+    fn stream_read(&mut self, args: &mut Args) -> Result<Execution, String> {
+        let group = match args.name() {
+            "XREADGROUP" => {
+                if !is_keyword(args.next()?, "GROUP") {
+                    return Err(SYNTAX_ERROR.to_string());
+                }
+                Some((args.next()?.to_vec(), args.next()?.to_vec()))
+            }
+            _ => None,
+        };
+
+        let mut count = None;
+        let mut block = None;
+        let mut no_ack = false;
+        loop {
+            let option = args.next()?;
+            if is_keyword(option, "COUNT") {
+                count = Some(args.next_usize()?);
+            } else if is_keyword(option, "BLOCK") {
+                block = Some(Duration::from_millis(args.next_u64()?));
+            } else if is_keyword(option, "NOACK") && group.is_some() {
+                no_ack = true;
+            } else if is_keyword(option, "STREAMS") {
+                break;
+            } else {
+                return Err(SYNTAX_ERROR.to_string());
+            }
+        }
+
+        let rest: Vec<&[u8]> = args.rest().collect();
+        if rest.is_empty() || rest.len() % 2 == 1 {
+            return Err(format!(
+                "ERR Unbalanced '{}' list of streams: for each stream key an ID or '$' must be specified.",
+                args.name().to_lowercase()));
+        }
+
+        let (keys, ids) = rest.split_at(rest.len() / 2);
+        let mut streams = Vec::with_capacity(keys.len());
+        for (key, id) in keys.iter().zip(ids.iter()) {
+            let from = match (*id, &group) {
+                (b">", Some((group, consumer))) => {
+                    self.touch_consumer(key, group, consumer)?;
+                    ReadFrom::NewInGroup
+                }
+                (b"$", None) => ReadFrom::After(
+                    self.stream(key)?.map(|stream| stream.last_id).unwrap_or((0, 0))),
+                (id, Some((group, consumer))) => {
+                    self.touch_consumer(key, group, consumer)?;
+                    ReadFrom::After(parse_id(id)?)
+                }
+                (id, None) => ReadFrom::After(parse_id(id)?),
+            };
+            streams.push((key.to_vec(), from));
+        }
+
+        let read = StreamRead { group, count, block, no_ack, streams };
+        match self.try_read_stream(&read) {
+            Some(reply) => Ok(Execution::Reply(reply)),
+            _ => match read.block {
+                Some(_) => Ok(Execution::Block(BlockingRead::Stream(read))),
+                _ => Ok(Execution::Reply(RespInternalValue::Nil)),
+            }
+        }
+    }
+
+    fn read_after(&self, key: &[u8], after: Id, count: Option<usize>) -> Vec<RespInternalValue> {
+        let stream = match self.keys.get(key) {
+            Some(Value::Stream(x)) => x,
+            _ => return Vec::new(),
+        };
+        stream.entries.range(after..)
+            .filter(|(id, _)| **id > after)
+            .take(count.unwrap_or(usize::MAX))
+            .map(|(id, fields)| entry_to_resp(*id, Some(fields)))
+            .collect()
+    }
+
+    fn read_new_in_group(&mut self, key: &[u8], group: &[u8], consumer: &[u8],
+                         count: Option<usize>, no_ack: bool) -> Vec<RespInternalValue> {
+        let stream = match self.keys.get_mut(key) {
+            Some(Value::Stream(x)) => x,
+            _ => return Vec::new(),
+        };
+        let group = match stream.groups.get_mut(group) {
+            Some(x) => x,
+            _ => return Vec::new(),
+        };
+
+        let mut result = Vec::new();
+        let last_delivered = group.last_delivered;
+        for (id, fields) in stream.entries.range(last_delivered..)
+            .filter(|(id, _)| **id > last_delivered)
+            .take(count.unwrap_or(usize::MAX)) {
+            result.push(entry_to_resp(*id, Some(fields)));
+            group.last_delivered = *id;
+            if no_ack {
+                continue;
+            }
+            let pending = PendingEntry { consumer: consumer.to_vec(), delivered_at: current_ms(), delivery_count: 1 };
+            group.pending.insert(*id, pending);
+        }
+        if !result.is_empty() {
+            group.see_consumer(consumer).active_at = Some(current_ms());
+        }
+        result
+    }
+
+    fn read_pending_of_consumer(&mut self, key: &[u8], group: &[u8], consumer: &[u8],
+                                after: Id, count: Option<usize>) -> Vec<RespInternalValue> {
+        let stream = match self.keys.get_mut(key) {
+            Some(Value::Stream(x)) => x,
+            _ => return Vec::new(),
+        };
+        let group = match stream.groups.get_mut(group) {
+            Some(x) => x,
+            _ => return Vec::new(),
+        };
+
+        let entries = &stream.entries;
+        let now = current_ms();
+        group.see_consumer(consumer).active_at = Some(now);
+        group.pending.iter_mut()
+            .filter(|(id, pending)| **id > after && pending.consumer == consumer)
+            .take(count.unwrap_or(usize::MAX))
+            .map(|(id, pending)| {
+                // the history reading is a delivery too
+                pending.delivered_at = now;
+                pending.delivery_count += 1;
+                // the entry could be deleted but still be pending
+                entry_to_resp(*id, entries.get(id))
+            })
+            .collect()
+    }
+
+    fn touch_consumer(&mut self, key: &[u8], group: &[u8], consumer: &[u8]) -> Result<(), String> {
+        let no_group = || format!(
+            "NOGROUP No such key '{}' or consumer group '{}' in XREADGROUP with GROUP option",
+            String::from_utf8_lossy(key), String::from_utf8_lossy(group));
+
+        let group = match self.stream_mut(key)? {
+            Some(stream) => stream.groups.get_mut(group).ok_or_else(no_group)?,
+            _ => return Err(no_group()),
+        };
+        group.see_consumer(consumer);
+        Ok(())
+    }
+
+    fn entries_and_group(&mut self, key: &[u8], group: &[u8])
+                         -> Result<(&BTreeMap<Id, Fields>, &mut Group), String> {
+        let no_group = || format!("NOGROUP No such key '{}' or consumer group '{}'",
+                                  String::from_utf8_lossy(key), String::from_utf8_lossy(group));
+
+        let stream = self.stream_mut(key)?.ok_or_else(no_group)?;
+        let group = stream.groups.get_mut(group).ok_or_else(no_group)?;
+        Ok((&stream.entries, group))
+    }
+
+    fn string(&self, key: &[u8]) -> Result<Option<&Vec<u8>>, String> {
+        match self.keys.get(key) {
+            Some(Value::String(x)) => Ok(Some(x)),
+            Some(_) => Err(WRONG_TYPE.to_string()),
+            _ => Ok(None),
+        }
+    }
+
+    fn hash(&self, key: &[u8]) -> Result<Option<&HashValue>, String> {
+        match self.keys.get(key) {
+            Some(Value::Hash(x)) => Ok(Some(x)),
+            Some(_) => Err(WRONG_TYPE.to_string()),
+            _ => Ok(None),
+        }
+    }
+
+    fn hash_mut(&mut self, key: &[u8]) -> Result<Option<&mut HashValue>, String> {
+        match self.keys.get_mut(key) {
+            Some(Value::Hash(x)) => Ok(Some(x)),
+            Some(_) => Err(WRONG_TYPE.to_string()),
+            _ => Ok(None),
+        }
+    }
+
+    fn hash_or_insert(&mut self, key: Vec<u8>) -> Result<&mut HashValue, String> {
+        match self.keys.entry(key).or_insert_with(|| Value::Hash(HashMap::new())) {
+            Value::Hash(x) => Ok(x),
+            _ => Err(WRONG_TYPE.to_string()),
+        }
+    }
+
+    fn list(&self, key: &[u8]) -> Result<Option<&VecDeque<Vec<u8>>>, String> {
+        match self.keys.get(key) {
+            Some(Value::List(x)) => Ok(Some(x)),
+            Some(_) => Err(WRONG_TYPE.to_string()),
+            _ => Ok(None),
+        }
+    }
+
+    fn list_mut(&mut self, key: &[u8]) -> Result<Option<&mut VecDeque<Vec<u8>>>, String> {
+        match self.keys.get_mut(key) {
+            Some(Value::List(x)) => Ok(Some(x)),
+            Some(_) => Err(WRONG_TYPE.to_string()),
+            _ => Ok(None),
+        }
+    }
+
+    fn list_or_insert(&mut self, key: Vec<u8>) -> Result<&mut VecDeque<Vec<u8>>, String> {
+        match self.keys.entry(key).or_insert_with(|| Value::List(VecDeque::new())) {
+            Value::List(x) => Ok(x),
+            _ => Err(WRONG_TYPE.to_string()),
+        }
+    }
+
+    fn zset(&self, key: &[u8]) -> Result<Option<&ZSetValue>, String> {
+        match self.keys.get(key) {
+            Some(Value::ZSet(x)) => Ok(Some(x)),
+            Some(_) => Err(WRONG_TYPE.to_string()),
+            _ => Ok(None),
+        }
+    }
+
+    fn zset_mut(&mut self, key: &[u8]) -> Result<Option<&mut ZSetValue>, String> {
+        match self.keys.get_mut(key) {
+            Some(Value::ZSet(x)) => Ok(Some(x)),
+            Some(_) => Err(WRONG_TYPE.to_string()),
+            _ => Ok(None),
+        }
+    }
+
+    fn zset_or_insert(&mut self, key: Vec<u8>) -> Result<&mut ZSetValue, String> {
+        match self.keys.entry(key).or_insert_with(|| Value::ZSet(HashMap::new())) {
+            Value::ZSet(x) => Ok(x),
+            _ => Err(WRONG_TYPE.to_string()),
+        }
+    }
+
+    fn stream(&self, key: &[u8]) -> Result<Option<&StreamValue>, String> {
+        match self.keys.get(key) {
+            Some(Value::Stream(x)) => Ok(Some(x)),
+            Some(_) => Err(WRONG_TYPE.to_string()),
+            _ => Ok(None),
+        }
+    }
+
+    fn stream_mut(&mut self, key: &[u8]) -> Result<Option<&mut StreamValue>, String> {
+        match self.keys.get_mut(key) {
+            Some(Value::Stream(x)) => Ok(Some(x)),
+            Some(_) => Err(WRONG_TYPE.to_string()),
+            _ => Ok(None),
+        }
+    }
+
+    fn stream_or_insert(&mut self, key: Vec<u8>) -> Result<&mut StreamValue, String> {
+        match self.keys.entry(key).or_insert_with(|| Value::Stream(StreamValue::default())) {
+            Value::Stream(x) => Ok(x),
+            _ => Err(WRONG_TYPE.to_string()),
+        }
+    }
+
+    fn existing_stream_for_group(&mut self, key: &[u8]) -> Result<&mut StreamValue, String> {
+        self.stream_mut(key)?
+            .ok_or_else(|| "ERR The XGROUP subcommand requires the key to exist. \
+                            Note that for CREATE you may want to use the MKSTREAM option \
+                            to create an empty stream automatically.".to_string())
+    }
+
+    fn remove_if_empty(&mut self, key: &[u8]) {
+        let is_empty = match self.keys.get(key) {
+            Some(Value::Hash(x)) => x.is_empty(),
+            Some(Value::List(x)) => x.is_empty(),
+            Some(Value::ZSet(x)) => x.is_empty(),
+            _ => false,
+        };
+        if is_empty {
+            self.keys.remove(key);
+        }
+    }
+}
+
+/// Cursor over the request arguments.
+struct Args<'a> {
+    name: &'a str,
+    args: &'a [Vec<u8>],
+    pos: usize,
+}
+
+impl<'a> Args<'a> {
+    fn new(name: &'a str, args: &'a [Vec<u8>]) -> Self {
+        Args { name, args, pos: 0 }
+    }
+
+    fn name(&self) -> &'a str {
+        self.name
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.args.len()
+    }
+
+    fn peek(&self) -> Option<&'a [u8]> {
+        self.args.get(self.pos).map(|arg| arg.as_slice())
+    }
+
+    fn next_opt(&mut self) -> Option<&'a [u8]> {
+        let arg = self.args.get(self.pos)?;
+        self.pos += 1;
+        Some(arg.as_slice())
+    }
+
+    fn next(&mut self) -> Result<&'a [u8], String> {
+        let name = self.name;
+        self.next_opt().ok_or_else(|| wrong_number_of_args(name))
+    }
+
+    fn next_i64(&mut self) -> Result<i64, String> {
+        parse_i64(self.next()?)
+    }
+
+    fn next_u64(&mut self) -> Result<u64, String> {
+        String::from_utf8_lossy(self.next()?).parse::<u64>()
+            .map_err(|_| NOT_INTEGER.to_string())
+    }
+
+    fn next_usize(&mut self) -> Result<usize, String> {
+        String::from_utf8_lossy(self.next()?).parse::<usize>()
+            .map_err(|_| NOT_INTEGER.to_string())
+    }
+
+    /// Take all of the remaining arguments.
+    fn rest(&mut self) -> impl Iterator<Item=&'a [u8]> {
+        let rest = &self.args[self.pos.min(self.args.len())..];
+        self.pos = self.args.len();
+        rest.iter().map(|arg| arg.as_slice())
+    }
+
+    /// Take all of the remaining arguments as at least one pair.
+    fn pairs(&mut self) -> Result<Fields, String> {
+        let rest: Vec<&[u8]> = self.rest().collect();
+        if rest.is_empty() || rest.len() % 2 == 1 {
+            return Err(wrong_number_of_args(self.name));
+        }
+        Ok(rest.chunks_exact(2).map(|pair| (pair[0].to_vec(), pair[1].to_vec())).collect())
+    }
+
+    /// Check if there are no unexpected arguments.
+    fn finish(&self) -> Result<(), String> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(wrong_number_of_args(self.name)),
+        }
+    }
+}
+
+enum RangeBound {
+    Start,
+    End,
+}
+
+/// Reference policy of XACKDEL/XDELEX
+#[derive(Clone, Copy)]
+enum DeleteRef {
+    KeepRef,
+    DelRef,
+    Acked,
+}
+
+enum TrimThreshold {
+    MaxLen(usize),
+    MinId(Id),
+}
+
+/// XADD and XTRIM trimming: MAXLEN|MINID [=|~] threshold [LIMIT count]
+struct StreamTrim {
+    strategy: TrimThreshold,
+    limit: Option<usize>,
+}
+
+/// Parse the trimming arguments that follow the MAXLEN or MINID `keyword`.
+fn parse_trim(keyword: &[u8], args: &mut Args) -> Result<StreamTrim, String> {
+    let approximate = match args.peek() {
+        Some(b"~") => { args.next()?; true }
+        Some(b"=") => { args.next()?; false }
+        _ => false,
+    };
+
+    let strategy = match is_keyword(keyword, "MAXLEN") {
+        true => TrimThreshold::MaxLen(args.next_usize()?),
+        false => {
+            TrimThreshold::MinId(parse_id(args.next()?)?)
+        }
+    };
+
+    let limit = match args.peek() {
+        Some(option) if is_keyword(option, "LIMIT") => {
+            args.next()?;
+            Some(args.next_usize()?)
+        }
+        _ => None,
+    };
+    if limit.is_some() && !approximate {
+        return Err("ERR syntax error, LIMIT cannot be used without the special ~ option".to_string());
+    }
+    Ok(StreamTrim { strategy, limit })
+}
+
+fn ok() -> RespInternalValue {
+    RespInternalValue::Status("OK".to_string())
+}
+
+fn wrong_number_of_args(name: &str) -> String {
+    format!("ERR wrong number of arguments for '{}' command", name.to_lowercase())
+}
+
+fn is_keyword(arg: &[u8], keyword: &str) -> bool {
+    arg.eq_ignore_ascii_case(keyword.as_bytes())
+}
+
+fn parse_i64(arg: &[u8]) -> Result<i64, String> {
+    String::from_utf8_lossy(arg).parse::<i64>()
+        .map_err(|_| NOT_INTEGER.to_string())
+}
+
+fn parse_side(arg: &[u8]) -> Result<Side, String> {
+    if is_keyword(arg, "LEFT") {
+        Ok(Side::Left)
+    } else if is_keyword(arg, "RIGHT") {
+        Ok(Side::Right)
+    } else {
+        Err(SYNTAX_ERROR.to_string())
+    }
+}
+
+/// Convert start and stop indexes (negative ones are offsets from the end)
+/// into the range of a sequence with the `len` elements.
+fn index_range(start: i64, stop: i64, len: usize) -> std::ops::Range<usize> {
+    let len = len as i64;
+    let start = if start < 0 { (len + start).max(0) } else { start };
+    let stop = if stop < 0 { len + stop } else { stop.min(len - 1) };
+    match start > stop || start >= len {
+        true => 0..0,
+        false => start as usize..(stop + 1) as usize,
+    }
+}
+
+fn parse_score(arg: &[u8]) -> Result<f64, String> {
+    match String::from_utf8_lossy(arg).parse::<f64>() {
+        Ok(score) if !score.is_nan() => Ok(score),
+        _ => Err(NOT_FLOAT.to_string()),
+    }
+}
+
+/// Parse a score bound like "1.5", "(1.5" or "-inf",
+/// returns the score and true if the bound is exclusive.
+fn parse_score_bound(arg: &[u8]) -> Result<(f64, bool), String> {
+    let (arg, is_exclusive) = match arg.first() {
+        Some(b'(') => (&arg[1..], true),
+        _ => (arg, false),
+    };
+    let score = parse_score(arg).map_err(|_| "ERR min or max is not a float".to_string())?;
+    Ok((score, is_exclusive))
+}
+
+fn score_to_bytes(score: f64) -> Vec<u8> {
+    score.to_string().into_bytes()
+}
+
+/// Get members ordered by the score, members with the same score are ordered lexicographically.
+fn sorted_members(zset: &ZSetValue) -> Vec<(Vec<u8>, f64)> {
+    let mut members: Vec<(Vec<u8>, f64)> = zset.iter()
+        .map(|(member, score)| (member.clone(), *score))
+        .collect();
+    members.sort_by(|(member1, score1), (member2, score2)|
+        score1.partial_cmp(score2).unwrap().then_with(|| member1.cmp(member2)));
+    members
+}
+
+fn scored_members_to_resp(members: Vec<(Vec<u8>, f64)>, with_scores: bool) -> RespInternalValue {
+    let mut result = Vec::with_capacity(members.len() * 2);
+    for (member, score) in members.into_iter() {
+        result.push(RespInternalValue::BulkString(member));
+        if with_scores {
+            result.push(RespInternalValue::BulkString(score_to_bytes(score)));
+        }
+    }
+    RespInternalValue::Array(result)
+}
+
+fn current_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|x| x.as_millis() as u64).unwrap_or(0)
+}
+
+fn id_to_bytes((ms, seq): Id) -> Vec<u8> {
+    format!("{}-{}", ms, seq).into_bytes()
+}
+
+fn entry_to_resp(id: Id, fields: Option<&Fields>) -> RespInternalValue {
+    let fields = match fields {
+        Some(fields) => RespInternalValue::Array(
+            fields.iter()
+                .flat_map(|(field, value)|
+                    vec![RespInternalValue::BulkString(field.clone()),
+                         RespInternalValue::BulkString(value.clone())])
+                .collect()),
+        _ => RespInternalValue::Nil,
+    };
+    RespInternalValue::Array(vec![RespInternalValue::BulkString(id_to_bytes(id)), fields])
+}
+
+/// XINFO reply: flat array of field-value pairs.
+fn info_to_resp(fields: Vec<(&str, RespInternalValue)>) -> RespInternalValue {
+    RespInternalValue::Array(
+        fields.into_iter()
+            .flat_map(|(field, value)| vec![RespInternalValue::BulkString(field.as_bytes().to_vec()), value])
+            .collect())
+}
+
+/// Fields common for XINFO STREAM and XINFO STREAM FULL replies.
+/// The mock has no radix tree, so its stats are approximated by the count of entries.
+fn stream_info_fields(stream: &StreamValue) -> Vec<(&'static str, RespInternalValue)> {
+    let first_id = stream.entries.keys().next().cloned().unwrap_or((0, 0));
+    vec![
+        ("length", RespInternalValue::Int(stream.entries.len() as i64)),
+        ("radix-tree-keys", RespInternalValue::Int(stream.entries.len() as i64)),
+        ("radix-tree-nodes", RespInternalValue::Int(stream.entries.len() as i64 + 1)),
+        ("last-generated-id", RespInternalValue::BulkString(id_to_bytes(stream.last_id))),
+        ("max-deleted-entry-id", RespInternalValue::BulkString(id_to_bytes(stream.max_deleted_id))),
+        ("entries-added", RespInternalValue::Int(stream.entries_added as i64)),
+        ("recorded-first-entry-id", RespInternalValue::BulkString(id_to_bytes(first_id))),
+    ]
+}
+
+fn stream_info_to_resp(stream: &StreamValue) -> RespInternalValue {
+    let entry_or_nil = |entry: Option<(&Id, &Fields)>| match entry {
+        Some((id, fields)) => entry_to_resp(*id, Some(fields)),
+        _ => RespInternalValue::Nil,
+    };
+
+    let mut fields = stream_info_fields(stream);
+    fields.push(("groups", RespInternalValue::Int(stream.groups.len() as i64)));
+    fields.push(("first-entry", entry_or_nil(stream.entries.iter().next())));
+    fields.push(("last-entry", entry_or_nil(stream.entries.iter().next_back())));
+    info_to_resp(fields)
+}
+
+/// XINFO STREAM FULL reply, the zero `count` means all of the entries.
+fn stream_full_info_to_resp(stream: &StreamValue, count: usize) -> RespInternalValue {
+    let count = match count {
+        0 => usize::MAX,
+        _ => count,
+    };
+
+    let entries = stream.entries.iter()
+        .take(count)
+        .map(|(id, fields)| entry_to_resp(*id, Some(fields)))
+        .collect();
+    let groups = sorted_groups(stream).into_iter()
+        .map(|(name, group)| {
+            let pending = group.pending.iter()
+                .take(count)
+                .map(|(id, pending)| RespInternalValue::Array(vec![
+                    RespInternalValue::BulkString(id_to_bytes(*id)),
+                    RespInternalValue::BulkString(pending.consumer.clone()),
+                    RespInternalValue::Int(pending.delivered_at as i64),
+                    RespInternalValue::Int(pending.delivery_count as i64),
+                ]))
+                .collect();
+            let consumers = group.consumers.iter()
+                .map(|(consumer_name, consumer)| {
+                    let pending = consumer_pending(group, consumer_name)
+                        .take(count)
+                        .map(|(id, pending)| RespInternalValue::Array(vec![
+                            RespInternalValue::BulkString(id_to_bytes(*id)),
+                            RespInternalValue::Int(pending.delivered_at as i64),
+                            RespInternalValue::Int(pending.delivery_count as i64),
+                        ]))
+                        .collect();
+                    info_to_resp(vec![
+                        ("name", RespInternalValue::BulkString(consumer_name.clone())),
+                        ("seen-time", RespInternalValue::Int(consumer.seen_at as i64)),
+                        ("active-time", RespInternalValue::Int(consumer.active_at.map(|x| x as i64).unwrap_or(-1))),
+                        ("pel-count", RespInternalValue::Int(consumer_pending(group, consumer_name).count() as i64)),
+                        ("pending", RespInternalValue::Array(pending)),
+                    ])
+                })
+                .collect();
+
+            info_to_resp(vec![
+                ("name", RespInternalValue::BulkString(name.clone())),
+                ("last-delivered-id", RespInternalValue::BulkString(id_to_bytes(group.last_delivered))),
+                ("entries-read", RespInternalValue::Nil),
+                ("lag", RespInternalValue::Nil),
+                ("pel-count", RespInternalValue::Int(group.pending.len() as i64)),
+                ("pending", RespInternalValue::Array(pending)),
+                ("consumers", RespInternalValue::Array(consumers)),
+            ])
+        })
+        .collect();
+
+    let mut fields = stream_info_fields(stream);
+    fields.push(("entries", RespInternalValue::Array(entries)));
+    fields.push(("groups", RespInternalValue::Array(groups)));
+    info_to_resp(fields)
+}
+
+/// Groups sorted by their names like Redis does.
+fn sorted_groups(stream: &StreamValue) -> Vec<(&Vec<u8>, &Group)> {
+    let mut groups: Vec<(&Vec<u8>, &Group)> = stream.groups.iter().collect();
+    groups.sort_by_key(|(name, _)| *name);
+    groups
+}
+
+fn consumer_pending<'a>(group: &'a Group, consumer: &'a [u8]) -> impl Iterator<Item=(&'a Id, &'a PendingEntry)> {
+    group.pending.iter().filter(move |(_, pending)| pending.consumer == consumer)
+}
+
+/// XPENDING reply in the summary form, the consumers are sorted by their names like Redis does.
+fn pending_summary_to_resp(group: &Group) -> RespInternalValue {
+    let (min, max) = match (group.pending.keys().next(), group.pending.keys().next_back()) {
+        (Some(min), Some(max)) => (*min, *max),
+        _ => return RespInternalValue::Array(vec![RespInternalValue::Int(0), RespInternalValue::Nil,
+                                                  RespInternalValue::Nil, RespInternalValue::Nil]),
+    };
+
+    let mut consumers: BTreeMap<&[u8], u64> = BTreeMap::new();
+    for pending in group.pending.values() {
+        *consumers.entry(&pending.consumer).or_insert(0) += 1;
+    }
+    let consumers = consumers.into_iter()
+        .map(|(consumer, count)| RespInternalValue::Array(vec![
+            RespInternalValue::BulkString(consumer.to_vec()),
+            RespInternalValue::BulkString(count.to_string().into_bytes()),
+        ]))
+        .collect();
+
+    RespInternalValue::Array(vec![
+        RespInternalValue::Int(group.pending.len() as i64),
+        RespInternalValue::BulkString(id_to_bytes(min)),
+        RespInternalValue::BulkString(id_to_bytes(max)),
+        RespInternalValue::Array(consumers),
+    ])
+}
+
+/// Claimed entry as a pair: [id, fields] or the id only if JUSTID is specified.
+fn claimed_to_resp(id: Id, fields: &Fields, just_id: bool) -> RespInternalValue {
+    match just_id {
+        true => RespInternalValue::BulkString(id_to_bytes(id)),
+        false => entry_to_resp(id, Some(fields)),
+    }
+}
+
+/// Parse "<ms>-<seq>" or "<ms>" that means "<ms>-0".
+fn parse_id(id: &[u8]) -> Result<Id, String> {
+    let id = String::from_utf8_lossy(id);
+    let parse = |x: &str| x.parse::<u64>().map_err(|_| INVALID_ID.to_string());
+    match id.find('-') {
+        Some(pos) => Ok((parse(&id[..pos])?, parse(&id[pos + 1..])?)),
+        _ => Ok((parse(&id)?, 0)),
+    }
+}
+
+/// Parse XACKDEL/XDELEX arguments: [KEEPREF | DELREF | ACKED] IDS numids id [id ...]
+fn parse_delete_ref_ids(args: &mut Args) -> Result<(DeleteRef, Vec<Id>), String> {
+    let mut policy = DeleteRef::KeepRef;
+    loop {
+        let option = args.next()?;
+        if is_keyword(option, "KEEPREF") {
+            policy = DeleteRef::KeepRef;
+        } else if is_keyword(option, "DELREF") {
+            policy = DeleteRef::DelRef;
+        } else if is_keyword(option, "ACKED") {
+            policy = DeleteRef::Acked;
+        } else if is_keyword(option, "IDS") {
+            break;
+        } else {
+            return Err(SYNTAX_ERROR.to_string());
+        }
+    }
+
+    let count = args.next_usize()?;
+    let ids = args.rest().map(parse_id).collect::<Result<Vec<Id>, String>>()?;
+    if count == 0 || ids.len() != count {
+        return Err("ERR The `numids` parameter must match the number of arguments".to_string());
+    }
+    Ok((policy, ids))
+}
+
+/// Parse XRANGE bound: "-", "+", "<ms>", "<ms>-<seq>" and the exclusive "(" forms.
+/// Returns None if an exclusive bound excludes all of the IDs.
+fn parse_range_bound(bound: &[u8], side: RangeBound) -> Result<Option<Id>, String> {
+    let (exclusive, bound) = match bound.first() {
+        Some(b'(') => (true, &bound[1..]),
+        _ => (false, bound),
+    };
+
+    let id = match bound {
+        b"-" => (0, 0),
+        b"+" => (u64::MAX, u64::MAX),
+        // a millisecond-only ID means "<ms>-0" on the left and "<ms>-<max>" on the right
+        _ if !bound.contains(&b'-') => match side {
+            RangeBound::Start => parse_id(bound)?,
+            RangeBound::End => (parse_id(bound)?.0, u64::MAX),
+        },
+        _ => parse_id(bound)?,
+    };
+
+    if !exclusive {
+        return Ok(Some(id));
+    }
+
+    Ok(match side {
+        RangeBound::Start => next_after(id),
+        RangeBound::End => prev_before(id),
+    })
+}
+
+fn next_after((ms, seq): Id) -> Option<Id> {
+    match seq.checked_add(1) {
+        Some(seq) => Some((ms, seq)),
+        _ => ms.checked_add(1).map(|ms| (ms, 0)),
+    }
+}
+
+fn prev_before((ms, seq): Id) -> Option<Id> {
+    match seq.checked_sub(1) {
+        Some(seq) => Some((ms, seq)),
+        _ => ms.checked_sub(1).map(|ms| (ms, u64::MAX)),
+    }
+}
+
+/// Generate an ID for XADD "*" that is greater than the last one.
+fn auto_id(last: Id) -> Result<Id, String> {
+    let ms = current_ms();
+    match ms > last.0 {
+        true => Ok((ms, 0)),
+        // the clock moved backwards or there are several entries within one millisecond
+        false => next_after(last).ok_or_else(|| INVALID_ID.to_string()),
+    }
+}
+
+/// Generate an ID for XADD "<ms>-*" that is greater than the last one.
+fn auto_seq_id(last: Id, ms: u64) -> Result<Id, String> {
+    let id = match ms == last.0 {
+        true => next_after(last).filter(|id| id.0 == ms).ok_or_else(|| INVALID_ID.to_string())?,
+        // "0-0" is not allowed, so the sequence starts from 1 within the zero millisecond
+        false => (ms, if ms == 0 { 1 } else { 0 }),
+    };
+    check_new_id(last, id)
+}
+
+/// Check if the ID for XADD is greater than the last one.
+fn check_new_id(last: Id, id: Id) -> Result<Id, String> {
+    if id == (0, 0) {
+        return Err("ERR The ID specified in XADD must be greater than 0-0".to_string());
+    }
+    if id <= last {
+        return Err("ERR The ID specified in XADD is equal or smaller than the target stream top item"
+            .to_string());
+    }
+    Ok(id)
+}
+
+/// The mock does not track the entries-read counter, so the option is only validated.
+fn skip_entries_read(args: &mut Args) -> Result<(), String> {
+    match args.next_opt() {
+        Some(option) if is_keyword(option, "ENTRIESREAD") => {
+            args.next_i64()?;
+            args.finish()
+        }
+        Some(_) => Err(SYNTAX_ERROR.to_string()),
+        _ => Ok(()),
+    }
+}
+
+fn group_mut<'a>(stream: &'a mut StreamValue, key: &[u8], group: &[u8]) -> Result<&'a mut Group, String> {
+    stream.groups.get_mut(group)
+        .ok_or_else(|| format!("NOGROUP No such consumer group '{}' for key name '{}'",
+                               String::from_utf8_lossy(group), String::from_utf8_lossy(key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execute(storage: &mut Storage, args: &[&str]) -> RespInternalValue {
+        let args: Vec<Vec<u8>> = args.iter().map(|arg| arg.as_bytes().to_vec()).collect();
+        match storage.execute(&args) {
+            Execution::Reply(reply) => reply,
+            Execution::Block(_) => panic!("Unexpected block on {:?}", args),
+        }
+    }
+
+    fn bulk(data: &str) -> RespInternalValue {
+        RespInternalValue::BulkString(data.as_bytes().to_vec())
+    }
+
+    fn is_error(reply: &RespInternalValue, prefix: &str) -> bool {
+        match reply {
+            RespInternalValue::Error(err) => err.starts_with(prefix),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn test_strings() {
+        let mut storage = Storage::default();
+        assert_eq!(RespInternalValue::Nil, execute(&mut storage, &["GET", "foo"]));
+        assert_eq!(ok(), execute(&mut storage, &["SET", "foo", "bar"]));
+        assert_eq!(bulk("bar"), execute(&mut storage, &["get", "foo"]));
+        assert_eq!(RespInternalValue::Int(5), execute(&mut storage, &["INCRBY", "counter", "5"]));
+        assert_eq!(RespInternalValue::Int(6), execute(&mut storage, &["INCR", "counter"]));
+        assert!(is_error(&execute(&mut storage, &["INCR", "foo"]), "ERR"));
+        assert_eq!(RespInternalValue::Array(vec![bulk("bar"), RespInternalValue::Nil]),
+                   execute(&mut storage, &["MGET", "foo", "unknown"]));
+        assert_eq!(RespInternalValue::Int(2), execute(&mut storage, &["DEL", "foo", "counter", "unknown"]));
+        assert_eq!(RespInternalValue::Int(0), execute(&mut storage, &["EXISTS", "foo"]));
+        assert!(is_error(&execute(&mut storage, &["GET"]), "ERR wrong number of arguments"));
+        assert!(is_error(&execute(&mut storage, &["UNKNOWN"]), "ERR unknown command"));
+    }
+
+    #[test]
+    fn test_hashes() {
+        let mut storage = Storage::default();
+        assert_eq!(RespInternalValue::Int(2), execute(&mut storage, &["HSET", "hash", "f1", "v1", "f2", "v2"]));
+        assert_eq!(RespInternalValue::Int(0), execute(&mut storage, &["HSET", "hash", "f1", "v3"]));
+        assert_eq!(bulk("v3"), execute(&mut storage, &["HGET", "hash", "f1"]));
+        assert_eq!(RespInternalValue::Int(2), execute(&mut storage, &["HDEL", "hash", "f1", "f2", "f3"]));
+        assert_eq!(RespInternalValue::Int(0), execute(&mut storage, &["EXISTS", "hash"]));
+        assert_eq!(RespInternalValue::Int(10), execute(&mut storage, &["HINCRBY", "counters", "f", "10"]));
+        assert_eq!(RespInternalValue::Int(7), execute(&mut storage, &["HINCRBY", "counters", "f", "-3"]));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["HDEL", "counters", "f"]));
+        assert_eq!(ok(), execute(&mut storage, &["SET", "foo", "bar"]));
+        assert!(is_error(&execute(&mut storage, &["HGET", "foo", "f1"]), "WRONGTYPE"));
+    }
+
+    #[test]
+    fn test_lists() {
+        let mut storage = Storage::default();
+        let array = |values: &[&str]| RespInternalValue::Array(values.iter().map(|x| bulk(x)).collect());
+
+        assert_eq!(RespInternalValue::Int(2), execute(&mut storage, &["LPUSH", "l", "b", "a"]));
+        assert_eq!(RespInternalValue::Int(4), execute(&mut storage, &["RPUSH", "l", "c", "a"]));
+        assert_eq!(array(&["a", "b", "c", "a"]), execute(&mut storage, &["LRANGE", "l", "0", "-1"]));
+        assert_eq!(array(&["c", "a"]), execute(&mut storage, &["LRANGE", "l", "-2", "10"]));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["LREM", "l", "-1", "a"]));
+        assert_eq!(bulk("c"), execute(&mut storage, &["LMOVE", "l", "other", "RIGHT", "LEFT"]));
+        assert_eq!(bulk("a"), execute(&mut storage, &["LPOP", "l"]));
+        assert_eq!(bulk("b"), execute(&mut storage, &["RPOP", "l"]));
+        assert_eq!(RespInternalValue::Nil, execute(&mut storage, &["RPOP", "l"]));
+        assert_eq!(RespInternalValue::Int(0), execute(&mut storage, &["EXISTS", "l"]));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["LLEN", "other"]));
+        assert!(is_error(&execute(&mut storage, &["LMOVE", "other", "l", "UP", "LEFT"]), "ERR syntax"));
+    }
+
+    #[test]
+    fn test_sorted_sets() {
+        let mut storage = Storage::default();
+        let array = |values: &[&str]| RespInternalValue::Array(values.iter().map(|x| bulk(x)).collect());
+
+        assert_eq!(RespInternalValue::Int(3), execute(&mut storage, &["ZADD", "z", "1", "a", "2", "b", "3", "c"]));
+        assert_eq!(RespInternalValue::Int(0), execute(&mut storage, &["ZADD", "z", "XX", "5", "d"]));
+        assert_eq!(RespInternalValue::Int(0), execute(&mut storage, &["ZADD", "z", "GT", "CH", "0", "a"]));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["ZADD", "z", "LT", "CH", "0", "a"]));
+        assert_eq!(bulk("2.5"), execute(&mut storage, &["ZADD", "z", "INCR", "0.5", "b"]));
+        assert_eq!(RespInternalValue::Nil, execute(&mut storage, &["ZADD", "z", "NX", "INCR", "1", "b"]));
+        assert!(is_error(&execute(&mut storage, &["ZADD", "z", "NX", "GT", "1", "b"]), "ERR GT"));
+
+        assert_eq!(array(&["a", "b", "c"]), execute(&mut storage, &["ZRANGE", "z", "0", "-1"]));
+        assert_eq!(array(&["c", "3", "b", "2.5"]),
+                   execute(&mut storage, &["ZRANGE", "z", "+inf", "(0", "BYSCORE", "REV", "WITHSCORES"]));
+        assert_eq!(array(&["b"]),
+                   execute(&mut storage, &["ZRANGE", "z", "-inf", "+inf", "BYSCORE", "LIMIT", "1", "1"]));
+        assert!(is_error(&execute(&mut storage, &["ZRANGE", "z", "0", "1", "LIMIT", "0", "1"]), "ERR syntax"));
+
+        assert_eq!(array(&["a", "0"]), execute(&mut storage, &["ZPOPMIN", "z"]));
+        assert_eq!(bulk("3"), execute(&mut storage, &["ZSCORE", "z", "c"]));
+        assert_eq!(RespInternalValue::Int(2), execute(&mut storage, &["ZREM", "z", "b", "c", "d"]));
+        assert_eq!(RespInternalValue::Int(0), execute(&mut storage, &["ZCARD", "z"]));
+    }
+
+    #[test]
+    fn test_blocking_pop() {
+        let mut storage = Storage::default();
+        let to_args = |args: &[&str]| -> Vec<Vec<u8>> {
+            args.iter().map(|arg| arg.as_bytes().to_vec()).collect()
+        };
+
+        let read = match storage.execute(&to_args(&["BLMOVE", "queue", "processing", "RIGHT", "LEFT", "0.5"])) {
+            Execution::Block(read) => read,
+            _ => panic!("Expected block"),
+        };
+        assert_eq!(Some(Duration::from_millis(500)), read.timeout());
+        assert!(storage.try_read(&read).is_none());
+
+        execute(&mut storage, &["LPUSH", "queue", "job"]);
+        assert_eq!(Some(bulk("job")), storage.try_read(&read));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["LLEN", "processing"]));
+
+        execute(&mut storage, &["ZADD", "z", "1", "a"]);
+        assert_eq!(RespInternalValue::Array(vec![bulk("z"), bulk("a"), bulk("1")]),
+                   execute(&mut storage, &["BZPOPMIN", "unknown", "z", "0"]));
+        assert_eq!(RespInternalValue::Array(vec![bulk("processing"), bulk("job")]),
+                   execute(&mut storage, &["BLPOP", "processing", "0"]));
+        assert!(is_error(&execute(&mut storage, &["BRPOP", "l", "-1"]), "ERR timeout is negative"));
+        assert!(is_error(&execute(&mut storage, &["BRPOP", "l", "1e30"]), "ERR timeout is out of range"));
+        assert!(is_error(&execute(&mut storage, &["BRPOP", "0"]), "ERR wrong number"));
+    }
+
+    #[test]
+    fn test_stream_add_and_range() {
+        let mut storage = Storage::default();
+        assert_eq!(bulk("1-1"), execute(&mut storage, &["XADD", "s", "1-1", "k", "v1"]));
+        assert_eq!(bulk("1-2"), execute(&mut storage, &["XADD", "s", "1-2", "k", "v2"]));
+        assert_eq!(bulk("2-0"), execute(&mut storage, &["XADD", "s", "2", "k", "v3"]));
+        assert!(is_error(&execute(&mut storage, &["XADD", "s", "2-0", "k", "v"]), "ERR The ID"));
+        assert!(is_error(&execute(&mut storage, &["XADD", "s", "0-0", "k", "v"]), "ERR The ID"));
+        assert!(is_error(&execute(&mut storage, &["XADD", "s", "*", "k"]), "ERR wrong number"));
+
+        let entry = |id: &str, value: &str| RespInternalValue::Array(
+            vec![bulk(id), RespInternalValue::Array(vec![bulk("k"), bulk(value)])]);
+
+        assert_eq!(RespInternalValue::Array(vec![entry("1-1", "v1"), entry("1-2", "v2"), entry("2-0", "v3")]),
+                   execute(&mut storage, &["XRANGE", "s", "-", "+"]));
+        assert_eq!(RespInternalValue::Array(vec![entry("1-2", "v2")]),
+                   execute(&mut storage, &["XRANGE", "s", "(1-1", "+", "COUNT", "1"]));
+        assert_eq!(RespInternalValue::Array(vec![]),
+                   execute(&mut storage, &["XRANGE", "s", "(2-0", "+"]));
+        assert_eq!(RespInternalValue::Array(vec![entry("2-0", "v3"), entry("1-2", "v2")]),
+                   execute(&mut storage, &["XREVRANGE", "s", "+", "-", "COUNT", "2"]));
+        assert_eq!(RespInternalValue::Array(vec![entry("1-2", "v2"), entry("1-1", "v1")]),
+                   execute(&mut storage, &["XREVRANGE", "s", "(2-0", "-"]));
+
+        assert_eq!(RespInternalValue::Int(3), execute(&mut storage, &["XLEN", "s"]));
+        assert_eq!(RespInternalValue::Int(0), execute(&mut storage, &["XLEN", "unknown"]));
+
+        // the sequence number is picked by the server
+        assert_eq!(bulk("2-1"), execute(&mut storage, &["XADD", "s", "2-*", "k", "v4"]));
+        assert_eq!(bulk("5-0"), execute(&mut storage, &["XADD", "s", "5-*", "k", "v5"]));
+        assert!(is_error(&execute(&mut storage, &["XADD", "s", "1-*", "k", "v"]), "ERR The ID"));
+        assert_eq!(bulk("0-1"), execute(&mut storage, &["XADD", "zero", "0-*", "k", "v"]));
+    }
+
+    #[test]
+    fn test_stream_trim() {
+        let mut storage = Storage::default();
+        assert_eq!(RespInternalValue::Nil, execute(&mut storage, &["XADD", "s", "NOMKSTREAM", "1-0", "k", "v"]));
+        assert_eq!(RespInternalValue::Int(0), execute(&mut storage, &["XTRIM", "s", "MAXLEN", "0"]));
+        for id in &["1-0", "2-0", "3-0", "4-0"] {
+            execute(&mut storage, &["XADD", "s", id, "k", "v"]);
+        }
+        assert_eq!(bulk("5-0"), execute(&mut storage, &["XADD", "s", "NOMKSTREAM", "MAXLEN", "=", "3", "5-0", "k", "v"]));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["XTRIM", "s", "MINID", "4"]));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["XTRIM", "s", "MAXLEN", "~", "0", "LIMIT", "1"]));
+        assert!(is_error(&execute(&mut storage, &["XTRIM", "s", "MAXLEN", "0", "LIMIT", "1"]), "ERR syntax error"));
+        assert!(is_error(&execute(&mut storage, &["XTRIM", "s", "LEN", "0"]), "ERR syntax error"));
+
+        let entry = |id: &str| RespInternalValue::Array(
+            vec![bulk(id), RespInternalValue::Array(vec![bulk("k"), bulk("v")])]);
+        assert_eq!(RespInternalValue::Array(vec![entry("5-0")]),
+                   execute(&mut storage, &["XRANGE", "s", "-", "+"]));
+    }
+
+    #[test]
+    fn test_stream_groups() {
+        let mut storage = Storage::default();
+        assert!(is_error(&execute(&mut storage, &["XGROUP", "CREATE", "s", "g", "$"]), "ERR"));
+        assert_eq!(ok(), execute(&mut storage, &["XGROUP", "CREATE", "s", "g", "$", "MKSTREAM"]));
+        assert!(is_error(&execute(&mut storage, &["XGROUP", "CREATE", "s", "g", "$"]), "BUSYGROUP"));
+        assert!(is_error(&execute(&mut storage, &["XREADGROUP", "GROUP", "x", "c", "STREAMS", "s", ">"]),
+                         "NOGROUP"));
+
+        execute(&mut storage, &["XADD", "s", "1-0", "k", "v1"]);
+        execute(&mut storage, &["XADD", "s", "2-0", "k", "v2"]);
+
+        let entry = |id: &str, value: &str| RespInternalValue::Array(
+            vec![bulk(id), RespInternalValue::Array(vec![bulk("k"), bulk(value)])]);
+        let reply = |entries: Vec<RespInternalValue>| RespInternalValue::Array(
+            vec![RespInternalValue::Array(vec![bulk("s"), RespInternalValue::Array(entries)])]);
+
+        assert_eq!(reply(vec![entry("1-0", "v1")]),
+                   execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Alice", "COUNT", "1", "STREAMS", "s", ">"]));
+        assert_eq!(reply(vec![entry("2-0", "v2")]),
+                   execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Bob", "STREAMS", "s", ">"]));
+        assert_eq!(RespInternalValue::Nil,
+                   execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Bob", "STREAMS", "s", ">"]));
+
+        // history of the consumer
+        assert_eq!(reply(vec![entry("2-0", "v2")]),
+                   execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Bob", "STREAMS", "s", "0"]));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["XACK", "s", "g", "2-0"]));
+        assert_eq!(RespInternalValue::Int(0), execute(&mut storage, &["XACK", "s", "g", "2-0"]));
+        assert_eq!(reply(vec![]),
+                   execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Bob", "STREAMS", "s", "0"]));
+
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["XGROUP", "DELCONSUMER", "s", "g", "Alice"]));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["XGROUP", "CREATECONSUMER", "s", "g", "Alice"]));
+        assert_eq!(ok(), execute(&mut storage, &["XGROUP", "SETID", "s", "g", "0"]));
+        assert_eq!(reply(vec![entry("1-0", "v1"), entry("2-0", "v2")]),
+                   execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Alice", "STREAMS", "s", ">"]));
+
+        // the entries received with NOACK are not pending
+        execute(&mut storage, &["XADD", "s", "3-0", "k", "v3"]);
+        assert_eq!(reply(vec![entry("3-0", "v3")]),
+                   execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Bob", "NOACK", "STREAMS", "s", ">"]));
+        assert_eq!(reply(vec![]),
+                   execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Bob", "STREAMS", "s", "0"]));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["XGROUP", "DESTROY", "s", "g"]));
+    }
+
+    #[test]
+    fn test_stream_claim() {
+        let mut storage = Storage::default();
+        execute(&mut storage, &["XGROUP", "CREATE", "s", "g", "$", "MKSTREAM"]);
+        execute(&mut storage, &["XADD", "s", "1-0", "k", "v1"]);
+        execute(&mut storage, &["XADD", "s", "2-0", "k", "v2"]);
+        execute(&mut storage, &["XADD", "s", "3-0", "k", "v3"]);
+        execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Alice", "STREAMS", "s", ">"]);
+
+        let entry = |id: &str, value: &str| RespInternalValue::Array(
+            vec![bulk(id), RespInternalValue::Array(vec![bulk("k"), bulk(value)])]);
+
+        // the entries are not idle enough
+        assert_eq!(RespInternalValue::Array(vec![]),
+                   execute(&mut storage, &["XCLAIM", "s", "g", "Bob", "60000", "1-0"]));
+        assert_eq!(RespInternalValue::Array(vec![entry("1-0", "v1")]),
+                   execute(&mut storage, &["XCLAIM", "s", "g", "Bob", "0", "1-0", "5-0", "RETRYCOUNT", "7"]));
+        assert_eq!(RespInternalValue::Array(vec![bulk("1-0")]),
+                   execute(&mut storage, &["XCLAIM", "s", "g", "Bob", "0", "1-0", "JUSTID"]));
+        assert!(is_error(&execute(&mut storage, &["XCLAIM", "s", "x", "Bob", "0", "1-0"]), "NOGROUP"));
+        assert_eq!(7, storage.stream(b"s").unwrap().unwrap().groups[b"g".as_ref()].pending[&(1, 0)].delivery_count);
+
+        // the deleted entry is removed from the pending entries list
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["XDEL", "s", "2-0"]));
+        assert_eq!(RespInternalValue::Array(vec![
+            bulk("3-0"), RespInternalValue::Array(vec![entry("1-0", "v1")]), RespInternalValue::Array(vec![bulk("2-0")])]),
+                   execute(&mut storage, &["XAUTOCLAIM", "s", "g", "Carol", "0", "0-0", "COUNT", "2"]));
+        assert_eq!(RespInternalValue::Array(vec![
+            bulk("0-0"), RespInternalValue::Array(vec![bulk("3-0")]), RespInternalValue::Array(vec![])]),
+                   execute(&mut storage, &["XAUTOCLAIM", "s", "g", "Carol", "0", "3-0", "JUSTID"]));
+        assert_eq!(RespInternalValue::Array(vec![]),
+                   execute(&mut storage, &["XCLAIM", "s", "g", "Bob", "0", "2-0", "FORCE"]));
+    }
+
+    #[test]
+    fn test_stream_ack_delete() {
+        let mut storage = Storage::default();
+        execute(&mut storage, &["XGROUP", "CREATE", "s", "g1", "$", "MKSTREAM"]);
+        execute(&mut storage, &["XGROUP", "CREATE", "s", "g2", "$"]);
+        for id in ["1-0", "2-0", "3-0"].iter() {
+            execute(&mut storage, &["XADD", "s", id, "k", "v"]);
+        }
+        execute(&mut storage, &["XREADGROUP", "GROUP", "g1", "Alice", "STREAMS", "s", ">"]);
+        execute(&mut storage, &["XREADGROUP", "GROUP", "g2", "Bob", "STREAMS", "s", ">"]);
+
+        let ints = |values: &[i64]| RespInternalValue::Array(
+            values.iter().map(|x| RespInternalValue::Int(*x)).collect());
+
+        assert_eq!(RespInternalValue::Int(2), execute(&mut storage, &["XACK", "s", "g1", "1-0", "2-0", "5-0"]));
+        // 1-0 is still pending within g2
+        assert_eq!(ints(&[2, -1]), execute(&mut storage, &["XDELEX", "s", "ACKED", "IDS", "2", "1-0", "5-0"]));
+        assert_eq!(ints(&[1]), execute(&mut storage, &["XACKDEL", "s", "g2", "ACKED", "IDS", "1", "1-0"]));
+        assert_eq!(ints(&[1]), execute(&mut storage, &["XACKDEL", "s", "g1", "DELREF", "IDS", "1", "3-0"]));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["XACK", "s", "g2", "2-0", "3-0"]));
+        assert_eq!(RespInternalValue::Int(1), execute(&mut storage, &["XLEN", "s"]));
+        assert!(is_error(&execute(&mut storage, &["XDELEX", "s", "IDS", "2", "2-0"]), "ERR"));
+        assert!(is_error(&execute(&mut storage, &["XACKDEL", "s", "x", "IDS", "1", "2-0"]), "NOGROUP"));
+    }
+
+    #[test]
+    fn test_stream_pending() {
+        let mut storage = Storage::default();
+        execute(&mut storage, &["XGROUP", "CREATE", "s", "g", "$", "MKSTREAM"]);
+        assert_eq!(RespInternalValue::Array(vec![RespInternalValue::Int(0), RespInternalValue::Nil,
+                                                 RespInternalValue::Nil, RespInternalValue::Nil]),
+                   execute(&mut storage, &["XPENDING", "s", "g"]));
+
+        execute(&mut storage, &["XADD", "s", "1-0", "k", "v1"]);
+        execute(&mut storage, &["XADD", "s", "2-0", "k", "v2"]);
+        execute(&mut storage, &["XADD", "s", "3-0", "k", "v3"]);
+        execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Bob", "COUNT", "2", "STREAMS", "s", ">"]);
+        execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Alice", "STREAMS", "s", ">"]);
+        // the history reading increments the delivery count
+        execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Bob", "STREAMS", "s", "0"]);
+
+        let consumer = |name: &str, count: &str| RespInternalValue::Array(vec![bulk(name), bulk(count)]);
+        assert_eq!(RespInternalValue::Array(vec![
+            RespInternalValue::Int(3), bulk("1-0"), bulk("3-0"),
+            RespInternalValue::Array(vec![consumer("Alice", "1"), consumer("Bob", "2")])]),
+                   execute(&mut storage, &["XPENDING", "s", "g"]));
+
+        let pending = |reply: RespInternalValue| match reply {
+            RespInternalValue::Array(entries) => entries.into_iter()
+                .map(|entry| match entry {
+                    RespInternalValue::Array(info) => (info[0].clone(), info[1].clone(), info[3].clone()),
+                    _ => panic!("Unexpected entry"),
+                })
+                .collect::<Vec<_>>(),
+            _ => panic!("Unexpected reply"),
+        };
+        assert_eq!(vec![(bulk("2-0"), bulk("Bob"), RespInternalValue::Int(2))],
+                   pending(execute(&mut storage, &["XPENDING", "s", "g", "(1-0", "+", "10", "Bob"])));
+        assert_eq!(vec![(bulk("1-0"), bulk("Bob"), RespInternalValue::Int(2)),
+                        (bulk("2-0"), bulk("Bob"), RespInternalValue::Int(2))],
+                   pending(execute(&mut storage, &["XPENDING", "s", "g", "-", "+", "2"])));
+        assert!(pending(execute(&mut storage, &["XPENDING", "s", "g", "IDLE", "60000", "-", "+", "10"]))
+            .is_empty());
+        assert!(is_error(&execute(&mut storage, &["XPENDING", "s", "x"]), "NOGROUP"));
+    }
+
+    #[test]
+    fn test_stream_info() {
+        let mut storage = Storage::default();
+        assert!(is_error(&execute(&mut storage, &["XINFO", "STREAM", "s"]), "ERR no such key"));
+        execute(&mut storage, &["XADD", "s", "1-0", "k", "v1"]);
+        execute(&mut storage, &["XADD", "s", "2-0", "k", "v2"]);
+        execute(&mut storage, &["XDEL", "s", "1-0"]);
+        execute(&mut storage, &["XGROUP", "CREATE", "s", "g", "0"]);
+        execute(&mut storage, &["XREADGROUP", "GROUP", "g", "Alice", "STREAMS", "s", ">"]);
+
+        let fields = |reply: RespInternalValue| match reply {
+            RespInternalValue::Array(values) => values.chunks_exact(2)
+                .map(|pair| (String::from_utf8_lossy(match &pair[0] {
+                    RespInternalValue::BulkString(x) => x,
+                    _ => panic!("Expected a field name"),
+                }).to_string(), pair[1].clone()))
+                .collect::<HashMap<String, RespInternalValue>>(),
+            _ => panic!("Expected an array"),
+        };
+
+        let info = fields(execute(&mut storage, &["XINFO", "STREAM", "s"]));
+        assert_eq!(RespInternalValue::Int(1), info["length"]);
+        assert_eq!(RespInternalValue::Int(2), info["entries-added"]);
+        assert_eq!(bulk("1-0"), info["max-deleted-entry-id"]);
+        assert_eq!(info["first-entry"], info["last-entry"]);
+
+        let groups = match execute(&mut storage, &["XINFO", "GROUPS", "s"]) {
+            RespInternalValue::Array(groups) => groups,
+            _ => panic!("Expected an array"),
+        };
+        let group = fields(groups[0].clone());
+        assert_eq!(RespInternalValue::Int(1), group["consumers"]);
+        assert_eq!(RespInternalValue::Int(1), group["pending"]);
+        assert_eq!(bulk("2-0"), group["last-delivered-id"]);
+
+        execute(&mut storage, &["XGROUP", "CREATECONSUMER", "s", "g", "Bob"]);
+        let consumers = match execute(&mut storage, &["XINFO", "CONSUMERS", "s", "g"]) {
+            RespInternalValue::Array(consumers) => consumers.into_iter().map(fields).collect::<Vec<_>>(),
+            _ => panic!("Expected an array"),
+        };
+        assert_eq!(bulk("Alice"), consumers[0]["name"]);
+        assert_eq!(RespInternalValue::Int(1), consumers[0]["pending"]);
+        assert_eq!(RespInternalValue::Int(-1), consumers[1]["inactive"]);
+
+        let info = fields(execute(&mut storage, &["XINFO", "STREAM", "s", "FULL", "COUNT", "0"]));
+        assert_eq!(RespInternalValue::Array(vec![RespInternalValue::Array(
+            vec![bulk("2-0"), RespInternalValue::Array(vec![bulk("k"), bulk("v2")])])]), info["entries"]);
+        assert!(is_error(&execute(&mut storage, &["XINFO", "CONSUMERS", "s", "x"]), "NOGROUP"));
+    }
+
+    #[test]
+    fn test_stream_blocking_read() {
+        let mut storage = Storage::default();
+        execute(&mut storage, &["XADD", "s", "1-0", "k", "v1"]);
+
+        let args: Vec<Vec<u8>> = ["XREAD", "BLOCK", "0", "STREAMS", "s", "$"].iter()
+            .map(|arg| arg.as_bytes().to_vec())
+            .collect();
+        let read = match storage.execute(&args) {
+            Execution::Block(read) => read,
+            _ => panic!("Expected block"),
+        };
+        assert_eq!(Some(Duration::from_millis(0)), read.timeout());
+        assert!(storage.try_read(&read).is_none());
+
+        execute(&mut storage, &["XADD", "s", "2-0", "k", "v2"]);
+        let entry = RespInternalValue::Array(
+            vec![bulk("2-0"), RespInternalValue::Array(vec![bulk("k"), bulk("v2")])]);
+        assert_eq!(Some(RespInternalValue::Array(
+            vec![RespInternalValue::Array(vec![bulk("s"), RespInternalValue::Array(vec![entry])])])),
+                   storage.try_read(&read));
+    }
+}
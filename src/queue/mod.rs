@@ -0,0 +1,5 @@
+//! Queue module that contains a reliable work queue based on Redis lists.
+
+mod reliable;
+
+pub use reliable::{ReliableQueue, QueueOptions};
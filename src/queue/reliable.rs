@@ -0,0 +1,311 @@
+use crate::{RedisCoreConnection, RedisError, IntoRedisArgument};
+use crate::cmds::{Commands, ListSide, ZAddOptions, ZAddCondition, ZRangeOptions, ScoreBound};
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use futures::{Future, Stream, future, stream};
+use futures::future::Either;
+
+
+/// Set of options that are required by `ReliableQueue`.
+#[derive(Clone)]
+pub struct QueueOptions {
+    /// List of the items waiting for processing
+    pub(crate) queue: String,
+    /// List of the items are being processed
+    pub(crate) processing: String,
+    /// Sorted set of the processing items scored by their deadlines in milliseconds
+    pub(crate) deadlines: String,
+    pub(crate) visibility_timeout: Duration,
+    /// Clock the deadlines are based on
+    pub(crate) clock: Arc<dyn Fn() -> SystemTime + Send + Sync>,
+}
+
+impl QueueOptions {
+    /// Items are processed within the "<queue>:processing" list,
+    /// their deadlines are stored within the "<queue>:deadlines" sorted set.
+    pub fn new(queue: String, visibility_timeout: Duration) -> QueueOptions {
+        let processing = format!("{}:processing", queue);
+        let deadlines = format!("{}:deadlines", queue);
+        QueueOptions { queue, processing, deadlines, visibility_timeout, clock: Arc::new(SystemTime::now) }
+    }
+
+    /// Base the deadlines on the `clock` instead of the system clock.
+    /// Note the clocks of the consumers of the queue should be in sync.
+    pub fn with_clock<F>(mut self, clock: F) -> QueueOptions
+        where F: Fn() -> SystemTime + Send + Sync + 'static {
+        self.clock = Arc::new(clock);
+        self
+    }
+}
+
+/// The structure represents a Redis connection that provides interface for
+/// working with a reliable queue: an item taken from the queue is moved into
+/// the processing list, and it is re-queued if it is not acknowledged within
+/// the visibility timeout (similar to the pending entries of Redis Stream groups).
+///
+/// The queue provides at-least-once delivery: an item could be processed twice
+/// if its visibility timeout expires before the acknowledgement.
+/// Items should be unique (e.g. contain a job ID),
+/// because the processing items are acknowledged by their values.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::net::SocketAddr;
+/// use std::time::Duration;
+/// use futures::Future;
+/// use redis_asio::queue::{ReliableQueue, QueueOptions};
+///
+/// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+/// let options = QueueOptions::new("jobs".to_string(), Duration::from_secs(30));
+///
+/// let future = ReliableQueue::connect(address, options)
+///     .and_then(|queue| queue.take(Duration::from_secs(0)))
+///     .and_then(|(queue, item)| {
+///         let item = item.unwrap();
+///         println!("Processing: {:?}", item);
+///         queue.ack(item)
+///     })
+///     .map(|(_, is_acked)| println!("the item is acknowledged: {}", is_acked))
+///     .map_err(|err| eprintln!("something went wrong: {}", err));
+/// tokio::run(future);
+/// ```
+pub struct ReliableQueue {
+    connection: RedisCoreConnection,
+    options: QueueOptions,
+}
+
+impl ReliableQueue {
+    /// Open a connection to Redis server and wrap it into `ReliableQueue`,
+    /// that will be available in the future.
+    pub fn connect(addr: &SocketAddr, options: QueueOptions)
+                   -> impl Future<Item=ReliableQueue, Error=RedisError> + Send + 'static {
+        RedisCoreConnection::connect(addr)
+            .map(move |connection| Self { connection, options })
+    }
+
+    /// Push an item into the queue, the response is the length of the queue.
+    pub fn push<T: IntoRedisArgument>(self, item: T)
+                                      -> impl Future<Item=(ReliableQueue, i64), Error=RedisError> + Send + 'static {
+        let ReliableQueue { connection, options } = self;
+        connection.lpush(options.queue.clone(), vec![item])
+            .map(move |(connection, len)| (ReliableQueue { connection, options }, len))
+    }
+
+    /// Take the oldest item from the queue and move it into the processing list.
+    /// Wait for an item for the `timeout` if the queue is empty,
+    /// the zero `timeout` blocks forever.
+    /// The response is None if the timeout has expired.
+    ///
+    /// The taken item should be acknowledged by `ReliableQueue::ack()`
+    /// within the visibility timeout.
+    pub fn take(self, timeout: Duration)
+                -> impl Future<Item=(ReliableQueue, Option<Vec<u8>>), Error=RedisError> + Send + 'static {
+        let ReliableQueue { connection, options } = self;
+        let deadlines = options.deadlines.clone();
+        let visibility_timeout = options.visibility_timeout;
+        let clock = options.clock.clone();
+
+        connection.blmove(options.queue.clone(), options.processing.clone(),
+                          ListSide::Right, ListSide::Left, timeout)
+            .and_then(move |(connection, item): (_, Option<Vec<u8>>)| match item {
+                // the visibility timeout starts once the item is taken, not when the waiting starts
+                Some(item) => Either::A(
+                    connection.zadd(deadlines, vec![(deadline_score(&*clock, visibility_timeout), item.clone())])
+                        .map(move |(connection, _)| (connection, Some(item)))),
+                _ => Either::B(future::ok((connection, None))),
+            })
+            .map(move |(connection, item)| (ReliableQueue { connection, options }, item))
+    }
+
+    /// Acknowledge the processed item and remove it from the processing list.
+    /// The response is false if there is no such item in the processing list
+    /// (e.g. it was re-queued already).
+    pub fn ack<T>(self, item: T)
+                  -> impl Future<Item=(ReliableQueue, bool), Error=RedisError> + Send + 'static
+        where T: IntoRedisArgument {
+        let ReliableQueue { connection, options } = self;
+        let item = item.into_redis_argument();
+        let deadlines = options.deadlines.clone();
+
+        connection.lrem(options.processing.clone(), -1, item.clone())
+            .and_then(move |(connection, removed)|
+                connection.zrem(deadlines, vec![item])
+                    .map(move |(connection, _)| (connection, removed > 0)))
+            .map(move |(connection, is_acked)| (ReliableQueue { connection, options }, is_acked))
+    }
+
+    /// Move the items whose visibility timeout has expired back into the queue,
+    /// they will be taken before the other items.
+    /// The response is the list of re-queued items.
+    ///
+    /// The processing items without a deadline (e.g. if a consumer has failed right
+    /// after it took an item) get the deadline after the visibility timeout.
+    pub fn requeue_expired(self)
+                           -> impl Future<Item=(ReliableQueue, Vec<Vec<u8>>), Error=RedisError> + Send + 'static {
+        let ReliableQueue { connection, options } = self;
+        let QueueOptions { queue, processing, deadlines, visibility_timeout, clock } = options.clone();
+        let expired_clock = clock.clone();
+
+        let track_deadlines = deadlines.clone();
+        let expired_deadlines = deadlines.clone();
+        connection.lrange(processing.clone(), 0, -1)
+            .and_then(move |(connection, items): (_, Vec<Vec<u8>>)| {
+                if items.is_empty() {
+                    return Either::A(future::ok(connection));
+                }
+                // NX keeps the deadlines of the tracked items
+                let deadline = deadline_score(&*clock, visibility_timeout);
+                let items = items.into_iter().map(|item| (deadline, item)).collect();
+                let options = ZAddOptions::new().with_condition(ZAddCondition::NotExists);
+                Either::B(connection.zadd_with(track_deadlines, items, options)
+                    .map(|(connection, _)| connection))
+            })
+            .and_then(move |connection| {
+                let now = ScoreBound::Inclusive(deadline_score(&*expired_clock, Duration::from_millis(0)));
+                connection.zrange(expired_deadlines, ZRangeOptions::by_score(ScoreBound::NegInf, now))
+            })
+            .and_then(move |(connection, expired): (_, Vec<Vec<u8>>)| {
+                stream::iter_ok(expired)
+                    .fold((connection, Vec::new()), move |(connection, requeued), item|
+                        requeue_item(connection, requeued, item, &queue, &processing, &deadlines))
+            })
+            .map(move |(connection, requeued)| (ReliableQueue { connection, options }, requeued))
+    }
+}
+
+/// Re-queue the expired item if it is not re-queued by another consumer.
+/// The item is pushed into the queue before it is removed from the processing list,
+/// so the item could be duplicated but never lost if the client fails.
+fn requeue_item(connection: RedisCoreConnection, mut requeued: Vec<Vec<u8>>, item: Vec<u8>,
+                queue: &str, processing: &str, deadlines: &str)
+                -> impl Future<Item=(RedisCoreConnection, Vec<Vec<u8>>), Error=RedisError> + Send + 'static {
+    let queue = queue.to_string();
+    let processing = processing.to_string();
+
+    // the deadline removal is a claim of the item among the concurrent consumers
+    connection.zrem(deadlines.to_string(), vec![item.clone()])
+        .and_then(move |(connection, removed)| {
+            if removed == 0 {
+                return Either::A(future::ok((connection, requeued)));
+            }
+            let future = connection.rpush(queue, vec![item.clone()])
+                .and_then(move |(connection, _)| connection.lrem(processing, -1, item.clone())
+                    .map(move |(connection, _)| {
+                        requeued.push(item);
+                        (connection, requeued)
+                    }));
+            Either::B(future)
+        })
+}
+
+/// Get the deadline after the `timeout` in milliseconds since the Unix epoch.
+fn deadline_score(clock: &dyn Fn() -> SystemTime, timeout: Duration) -> f64 {
+    let now = clock().duration_since(UNIX_EPOCH).unwrap_or_default();
+    (now + timeout).as_millis() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+    use tokio::runtime::Runtime;
+    use futures::sync::oneshot;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_take_and_ack() {
+        let server = MockServer::start().unwrap();
+        let options = QueueOptions::new("jobs".to_string(), Duration::from_secs(60));
+
+        let future = ReliableQueue::connect(&server.address(), options)
+            .and_then(|queue| queue.push("first"))
+            .and_then(|(queue, _)| queue.push("second"))
+            .and_then(|(queue, len)| {
+                assert_eq!(2, len);
+                queue.take(Duration::from_secs(0))
+            })
+            .and_then(|(queue, item)| {
+                assert_eq!(Some(b"first".to_vec()), item);
+                queue.requeue_expired()
+            })
+            .and_then(|(queue, requeued)| {
+                // the visibility timeout has not expired yet
+                assert!(requeued.is_empty());
+                queue.ack("first")
+            })
+            .and_then(|(queue, is_acked)| {
+                assert!(is_acked);
+                queue.ack("first")
+            })
+            .map(|(_, is_acked)| assert!(!is_acked));
+        Runtime::new().unwrap().block_on(future).unwrap();
+    }
+
+    #[test]
+    fn test_take_blocked_longer_than_visibility_timeout() {
+        let server = MockServer::start().unwrap();
+        let time = Arc::new(Mutex::new(1000));
+        let clock_time = time.clone();
+        let options = QueueOptions::new("jobs".to_string(), Duration::from_millis(200))
+            .with_clock(move || UNIX_EPOCH + Duration::from_millis(*clock_time.lock().unwrap()));
+
+        // the item is pushed after the visibility timeout since the waiting has started,
+        // the waiting is over only once the item is pushed
+        let (started_tx, started_rx) = oneshot::channel();
+        let push_options = options.clone();
+        let address = server.address();
+        let push = started_rx
+            .map_err(|err| panic!("{}", err))
+            .and_then(move |_| {
+                *time.lock().unwrap() += 400;
+                ReliableQueue::connect(&address, push_options)
+            })
+            .and_then(|queue| queue.push("first"));
+
+        let take = ReliableQueue::connect(&server.address(), options)
+            .and_then(move |queue| {
+                let take = queue.take(Duration::from_secs(0));
+                started_tx.send(()).unwrap();
+                take
+            });
+        let future = take.join(push)
+            .and_then(|((queue, item), _)| {
+                assert_eq!(Some(b"first".to_vec()), item);
+                queue.requeue_expired()
+            })
+            // the item has just been taken, so it should not be handed to another consumer
+            .map(|(_, requeued)| assert!(requeued.is_empty()));
+        Runtime::new().unwrap().block_on(future).unwrap();
+    }
+
+    #[test]
+    fn test_requeue_expired() {
+        let server = MockServer::start().unwrap();
+        let options = QueueOptions::new("jobs".to_string(), Duration::from_millis(0));
+
+        let future = ReliableQueue::connect(&server.address(), options)
+            .and_then(|queue| queue.push("first"))
+            .and_then(|(queue, _)| queue.push("second"))
+            .and_then(|(queue, _)| queue.take(Duration::from_secs(0)))
+            .and_then(|(queue, _)| queue.take(Duration::from_secs(0)))
+            .and_then(|(queue, item)| {
+                assert_eq!(Some(b"second".to_vec()), item);
+                queue.ack(item.unwrap())
+            })
+            .and_then(|(queue, _)| queue.requeue_expired())
+            .and_then(|(queue, requeued)| {
+                assert_eq!(vec![b"first".to_vec()], requeued);
+                queue.take(Duration::from_secs(0))
+            })
+            .and_then(|(queue, item)| {
+                assert_eq!(Some(b"first".to_vec()), item);
+                queue.ack("first")
+            })
+            .and_then(|(queue, _)| queue.take(Duration::from_millis(100)))
+            .map(|(_, item)| assert_eq!(None, item));
+        Runtime::new().unwrap().block_on(future).unwrap();
+    }
+}
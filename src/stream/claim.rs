@@ -0,0 +1,312 @@
+use super::{EntryId, StreamEntry, parse_claimed_entries};
+use crate::{RedisValue, RedisCommand, RedisResult, RedisError, RedisErrorKind, command,
+            from_redis_value};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+
+/// Set of options that are required by `RedisStream::claim()`
+/// and `RedisStream::claim_ids()`.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use redis_asio::stream::{ClaimOptions, EntryId};
+///
+/// // "XCLAIM mystream mygroup Alice 60000 1-0 2-0 RETRYCOUNT 5 FORCE"
+/// let options = ClaimOptions::new("mystream".to_string(),
+///                                 "mygroup".to_string(),
+///                                 "Alice".to_string(),
+///                                 Duration::from_secs(60),
+///                                 vec![EntryId::new(1, 0), EntryId::new(2, 0)])
+///     .with_retry_count(5)
+///     .with_force();
+/// ```
+#[derive(Clone)]
+pub struct ClaimOptions {
+    pub(crate) stream: String,
+    pub(crate) group: String,
+    /// The consumer that will own the claimed entries
+    pub(crate) consumer: String,
+    /// Claim only the entries that are idle for at least the time
+    pub(crate) min_idle_time: Duration,
+    pub(crate) ids: Vec<EntryId>,
+    /// IDLE - set the idle time of the claimed entries
+    pub(crate) idle: Option<Duration>,
+    /// TIME - set the idle time to the specified Unix time in milliseconds
+    pub(crate) time: Option<u64>,
+    /// RETRYCOUNT - set the delivery count of the claimed entries
+    pub(crate) retry_count: Option<u64>,
+    /// FORCE - create the pending entries even if they were not delivered yet
+    pub(crate) force: bool,
+}
+
+/// Set of options that are required by `RedisStream::auto_claim()`
+#[derive(Clone)]
+pub struct AutoClaimOptions {
+    pub(crate) stream: String,
+    pub(crate) group: String,
+    /// The consumer that will own the claimed entries
+    pub(crate) consumer: String,
+    /// Claim only the entries that are idle for at least the time
+    pub(crate) min_idle_time: Duration,
+    /// Scan the pending entries starting from the ID
+    pub(crate) start: EntryId,
+    /// Max count of entries per request. Redis uses 100 if the value is None.
+    pub(crate) count: Option<u16>,
+}
+
+/// Structure that wraps a response on XCLAIM or XAUTOCLAIM request.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ClaimedEntries {
+    /// Entries that were claimed by the consumer
+    pub entries: Vec<StreamEntry>,
+    /// IDs of the pending entries that were deleted from the stream,
+    /// Redis removes them from the pending entries list
+    pub deleted: Vec<EntryId>,
+}
+
+/// Structure that wraps a response on XAUTOCLAIM request.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AutoClaimedEntries {
+    /// Entries that were claimed and deleted within the scanned part of the list
+    pub claimed: ClaimedEntries,
+    /// Cursor to continue the scanning from (see `AutoClaimOptions::with_start()`),
+    /// None if the whole pending entries list has been scanned
+    pub next: Option<EntryId>,
+}
+
+impl ClaimOptions {
+    pub fn new(stream: String, group: String, consumer: String, min_idle_time: Duration,
+               ids: Vec<EntryId>) -> ClaimOptions {
+        ClaimOptions {
+            stream,
+            group,
+            consumer,
+            min_idle_time,
+            ids,
+            idle: None,
+            time: None,
+            retry_count: None,
+            force: false,
+        }
+    }
+
+    pub fn with_idle(mut self, idle: Duration) -> ClaimOptions {
+        self.idle = Some(idle);
+        self
+    }
+
+    pub fn with_time(mut self, time: SystemTime) -> ClaimOptions {
+        self.time = Some(to_unix_ms(time));
+        self
+    }
+
+    pub fn with_retry_count(mut self, retry_count: u64) -> ClaimOptions {
+        self.retry_count = Some(retry_count);
+        self
+    }
+
+    pub fn with_force(mut self) -> ClaimOptions {
+        self.force = true;
+        self
+    }
+}
+
+impl AutoClaimOptions {
+    /// Scan the pending entries from the beginning ("0-0").
+    pub fn new(stream: String, group: String, consumer: String, min_idle_time: Duration)
+               -> AutoClaimOptions {
+        AutoClaimOptions {
+            stream,
+            group,
+            consumer,
+            min_idle_time,
+            start: EntryId::new(0, 0),
+            count: None,
+        }
+    }
+
+    pub fn with_start(mut self, start: EntryId) -> AutoClaimOptions {
+        self.start = start;
+        self
+    }
+
+    pub fn with_count(mut self, count: u16) -> AutoClaimOptions {
+        self.count = Some(count);
+        self
+    }
+}
+
+pub(crate) fn claim_command(options: ClaimOptions, just_id: bool) -> RedisCommand {
+    let mut cmd = command("XCLAIM")
+        .arg(options.stream)
+        .arg(options.group)
+        .arg(options.consumer)
+        .arg(options.min_idle_time.as_millis() as u64);
+    for id in options.ids.iter() {
+        cmd.arg_mut(id.to_string());
+    }
+
+    if let Some(idle) = options.idle {
+        cmd.append(command("IDLE").arg(idle.as_millis() as u64));
+    }
+    if let Some(time) = options.time {
+        cmd.append(command("TIME").arg(time));
+    }
+    if let Some(retry_count) = options.retry_count {
+        cmd.append(command("RETRYCOUNT").arg(retry_count));
+    }
+    if options.force {
+        cmd.arg_mut("FORCE");
+    }
+    if just_id {
+        cmd.arg_mut("JUSTID");
+    }
+
+    cmd
+}
+
+pub(crate) fn auto_claim_command(options: AutoClaimOptions) -> RedisCommand {
+    let mut cmd = command("XAUTOCLAIM")
+        .arg(options.stream)
+        .arg(options.group)
+        .arg(options.consumer)
+        .arg(options.min_idle_time.as_millis() as u64)
+        .arg(options.start.to_string());
+    if let Some(count) = options.count {
+        cmd.append(command("COUNT").arg(count));
+    }
+    cmd
+}
+
+/// Parse XCLAIM JUSTID result: RedisValue to vec of EntryId
+pub(crate) fn parse_claimed_ids(value: RedisValue) -> RedisResult<Vec<EntryId>> {
+    let ids: Vec<String> = from_redis_value(&value)?;
+    ids.into_iter().map(EntryId::from_string).collect()
+}
+
+/// Parse XAUTOCLAIM result: RedisValue to the claimed entries and the next cursor.
+/// Redis < 7.0 does not reply the deleted IDs.
+pub(crate) fn parse_auto_claim(stream: &str, value: RedisValue) -> RedisResult<AutoClaimedEntries> {
+    let mut values: Vec<RedisValue> = from_redis_value(&value)?;
+    if values.len() != 2 && values.len() != 3 {
+        return Err(RedisError::new(
+            RedisErrorKind::ParseError,
+            format!("Couldn't parse a XAUTOCLAIM response: {:?}", values)));
+    }
+
+    let mut deleted = match values.len() {
+        3 => parse_claimed_ids(values.pop().unwrap())?,
+        _ => Vec::new(),
+    };
+    let (entries, deleted_entries) = parse_claimed_entries(stream, &values.pop().unwrap())?;
+    deleted.extend(deleted_entries);
+    let next = EntryId::from_string(from_redis_value(&values[0])?)?;
+    // the zero cursor means the whole list has been scanned
+    let next = Some(next).filter(|next| *next != EntryId::new(0, 0));
+
+    Ok(AutoClaimedEntries { claimed: ClaimedEntries { entries, deleted }, next })
+}
+
+fn to_unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|x| x.as_millis() as u64).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RedisCoreConnection;
+    use crate::stream::{SubscribeOptions, RedisGroup, TouchGroupOptions, PendingSummaryOptions,
+                        PendingInfoOptions};
+    use crate::stream::fixture::Fixture;
+    use futures::Future;
+
+    #[test]
+    fn test_claim() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let group = "mygroup".to_string();
+
+        let touch = fixture.connect().touch_group(TouchGroupOptions::new(stream.clone(), group.clone()));
+        fixture.block_on(touch).unwrap();
+        let ids = fixture.populate(&stream, 3);
+
+        // Alice reads the entries and fails without the acknowledgement
+        let read_options = SubscribeOptions::with_group(
+            vec![stream.clone()], RedisGroup::new(group.clone(), "Alice".to_string()));
+        let subscribe = fixture.connect().subscribe(read_options);
+        let subscribe = fixture.block_on(subscribe).unwrap();
+        let (entries, _) = fixture.next(subscribe);
+        assert_eq!(3, entries.len());
+
+        let summary_options = PendingSummaryOptions::new(stream.clone(), group.clone());
+        let summary = fixture.connect().pending_summary(summary_options);
+        let (connection, summary) = fixture.block_on(summary).unwrap();
+        assert_eq!(3, summary.count);
+        assert_eq!(Some(ids[0]), summary.min_id);
+        assert_eq!(vec![("Alice".to_string(), 3)], summary.consumers);
+
+        let claim_options = ClaimOptions::new(stream.clone(), group.clone(), "Bob".to_string(),
+                                              Duration::from_millis(0), vec![ids[0]]);
+        let (connection, claimed) = fixture.block_on(connection.claim(claim_options)).unwrap();
+        assert_eq!(vec![ids[0]], claimed.entries.iter().map(|entry| entry.id).collect::<Vec<_>>());
+        assert_eq!(stream, claimed.entries[0].stream);
+        assert!(claimed.deleted.is_empty());
+
+        let claim_options = ClaimOptions::new(stream.clone(), group.clone(), "Bob".to_string(),
+                                              Duration::from_secs(60), vec![ids[1]]);
+        let (connection, claimed_ids) = fixture.block_on(connection.claim_ids(claim_options)).unwrap();
+        assert!(claimed_ids.is_empty());
+
+        let info_options = PendingInfoOptions::new(stream.clone(), group.clone(), 10)
+            .with_consumer("Bob".to_string());
+        let (connection, pending) = fixture.block_on(connection.pending_info(info_options)).unwrap();
+        assert_eq!(1, pending.len());
+        assert_eq!(ids[0], pending[0].id);
+        assert_eq!(2, pending[0].delivery_count);
+
+        // the entry is deleted but still pending
+        let deleted_id = ids[1].to_string();
+        let delete = RedisCoreConnection::connect(&fixture.server.address())
+            .and_then(move |con| con.send(command("XDEL").arg("mystream").arg(deleted_id)));
+        fixture.block_on(delete).unwrap();
+
+        let claim_options = AutoClaimOptions::new(stream.clone(), group.clone(), "Carol".to_string(),
+                                                  Duration::from_millis(0))
+            .with_count(1);
+        // each call claims one page, the scanning is continued by the cursor
+        let mut connection = connection;
+        let mut claim_options = Some(claim_options);
+        let mut claimed = Vec::new();
+        while let Some(options) = claim_options.take() {
+            let (con, page) = fixture.block_on(connection.auto_claim(options.clone())).unwrap();
+            claim_options = page.next.map(|next| options.with_start(next));
+            claimed.push(page.claimed);
+            connection = con;
+        }
+        assert!(claimed.len() > 1);
+        let claimed_ids: Vec<EntryId> = claimed.iter()
+            .flat_map(|claimed| claimed.entries.iter().map(|entry| entry.id))
+            .collect();
+        let deleted: Vec<EntryId> = claimed.into_iter().flat_map(|claimed| claimed.deleted).collect();
+        assert_eq!(2, claimed_ids.len());
+        assert_eq!(vec![ids[1]], deleted);
+
+        // Redis before 7.0 replies a deleted entry of XCLAIM as [id, Nil]
+        let entry = RedisValue::Array(vec![
+            RedisValue::BulkString(ids[2].to_string().into_bytes()),
+            RedisValue::Array(vec![RedisValue::BulkString(b"key".to_vec()),
+                                   RedisValue::BulkString(b"2".to_vec())])]);
+        let deleted_entry = RedisValue::Array(vec![
+            RedisValue::BulkString(ids[1].to_string().into_bytes()), RedisValue::Nil]);
+        fixture.server.expect(command("XCLAIM").arg(stream.clone()).arg(group.clone()).arg("Bob").arg(0)
+                                  .arg(ids[2].to_string()).arg(ids[1].to_string()),
+                              RedisValue::Array(vec![entry, deleted_entry]));
+        let claim_options = ClaimOptions::new(stream.clone(), group.clone(), "Bob".to_string(),
+                                              Duration::from_millis(0), vec![ids[2], ids[1]]);
+        let (_, claimed) = fixture.block_on(connection.claim(claim_options)).unwrap();
+        assert_eq!(vec![ids[2]], claimed.entries.iter().map(|entry| entry.id).collect::<Vec<_>>());
+        assert_eq!(vec![ids[1]], claimed.deleted);
+        assert!(fixture.server.is_satisfied());
+    }
+}
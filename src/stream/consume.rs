@@ -1,17 +1,55 @@
 use crate::{RedisResult, RedisValue, RedisError, RedisErrorKind, RespInternalValue,
             RedisCommand, command};
-use super::{EntryId, RangeType, StreamEntry, parse_stream_entries};
-use futures::{Stream, Future, Sink};
+use super::{EntryId, RangeType, StreamEntry, RawStreamEntry, parse_raw_stream_entries, parse_range_entries};
+use futures::{Stream, Future, Sink, future, task};
+use futures::task::Task;
 use futures::sync::mpsc::{channel, Sender, Receiver};
-use futures::Async;
+use futures::{Async, Poll};
+use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::collections::VecDeque;
 
 /// Set of options that are required by `RedisStream::subscribe()`
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use redis_asio::stream::{SubscribeOptions, SubscribeStart, EntryId};
+///
+/// // "XREAD COUNT 100 BLOCK 5000 STREAMS stream1 stream2 0 1-0"
+/// let mut options = SubscribeOptions::new(vec!["stream1".to_string()])
+///     .with_start(SubscribeStart::All)
+///     .with_count(100)
+///     .with_block_timeout(Duration::from_secs(5));
+/// options.add_stream("stream2".to_string(), SubscribeStart::Id(EntryId::new(1, 0)));
+/// ```
 #[derive(Clone)]
 pub struct SubscribeOptions {
-    /// List of listen streams
-    pub(crate) streams: Vec<String>,
+    /// List of listen streams and positions to start from
+    pub(crate) streams: Vec<(String, SubscribeStart)>,
     /// Optional group info
     pub(crate) group: Option<RedisGroup>,
+    /// Max count of entries per one request
+    pub(crate) count: Option<u16>,
+    /// BLOCK timeout, None means block until new entries are received
+    pub(crate) block_timeout: Option<Duration>,
+    /// NOACK - do not add the entries received by the group to the PEL
+    pub(crate) no_ack: bool,
+    /// Max count of the received but not released entries, None means there is no limit
+    pub(crate) prefetch: Option<usize>,
+}
+
+/// Position of a stream the subscription starts from.
+#[derive(Clone, PartialEq, Debug)]
+pub enum SubscribeStart {
+    /// "$" or ">" - receive the entries added after the subscription only
+    New,
+    /// "0" - receive all of the stream entries,
+    /// or all of the pending entries of the consumer if the subscription is within a group
+    All,
+    /// Receive the entries with IDs greater than the specified one,
+    /// or the pending entries of the consumer if the subscription is within a group
+    Id(EntryId),
 }
 
 /// Set of options that are required by `RedisStream::read_explicit()`
@@ -43,9 +81,306 @@ pub struct RedisGroup {
     pub(crate) consumer: String,
 }
 
-/// The `Stream<Item=Vec<StreamEntry>, Error=RedisError>` wrapper
+/// The `Stream<Item=Vec<StreamEntry>, Error=RedisError>` wrapper.
+/// The next request is sent once the previous entries are taken from the stream.
+///
+/// Entries of a batch are interleaved by the streams in the round-robin order,
+/// so a busy stream does not delay processing of the others.
 pub struct Subscribe {
-    pub(crate) stream: Box<dyn Stream<Item=RedisValue, Error=RedisError> + Send + 'static>,
+    stream: Box<dyn Stream<Item=RedisValue, Error=RedisError> + Send + 'static>,
+    tx: Sender<StreamInternalCommand>,
+    /// Options of the last sent request, the cursors are moved once the response is received
+    options: SubscribeOptions,
+    /// The sent request the response is expected on, None if the next request is not sent yet
+    in_flight: Option<InFlightRequest>,
+    window: Option<PrefetchWindow>,
+    control: SubscribeControl,
+    /// Stream the interleaving of the next batch starts from
+    next_first_stream: usize,
+}
+
+/// Handle that allows to add and remove streams of a running subscription
+/// (see `Subscribe::control()`).
+/// The changes are applied before the next request, so a blocked request should be completed first:
+/// consider `SubscribeOptions::with_block_timeout()` to apply the changes in time.
+///
+/// The handle may be cloned and moved into another task.
+#[derive(Clone)]
+pub struct SubscribeControl {
+    state: Arc<Mutex<ControlState>>,
+}
+
+#[derive(Default)]
+struct ControlState {
+    changes: Vec<MembershipChange>,
+    /// Subscription task that waits for a stream to listen
+    task: Option<Task>,
+}
+
+enum MembershipChange {
+    Add(String, SubscribeStart),
+    Remove(String),
+}
+
+enum InFlightRequest {
+    /// XREAD/XREADGROUP request made with the options
+    Read(SubscribeOptions),
+    /// XREVRANGE request of the last entry ID of the stream to resolve its "$" start
+    ResolveNew(String),
+}
+
+/// The `Stream<Item=Vec<RawStreamEntry>, Error=RedisError>` wrapper
+/// that is made by `Subscribe::raw()`.
+pub struct RawSubscribe {
+    subscribe: Subscribe,
+}
+
+/// The `Stream<Item=(SubscribePhase, Vec<StreamEntry>), Error=RedisError>` wrapper
+/// that marks each received batch by the phase of the subscription (see `Subscribe::phased()`).
+pub struct PhasedSubscribe {
+    subscribe: Subscribe,
+}
+
+/// Phase of the subscription a batch is received within.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SubscribePhase {
+    /// The batch contains the pending entries of the consumer,
+    /// i.e. the entries that were delivered to the consumer before but were not acknowledged.
+    /// An empty batch means the pending entries are over, the next batches are new
+    Pending,
+    /// The batch contains the entries that were not delivered before
+    New,
+}
+
+/// Window of the entries that are received by a `Subscribe` but not processed yet
+/// (see `SubscribeOptions::with_prefetch()`).
+/// The handle may be cloned and moved into the entries processing code.
+#[derive(Clone)]
+pub struct PrefetchWindow {
+    state: Arc<Mutex<WindowState>>,
+}
+
+struct WindowState {
+    limit: usize,
+    outstanding: usize,
+    /// Subscription task that waits for free space of the window
+    task: Option<Task>,
+}
+
+impl Subscribe {
+    /// Get the prefetch window if the subscription was configured with the one.
+    pub fn window(&self) -> Option<PrefetchWindow> {
+        self.window.clone()
+    }
+
+    /// Get the handle that allows to add and remove streams of the subscription.
+    pub fn control(&self) -> SubscribeControl {
+        self.control.clone()
+    }
+
+    /// Mark each received batch by the phase of the subscription,
+    /// that is useful with `SubscribeOptions::with_recovery()`.
+    pub fn phased(self) -> PhasedSubscribe {
+        PhasedSubscribe { subscribe: self }
+    }
+
+    /// Resolve the "$" starts and send the first XREAD/XREADGROUP request.
+    pub(crate) fn start(self) -> impl Future<Item=Subscribe, Error=RedisError> + Send + 'static {
+        let mut subscribe = Some(self);
+        future::poll_fn(move || {
+            let is_started = {
+                let subscribe = subscribe.as_mut().expect("Subscribe is started already");
+                subscribe.poll_start()?
+            };
+            match is_started {
+                Async::Ready(()) => Ok(Async::Ready(subscribe.take().unwrap())),
+                Async::NotReady => Ok(Async::NotReady),
+            }
+        })
+    }
+
+    fn poll_start(&mut self) -> Poll<(), RedisError> {
+        loop {
+            self.request_next()?;
+            match &self.in_flight {
+                Some(InFlightRequest::ResolveNew(_)) => (),
+                // either the first read is sent or there are no streams to listen yet
+                _ => return Ok(Async::Ready(())),
+            }
+
+            match self.stream.poll()? {
+                Async::Ready(Some(value)) => self.on_resolved(value)?,
+                Async::Ready(None) => return Err(
+                    RedisError::new(RedisErrorKind::ConnectionError,
+                                    "Connection is closed before the subscription".to_string())),
+                Async::NotReady => return Ok(Async::NotReady),
+            }
+        }
+    }
+
+    /// Send the next request if the previous entries are taken and the window has free space.
+    fn request_next(&mut self) -> RedisResult<()> {
+        if self.in_flight.is_some() {
+            return Ok(());
+        }
+
+        if !self.control.apply(&mut self.options) {
+            // wait for a stream is added
+            return Ok(());
+        }
+
+        // XREAD "$" misses the entries added between two requests, so resolve it to the last ID
+        let new_stream = self.options.streams.iter()
+            .find(|(_, start)| self.options.group.is_none() && *start == SubscribeStart::New)
+            .map(|(stream, _)| stream.clone());
+        if let Some(stream) = new_stream {
+            let range_options = RangeOptions { stream: stream.clone(), count: 1, range: RangeType::Any };
+            return self.send(rev_range_cmd(range_options), InFlightRequest::ResolveNew(stream));
+        }
+
+        let available = match &self.window {
+            Some(window) => match window.reserve() {
+                Some(available) => Some(available),
+                // wait for the window is released
+                _ => return Ok(()),
+            },
+            _ => None,
+        };
+
+        let requested = self.options.next_request(available, self.next_first_stream);
+        self.send(subscribe_cmd(requested.clone()), InFlightRequest::Read(requested))
+    }
+
+    fn send(&mut self, cmd: RedisCommand, request: InFlightRequest) -> RedisResult<()> {
+        self.in_flight = Some(request);
+        self.tx.try_send(StreamInternalCommand::ListenNextMessage(cmd))
+            .map_err(|err| RedisError::new(RedisErrorKind::ConnectionError,
+                                           format!("Could not send listen request: {:?}", err)))
+    }
+
+    /// Poll the response on the sent XREAD/XREADGROUP request,
+    /// the "$" starts are resolved on the way.
+    fn poll_response(&mut self) -> Poll<Option<(SubscribeOptions, RedisValue)>, RedisError> {
+        loop {
+            let value = match self.stream.poll()? {
+                Async::Ready(Some(x)) => x,
+                Async::Ready(None) => return Ok(Async::Ready(None)),
+                Async::NotReady => return Ok(Async::NotReady),
+            };
+
+            match self.in_flight.take() {
+                Some(InFlightRequest::Read(requested)) => return Ok(Async::Ready(Some((requested, value)))),
+                request => {
+                    self.in_flight = request;
+                    self.on_resolved(value)?;
+                    self.request_next()?;
+                }
+            }
+        }
+    }
+
+    /// Replace the "$" start of the stream with the ID of the stream last entry.
+    fn on_resolved(&mut self, value: RedisValue) -> RedisResult<()> {
+        let stream = match self.in_flight.take() {
+            Some(InFlightRequest::ResolveNew(stream)) => stream,
+            _ => unreachable!("on_resolved() is called on the XREVRANGE response only"),
+        };
+
+        // the stream may not exist yet, then any entry is new
+        let last_id = parse_range_entries(value)?.pop()
+            .map(|entry| entry.id)
+            .unwrap_or_else(|| EntryId::new(0, 0));
+        // the stream could be removed while the request was in flight
+        for (_, start) in self.options.streams.iter_mut().filter(|(name, _)| *name == stream) {
+            *start = SubscribeStart::Id(last_id);
+        }
+        Ok(())
+    }
+
+    /// Receive the entries as they are stored in the streams:
+    /// with binary field names, the order and the duplicates of the fields are kept.
+    pub fn raw(self) -> RawSubscribe {
+        RawSubscribe { subscribe: self }
+    }
+
+    fn poll_phased(&mut self) -> Poll<Option<(SubscribePhase, Vec<StreamEntry>)>, RedisError> {
+        let batch = match self.poll_phased_raw()? {
+            Async::Ready(Some(x)) => x,
+            Async::Ready(None) => return Ok(Async::Ready(None)),
+            Async::NotReady => return Ok(Async::NotReady),
+        };
+
+        let (phase, entries) = batch;
+        let entries = entries.into_iter()
+            .map(RawStreamEntry::into_entry)
+            .collect::<RedisResult<Vec<StreamEntry>>>()?;
+        Ok(Async::Ready(Some((phase, entries))))
+    }
+
+    fn poll_phased_raw(&mut self) -> Poll<Option<(SubscribePhase, Vec<RawStreamEntry>)>, RedisError> {
+        self.request_next()?;
+
+        let (requested, value) = match self.poll_response()? {
+            Async::Ready(Some(x)) => x,
+            Async::Ready(None) => return Ok(Async::Ready(None)),
+            Async::NotReady => return Ok(Async::NotReady),
+        };
+        let entries = parse_raw_subscribe_entries(value)?;
+
+        if let Some(window) = &self.window {
+            window.acquire(entries.len());
+        }
+
+        let phase = self.options.phase();
+        self.options = self.options.clone().advance(&requested, &last_ids(&entries));
+        let entries = interleave(entries, self.next_first_stream);
+        self.next_first_stream = self.next_first_stream.wrapping_add(1);
+        Ok(Async::Ready(Some((phase, entries))))
+    }
+}
+
+impl SubscribeControl {
+    /// Listen the stream starting from the `start` position since the next request.
+    pub fn add_stream(&self, stream: String, start: SubscribeStart) {
+        self.change(MembershipChange::Add(stream, start))
+    }
+
+    /// Stop listening the stream since the next request.
+    pub fn remove_stream(&self, stream: String) {
+        self.change(MembershipChange::Remove(stream))
+    }
+
+    fn change(&self, change: MembershipChange) {
+        let mut state = self.state.lock().unwrap();
+        state.changes.push(change);
+        if let Some(task) = state.task.take() {
+            task.notify();
+        }
+    }
+
+    /// Apply the changes to the options.
+    /// Returns false and parks the current task until the next change if there are no streams to listen.
+    fn apply(&self, options: &mut SubscribeOptions) -> bool {
+        let mut state = self.state.lock().unwrap();
+        for change in state.changes.drain(..) {
+            match change {
+                MembershipChange::Add(stream, start) => {
+                    // the added stream continues from its cursor if it is listened already
+                    if options.streams.iter().all(|(name, _)| *name != stream) {
+                        options.add_stream(stream, start);
+                    }
+                }
+                MembershipChange::Remove(stream) =>
+                    options.streams.retain(|(name, _)| *name != stream),
+            }
+        }
+
+        if options.streams.is_empty() {
+            state.task = Some(task::current());
+            return false;
+        }
+        true
+    }
 }
 
 impl Stream for Subscribe {
@@ -53,32 +388,224 @@ impl Stream for Subscribe {
     type Error = RedisError;
 
     fn poll(&mut self) -> Result<Async<Option<Self::Item>>, Self::Error> {
-        self.stream.poll()
-            .and_then(|value| {
-                let value = match value {
-                    Async::Ready(x) => x,
-                    _ => return Ok(Async::NotReady)
-                };
-                let value = match value {
-                    Some(x) => x,
-                    _ => return Ok(Async::Ready(None)),
-                };
+        self.poll_phased()
+            .map(|value| value.map(|batch| batch.map(|(_, entries)| entries)))
+    }
+}
+
+impl PhasedSubscribe {
+    /// Get the prefetch window if the subscription was configured with the one.
+    pub fn window(&self) -> Option<PrefetchWindow> {
+        self.subscribe.window()
+    }
 
-                parse_stream_entries(value)
-                    .map(|stream_entries| Async::Ready(Some(stream_entries)))
-            })
+    /// Get the handle that allows to add and remove streams of the subscription.
+    pub fn control(&self) -> SubscribeControl {
+        self.subscribe.control()
+    }
+}
+
+impl Stream for PhasedSubscribe {
+    type Item = (SubscribePhase, Vec<StreamEntry>);
+    type Error = RedisError;
+
+    fn poll(&mut self) -> Result<Async<Option<Self::Item>>, Self::Error> {
+        self.subscribe.poll_phased()
+    }
+}
+
+impl RawSubscribe {
+    /// Get the prefetch window if the subscription was configured with the one.
+    pub fn window(&self) -> Option<PrefetchWindow> {
+        self.subscribe.window()
+    }
+
+    /// Get the handle that allows to add and remove streams of the subscription.
+    pub fn control(&self) -> SubscribeControl {
+        self.subscribe.control()
+    }
+}
+
+impl Stream for RawSubscribe {
+    type Item = Vec<RawStreamEntry>;
+    type Error = RedisError;
+
+    fn poll(&mut self) -> Result<Async<Option<Self::Item>>, Self::Error> {
+        self.subscribe.poll_phased_raw()
+            .map(|value| value.map(|batch| batch.map(|(_, entries)| entries)))
+    }
+}
+
+impl PrefetchWindow {
+    fn new(limit: usize) -> PrefetchWindow {
+        let state = WindowState { limit, outstanding: 0, task: None };
+        PrefetchWindow { state: Arc::new(Mutex::new(state)) }
+    }
+
+    /// Release the `count` entries that are processed (usually acknowledged),
+    /// the subscription is woken up if it waits for free space.
+    pub fn release(&self, count: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.outstanding = state.outstanding.saturating_sub(count);
+        if state.outstanding < state.limit {
+            if let Some(task) = state.task.take() {
+                task.notify();
+            }
+        }
+    }
+
+    /// Count of the entries that are received but not released yet.
+    pub fn outstanding(&self) -> usize {
+        self.state.lock().unwrap().outstanding
+    }
+
+    fn acquire(&self, count: usize) {
+        self.state.lock().unwrap().outstanding += count;
+    }
+
+    /// Get free space of the window,
+    /// or park the current task until the window is released if there is no free space.
+    fn reserve(&self) -> Option<usize> {
+        let mut state = self.state.lock().unwrap();
+        match state.limit.checked_sub(state.outstanding) {
+            Some(available) if available > 0 => Some(available),
+            _ => {
+                state.task = Some(task::current());
+                None
+            }
+        }
     }
 }
 
 impl SubscribeOptions {
     pub fn new(stream: Vec<String>) -> SubscribeOptions {
         let group: Option<RedisGroup> = None;
-        SubscribeOptions { streams: stream, group }
+        SubscribeOptions::with_streams(stream, group)
     }
 
     pub fn with_group(stream: Vec<String>, group: RedisGroup) -> SubscribeOptions {
         let group = Some(group);
-        SubscribeOptions { streams: stream, group }
+        SubscribeOptions::with_streams(stream, group)
+    }
+
+    /// Start each of the listen streams from the `start` position.
+    pub fn with_start(mut self, start: SubscribeStart) -> SubscribeOptions {
+        for (_, stream_start) in self.streams.iter_mut() {
+            *stream_start = start.clone();
+        }
+        self
+    }
+
+    /// Listen the stream starting from the `start` position.
+    pub fn add_stream(&mut self, stream: String, start: SubscribeStart) {
+        self.streams.push((stream, start))
+    }
+
+    /// Receive at most `count` entries per one request.
+    pub fn with_count(mut self, count: u16) -> SubscribeOptions {
+        self.count = Some(count);
+        self
+    }
+
+    /// Block each request for at most the `timeout`,
+    /// the subscription yields an empty batch if there are no new entries within the time.
+    /// Note the zero timeout means block until new entries are received.
+    pub fn with_block_timeout(mut self, timeout: Duration) -> SubscribeOptions {
+        self.block_timeout = Some(timeout);
+        self
+    }
+
+    /// Do not add the received entries to the PEL of the group,
+    /// i.e. the entries are acknowledged once they are delivered.
+    pub fn with_no_ack(mut self) -> SubscribeOptions {
+        self.no_ack = true;
+        self
+    }
+
+    /// Receive the pending entries of the consumer first (page by page if COUNT is specified),
+    /// then switch to the new entries once the pending entries of all of the streams are received.
+    /// It is the same as `with_start(SubscribeStart::All)` within a group,
+    /// the phase of each batch can be got via `Subscribe::phased()`.
+    pub fn with_recovery(self) -> SubscribeOptions {
+        self.with_start(SubscribeStart::All)
+    }
+
+    /// Limit count of the entries that are received but not released
+    /// via `PrefetchWindow::release()` yet.
+    /// The next request is not sent until the count is below the `limit`,
+    /// and the request COUNT is reduced to the free space of the window.
+    pub fn with_prefetch(mut self, limit: usize) -> SubscribeOptions {
+        self.prefetch = Some(limit);
+        self
+    }
+
+    fn with_streams(streams: Vec<String>, group: Option<RedisGroup>) -> SubscribeOptions {
+        let streams = streams.into_iter().map(|stream| (stream, SubscribeStart::New)).collect();
+        SubscribeOptions { streams, group, count: None, block_timeout: None, no_ack: false, prefetch: None }
+    }
+
+    /// Make the options of the next request.
+    /// The pending history is requested only for the streams where it is not over yet,
+    /// so a batch never mixes the pending and the new entries.
+    ///
+    /// The request is reduced to the `available` space of the prefetch window if there is.
+    /// Note COUNT limits each of the streams, so the space is shared between the streams equally,
+    /// and only `available` streams starting from the `first` one are requested
+    /// if there is less space than the streams.
+    pub(crate) fn next_request(&self, available: Option<usize>, first: usize) -> SubscribeOptions {
+        let mut request = self.clone();
+        if self.phase() == SubscribePhase::Pending {
+            request.streams.retain(|(_, start)| *start != SubscribeStart::New);
+        }
+
+        if let Some(available) = available {
+            let len = request.streams.len();
+            if available < len {
+                request.streams.rotate_left(first % len);
+                request.streams.truncate(available);
+            }
+            let available = (available / len.max(1)).max(1);
+            let available = available.min(u16::MAX as usize) as u16;
+            request.count = Some(request.count.map_or(available, |count| count.min(available)));
+        }
+        request
+    }
+
+    /// Move the cursors of the `requested` streams after the last received entries,
+    /// so the next request continues from the point the previous one stopped.
+    /// A group subscription listens new entries once the pending history of all of the streams is over.
+    pub(crate) fn advance(mut self, requested: &SubscribeOptions, last_ids: &[(String, EntryId)])
+                          -> SubscribeOptions {
+        let last_id = |stream: &str| last_ids.iter()
+            .find(|(last_stream, _)| last_stream == stream)
+            .map(|(_, id)| *id);
+        let is_requested = |stream: &str| requested.streams.iter()
+            .any(|(requested_stream, _)| requested_stream == stream);
+
+        let is_history = self.phase() == SubscribePhase::Pending;
+        let is_group = self.group.is_some();
+        for (stream, start) in self.streams.iter_mut().filter(|(stream, _)| is_requested(stream)) {
+            *start = match (last_id(stream), &start) {
+                // the pending history of the stream is received completely
+                (None, _) if is_history => SubscribeStart::New,
+                // ">" moves the group cursor on the server side
+                (_, SubscribeStart::New) if is_group => SubscribeStart::New,
+                (Some(id), _) => SubscribeStart::Id(id),
+                (None, _) => start.clone(),
+            };
+        }
+        self
+    }
+
+    /// Get the phase of the request made with the options:
+    /// a group request with any of the streams not started from ">" reads the pending history.
+    pub(crate) fn phase(&self) -> SubscribePhase {
+        let is_history = self.group.is_some()
+            && self.streams.iter().any(|(_, start)| *start != SubscribeStart::New);
+        match is_history {
+            true => SubscribePhase::Pending,
+            false => SubscribePhase::New,
+        }
     }
 }
 
@@ -98,7 +625,7 @@ impl RangeOptions {
         if !range.is_valid() {
             return Err(
                 RedisError::new(RedisErrorKind::InvalidOptions,
-                                format!("Left bound should be less than right bound")));
+                                "Left bound should be less than right bound".to_string()));
         }
 
         Ok(RangeOptions { stream, count, range })
@@ -112,11 +639,12 @@ impl RedisGroup {
 }
 
 enum StreamInternalCommand {
-    ListenNextMessage,
+    /// Request the next entries by the XREAD/XREADGROUP request
+    ListenNextMessage(RedisCommand),
 }
 
-pub(crate) fn subscribe<F, T>(from_srv: F, to_srv: T, options: SubscribeOptions)
-                              -> impl Stream<Item=RedisValue, Error=RedisError> + Send + 'static
+/// Make the subscription, it should be started via `Subscribe::start()`.
+pub(crate) fn subscribe<F, T>(from_srv: F, to_srv: T, options: SubscribeOptions) -> Subscribe
     where F: Stream<Item=RespInternalValue, Error=RedisError> + Send + 'static,
           T: Sink<SinkItem=RedisCommand, SinkError=RedisError> + Send + 'static {
     // Redis Streams protocol is a simple request-response protocol,
@@ -125,9 +653,12 @@ pub(crate) fn subscribe<F, T>(from_srv: F, to_srv: T, options: SubscribeOptions)
     let (tx, rx) =
         channel::<StreamInternalCommand>(BUFFER_SIZE);
 
-    let output = fwd_from_channel_to_srv(to_srv, rx, options);
-    let input
-        = process_from_srv_and_notify_channel(from_srv, tx);
+    let window = options.prefetch.map(PrefetchWindow::new);
+    let output = fwd_from_channel_to_srv(to_srv, rx);
+    // convert RespInternalValue to RedisValue
+    // note: the function returns an error if the Resp value is Error
+    //       else returns RedisValue
+    let input = from_srv.and_then(RedisValue::from_resp_value);
 
     // We have the following conditions:
     // 1) a return stream should include both output future and input stream
@@ -139,20 +670,16 @@ pub(crate) fn subscribe<F, T>(from_srv: F, to_srv: T, options: SubscribeOptions)
     // where output future will not influence a selected stream (via filter_map())
 
     let output = output.map(|_| None);
-    let input = input.map(|x| Some(x));
+    let input = input.map(Some);
 
-    input.select(output.into_stream()).filter_map(|x| x)
+    let stream = Box::new(input.select(output.into_stream()).filter_map(|x| x));
+    let control = SubscribeControl { state: Arc::new(Mutex::new(ControlState::default())) };
+    Subscribe { stream, tx, options, in_flight: None, window, control, next_first_stream: 0 }
 }
 
 pub(crate) fn subscribe_cmd(options: SubscribeOptions) -> RedisCommand
 {
-    let SubscribeOptions { streams, group } = options;
-
-    // receive only new messages (specifier is different for XREAD and XREADGROUP)
-    let id_specifier = match &group {
-        Some(_) => ">",
-        _ => "$"
-    };
+    let SubscribeOptions { streams, group, count, block_timeout, no_ack, .. } = options;
 
     let mut cmd =
         match &group {
@@ -160,21 +687,39 @@ pub(crate) fn subscribe_cmd(options: SubscribeOptions) -> RedisCommand
             _ => command("XREAD"),
         };
 
+    let is_group = group.is_some();
     if let Some(RedisGroup { group, consumer }) = group {
         cmd.arg_mut("GROUP");
         cmd.arg_mut(group.as_str());
         cmd.arg_mut(consumer.as_str());
     }
 
-    let mut cmd =
-        cmd.arg("BLOCK")
-            .arg("0") // block until next pkt
-            .arg("STREAMS");
+    if let Some(count) = count {
+        cmd.arg_mut("COUNT");
+        cmd.arg_mut(count as i64);
+    }
+
+    // block until next pkt if the timeout is not specified
+    let block_timeout = block_timeout.map(|timeout| timeout.as_millis() as i64).unwrap_or(0);
+    cmd.arg_mut("BLOCK");
+    cmd.arg_mut(block_timeout);
+
+    if no_ack && is_group {
+        cmd.arg_mut("NOACK");
+    }
+
+    cmd.arg_mut("STREAMS");
 
     let mut ids_cmd = RedisCommand::new();
-    for stream in streams.into_iter() {
+    for (stream, start) in streams.into_iter() {
         cmd.arg_mut(stream);
-        ids_cmd.arg_mut(id_specifier);
+        // receive only new messages (specifier is different for XREAD and XREADGROUP)
+        match start {
+            SubscribeStart::New if is_group => ids_cmd.arg_mut(">"),
+            SubscribeStart::New => ids_cmd.arg_mut("$"),
+            SubscribeStart::All => ids_cmd.arg_mut("0"),
+            SubscribeStart::Id(id) => ids_cmd.arg_mut(id.to_string()),
+        }
     }
 
     cmd.append(ids_cmd);
@@ -201,10 +746,21 @@ pub(crate) fn read_explicit_cmd(options: ReadExplicitOptions) -> RedisCommand
 }
 
 pub(crate) fn range_cmd(options: RangeOptions) -> RedisCommand
+{
+    range_page_cmd(options, None)
+}
+
+/// Make XRANGE request of the next page that starts after the `last_id` exclusively
+/// if the one is specified, otherwise the page starts from the left bound of the range.
+pub(crate) fn range_page_cmd(options: RangeOptions, last_id: Option<EntryId>) -> RedisCommand
 {
     let RangeOptions { stream, count, range } = options;
 
     let (left, right) = range.to_left_right();
+    let left = match last_id {
+        Some(last_id) => format!("({}", last_id),
+        _ => left,
+    };
 
     command("XRANGE")
         .arg(stream)
@@ -214,42 +770,438 @@ pub(crate) fn range_cmd(options: RangeOptions) -> RedisCommand
         .arg(count as i64)
 }
 
+pub(crate) fn rev_range_cmd(options: RangeOptions) -> RedisCommand
+{
+    let RangeOptions { stream, count, range } = options;
+
+    let (left, right) = range.to_left_right();
+
+    // XREVRANGE takes the right bound first
+    command("XREVRANGE")
+        .arg(stream)
+        .arg(right)
+        .arg(left)
+        .arg("COUNT")
+        .arg(count as i64)
+}
+
 fn fwd_from_channel_to_srv<T>(to_srv: T,
-                              rx: Receiver<StreamInternalCommand>,
-                              options: SubscribeOptions)
+                              rx: Receiver<StreamInternalCommand>)
                               -> impl Future<Item=(), Error=RedisError> + Send + 'static
     where T: Sink<SinkItem=RedisCommand, SinkError=RedisError> + Send + 'static {
     rx
         .map_err(|_| RedisError::new(RedisErrorKind::InternalError,
                                      "Cannot read from internal channel".to_string()))
-        .fold(to_srv, move |to_srv, msg| {
+        .fold(to_srv, |to_srv, msg| {
             match msg {
-                StreamInternalCommand::ListenNextMessage =>
-                    to_srv.send(subscribe_cmd(options.clone()))
+                StreamInternalCommand::ListenNextMessage(cmd) => to_srv.send(cmd)
             }
         })
         .map(|_| ())
 }
 
-fn process_from_srv_and_notify_channel<F>(from_srv: F,
-                                          tx: Sender<StreamInternalCommand>)
-                                          -> impl Stream<Item=RedisValue, Error=RedisError> + Send + 'static
-    where F: Stream<Item=RespInternalValue, Error=RedisError> + Send + 'static
-{
-    from_srv
-        .and_then(move |msg| {
-            tx.clone().send(StreamInternalCommand::ListenNextMessage)
-                .then(|res| {
-                    match res {
-                        Ok(_) => (),
-                        Err(err) =>
-                            return Err(RedisError::new(RedisErrorKind::ConnectionError,
-                                                       format!("Could not send listen request: {:?}", err)))
-                    }
-                    // convert RespInternalValue to RedisValue
-                    // note: the function returns an error if the Resp value is Error
-                    //       else returns RedisValue
-                    RedisValue::from_resp_value(msg)
-                })
-        })
+pub(crate) fn parse_raw_subscribe_entries(value: RedisValue) -> RedisResult<Vec<RawStreamEntry>> {
+    match value {
+        // the BLOCK timeout is expired and there are no new entries
+        RedisValue::Nil => Ok(Vec::new()),
+        value => parse_raw_stream_entries(value),
+    }
+}
+
+/// Interleave the entries of the streams in the round-robin order starting from the `first` stream,
+/// the order of the entries within each stream is kept.
+fn interleave(entries: Vec<RawStreamEntry>, first: usize) -> Vec<RawStreamEntry> {
+    let mut streams: Vec<VecDeque<RawStreamEntry>> = Vec::new();
+    for entry in entries.into_iter() {
+        match streams.iter_mut().find(|stream| stream[0].stream == entry.stream) {
+            Some(stream) => stream.push_back(entry),
+            _ => streams.push(VecDeque::from(vec![entry])),
+        }
+    }
+
+    if streams.len() > 1 {
+        let first = first % streams.len();
+        streams.rotate_left(first);
+    }
+
+    let mut result = Vec::with_capacity(streams.iter().map(|stream| stream.len()).sum());
+    while !streams.is_empty() {
+        for stream in streams.iter_mut() {
+            result.extend(stream.pop_front());
+        }
+        streams.retain(|stream| !stream.is_empty());
+    }
+    result
+}
+
+/// Get the last entry ID of each stream the entries are received from.
+pub(crate) fn last_ids(entries: &[RawStreamEntry]) -> Vec<(String, EntryId)> {
+    let mut last_ids: Vec<(String, EntryId)> = Vec::new();
+    for entry in entries.iter() {
+        match last_ids.iter_mut().find(|(stream, _)| *stream == entry.stream) {
+            Some((_, id)) => *id = entry.id,
+            _ => last_ids.push((entry.stream.clone(), entry.id)),
+        }
+    }
+    last_ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RedisCoreConnection;
+    use crate::stream::fixture::Fixture;
+    use crate::stream::{RangeBound, CreateGroupOptions, GroupStart, SendEntryOptions};
+    use futures::future::Either;
+    use tokio::timer::Delay;
+    use std::time::Instant;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_subscribe() {
+        let mut fixture = Fixture::start();
+        let subscribe = fixture.connect().subscribe(SubscribeOptions::new(vec!["mystream".to_string()]));
+        let subscribe = fixture.block_on(subscribe).unwrap();
+
+        // the subscription is blocked on the server,
+        // send an entry via another connection to wake it up
+        let ids = fixture.populate("mystream", 1);
+
+        let (entries, _) = fixture.next(subscribe);
+        assert_eq!(1, entries.len());
+        assert_eq!(ids[0], entries[0].id);
+        assert_eq!(RedisValue::BulkString(b"0".to_vec()), entries[0].values["key"]);
+    }
+
+    #[test]
+    fn test_subscribe_options() {
+        let mut fixture = Fixture::start();
+        let ids = fixture.populate("mystream", 3);
+
+        let options = SubscribeOptions::new(vec!["mystream".to_string()])
+            .with_start(SubscribeStart::All)
+            .with_count(2)
+            .with_block_timeout(Duration::from_millis(50));
+        let subscribe = fixture.connect().subscribe(options);
+        let subscribe = fixture.block_on(subscribe).unwrap();
+
+        let (entries, subscribe) = fixture.next(subscribe);
+        assert_eq!(ids[..2].to_vec(), entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+        let (entries, subscribe) = fixture.next(subscribe);
+        assert_eq!(ids[2..].to_vec(), entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+
+        // there are no new entries, so the subscription wakes up on the timeout
+        let (entries, _) = fixture.next(subscribe);
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_subscribe_prefetch() {
+        let mut fixture = Fixture::start();
+        let ids = fixture.populate("mystream", 5);
+        let create_options =
+            CreateGroupOptions::new("mystream".to_string(), "mygroup".to_string(), GroupStart::All);
+        let create_group = fixture.connect().create_group(create_options);
+        fixture.block_on(create_group).unwrap();
+
+        let group = RedisGroup::new("mygroup".to_string(), "Alice".to_string());
+        let options = SubscribeOptions::with_group(vec!["mystream".to_string()], group)
+            .with_prefetch(2);
+        let subscribe = fixture.connect().subscribe(options);
+        let subscribe = fixture.block_on(subscribe).unwrap();
+        let window = subscribe.window().unwrap();
+
+        let (entries, subscribe) = fixture.next(subscribe);
+        assert_eq!(ids[..2].to_vec(), entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+        assert_eq!(2, window.outstanding());
+
+        // the window is full, so the next request is not sent
+        let timeout = Delay::new(Instant::now() + Duration::from_millis(100));
+        let subscribe = match fixture.block_on(subscribe.into_future().select2(timeout)) {
+            Ok(Either::B((_, subscribe))) => subscribe.into_inner().unwrap(),
+            _ => panic!("Expected the subscription waits for the window"),
+        };
+
+        // the next request is reduced to the free space of the window
+        window.release(1);
+        let (entries, _) = fixture.next(subscribe);
+        assert_eq!(ids[2..3].to_vec(), entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+        assert_eq!(2, window.outstanding());
+    }
+
+    #[test]
+    fn test_subscribe_prefetch_streams() {
+        let mut fixture = Fixture::start();
+        let streams = vec!["first".to_string(), "second".to_string(), "third".to_string()];
+        let mut ids = Vec::new();
+        for stream in streams.iter() {
+            ids.extend(fixture.populate(stream, 1));
+            let create_options = CreateGroupOptions::new(stream.clone(), "mygroup".to_string(), GroupStart::All);
+            let create_group = fixture.connect().create_group(create_options);
+            fixture.block_on(create_group).unwrap();
+        }
+
+        // 3 streams share the single free slot of the window
+        let group = RedisGroup::new("mygroup".to_string(), "Alice".to_string());
+        let options = SubscribeOptions::with_group(streams, group).with_prefetch(1);
+        let subscribe = fixture.connect().subscribe(options);
+        let mut subscribe = fixture.block_on(subscribe).unwrap();
+        let window = subscribe.window().unwrap();
+
+        let mut received = Vec::new();
+        while received.len() < ids.len() {
+            let (entries, rest) = fixture.next(subscribe);
+            assert_eq!(1, entries.len());
+            assert_eq!(1, window.outstanding());
+            received.extend(entries.into_iter().map(|entry| entry.id));
+            window.release(1);
+            subscribe = rest;
+        }
+        received.sort();
+        ids.sort();
+        assert_eq!(ids, received);
+    }
+
+    #[test]
+    fn test_subscribe_recovery() {
+        let mut fixture = Fixture::start();
+        let ids = fixture.populate("mystream", 3);
+        let create_options =
+            CreateGroupOptions::new("mystream".to_string(), "mygroup".to_string(), GroupStart::All);
+        let create_group = fixture.connect().create_group(create_options);
+        fixture.block_on(create_group).unwrap();
+
+        // the entries are delivered to the consumer but are not acknowledged
+        let group = RedisGroup::new("mygroup".to_string(), "Alice".to_string());
+        let options = SubscribeOptions::with_group(vec!["mystream".to_string()], group.clone());
+        let subscribe = fixture.connect().subscribe(options.clone());
+        let subscribe = fixture.block_on(subscribe).unwrap();
+        fixture.next(subscribe);
+
+        let subscribe = fixture.connect().subscribe(options.with_recovery().with_count(2));
+        let subscribe = fixture.block_on(subscribe).unwrap().phased();
+        let ids_of = |entries: Vec<StreamEntry>| entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>();
+
+        let ((phase, entries), subscribe) = fixture.next(subscribe);
+        assert_eq!((SubscribePhase::Pending, ids[..2].to_vec()), (phase, ids_of(entries)));
+        let ((phase, entries), subscribe) = fixture.next(subscribe);
+        assert_eq!((SubscribePhase::Pending, ids[2..].to_vec()), (phase, ids_of(entries)));
+        let ((phase, entries), subscribe) = fixture.next(subscribe);
+        assert_eq!((SubscribePhase::Pending, Vec::new()), (phase, ids_of(entries)));
+
+        let new_ids = fixture.populate("mystream", 1);
+        let ((phase, entries), _) = fixture.next(subscribe);
+        assert_eq!((SubscribePhase::New, new_ids), (phase, ids_of(entries)));
+    }
+
+    #[test]
+    fn test_subscribe_recovery_deleted() {
+        let mut fixture = Fixture::start();
+        let ids = fixture.populate("mystream", 3);
+        let create_options =
+            CreateGroupOptions::new("mystream".to_string(), "mygroup".to_string(), GroupStart::All);
+        let create_group = fixture.connect().create_group(create_options);
+        fixture.block_on(create_group).unwrap();
+
+        let group = RedisGroup::new("mygroup".to_string(), "Alice".to_string());
+        let options = SubscribeOptions::with_group(vec!["mystream".to_string()], group.clone());
+        let subscribe = fixture.connect().subscribe(options.clone());
+        let subscribe = fixture.block_on(subscribe).unwrap();
+        fixture.next(subscribe);
+
+        // the entry is deleted but still pending, so it is received without the fields
+        let deleted_id = ids[1].to_string();
+        let delete = RedisCoreConnection::connect(&fixture.server.address())
+            .and_then(move |con| con.send(command("XDEL").arg("mystream").arg(deleted_id)));
+        fixture.block_on(delete).unwrap();
+
+        let subscribe = fixture.connect().subscribe(options.with_recovery().with_count(1));
+        let mut subscribe = fixture.block_on(subscribe).unwrap().phased();
+        let mut received = Vec::new();
+        loop {
+            let ((phase, entries), rest) = fixture.next(subscribe);
+            if entries.is_empty() {
+                break;
+            }
+            assert_eq!(SubscribePhase::Pending, phase);
+            received.extend(entries.into_iter().map(|entry| (entry.id, entry.is_deleted())));
+            subscribe = rest;
+        }
+        assert_eq!(vec![(ids[0], false), (ids[1], true), (ids[2], false)], received);
+    }
+
+    #[test]
+    fn test_subscribe_cursor() {
+        let mut fixture = Fixture::start();
+        let subscribe = fixture.connect().subscribe(SubscribeOptions::new(vec!["mystream".to_string()]));
+        let mut subscribe = fixture.block_on(subscribe).unwrap();
+
+        // the entries are sent in a burst while the subscription processes the previous ones
+        let ids = fixture.populate("mystream", 10);
+
+        let mut received = Vec::new();
+        while received.len() < ids.len() {
+            let (entries, rest) = fixture.next(subscribe);
+            received.extend(entries.into_iter().map(|entry| entry.id));
+            subscribe = rest;
+        }
+        assert_eq!(ids, received);
+    }
+
+    #[test]
+    fn test_subscribe_cursor_scripted() {
+        let mut fixture = Fixture::start();
+        let entry = |id: &str| RedisValue::Array(vec![
+            RedisValue::BulkString(id.as_bytes().to_vec()),
+            RedisValue::Array(vec![RedisValue::BulkString(b"key".to_vec()),
+                                   RedisValue::BulkString(b"value".to_vec())])]);
+        let reply = |ids: &[&str]| RedisValue::Array(vec![RedisValue::Array(vec![
+            RedisValue::BulkString(b"mystream".to_vec()),
+            RedisValue::Array(ids.iter().map(|id| entry(id)).collect())])]);
+        let xread = |id: &str| command("XREAD").arg("BLOCK").arg(0).arg("STREAMS").arg("mystream").arg(id);
+
+        // "$" is resolved to the last entry ID of the stream
+        fixture.server.expect(command("XREVRANGE").arg("mystream").arg("+").arg("-").arg("COUNT").arg(1),
+                              RedisValue::Array(vec![entry("1-0")]));
+        fixture.server.expect(xread("1-0"), reply(&["2-0", "3-0"]));
+        // the entry 4-0 was added between the two requests
+        fixture.server.expect(xread("3-0"), reply(&["4-0"]));
+        fixture.server.expect(xread("4-0"), RedisValue::Nil);
+
+        let subscribe = fixture.connect().subscribe(SubscribeOptions::new(vec!["mystream".to_string()]));
+        let subscribe = fixture.block_on(subscribe).unwrap();
+
+        let (entries, subscribe) = fixture.next(subscribe);
+        assert_eq!(vec![EntryId::new(2, 0), EntryId::new(3, 0)],
+                   entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+        let (entries, subscribe) = fixture.next(subscribe);
+        assert_eq!(vec![EntryId::new(4, 0)], entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+        let (entries, _) = fixture.next(subscribe);
+        assert!(entries.is_empty());
+        assert!(fixture.server.is_satisfied());
+    }
+
+    #[test]
+    fn test_subscribe_control() {
+        let mut fixture = Fixture::start();
+        let options = SubscribeOptions::new(vec!["first".to_string()])
+            .with_block_timeout(Duration::from_millis(50));
+        let subscribe = fixture.connect().subscribe(options);
+        let subscribe = fixture.block_on(subscribe).unwrap();
+        let control = subscribe.control();
+        let ids_of = |entries: Vec<StreamEntry>|
+            entries.into_iter().map(|entry| (entry.stream, entry.id)).collect::<Vec<_>>();
+
+        // the stream is added since the next request that is sent on the block timeout
+        control.add_stream("second".to_string(), SubscribeStart::All);
+        let second_ids = fixture.populate("second", 1);
+        let (entries, subscribe) = fixture.next(subscribe);
+        assert!(entries.is_empty());
+        let (entries, subscribe) = fixture.next(subscribe);
+        assert_eq!(vec![("second".to_string(), second_ids[0])], ids_of(entries));
+
+        control.remove_stream("second".to_string());
+        let (entries, subscribe) = fixture.next(subscribe);
+        assert!(entries.is_empty());
+        fixture.populate("second", 1);
+        let first_ids = fixture.populate("first", 1);
+        let (entries, _) = fixture.next(subscribe);
+        assert_eq!(vec![("first".to_string(), first_ids[0])], ids_of(entries));
+    }
+
+    #[test]
+    fn test_subscribe_fairness() {
+        let mut fixture = Fixture::start();
+        let busy_ids = fixture.populate("busy", 4);
+        let quiet_ids = fixture.populate("quiet", 2);
+
+        let options = SubscribeOptions::new(vec!["busy".to_string(), "quiet".to_string()])
+            .with_start(SubscribeStart::All);
+        let subscribe = fixture.connect().subscribe(options);
+        let subscribe = fixture.block_on(subscribe).unwrap();
+
+        // the streams are interleaved, the order within each stream is kept
+        let (entries, _) = fixture.next(subscribe);
+        let expected = vec![&busy_ids[0], &quiet_ids[0], &busy_ids[1], &quiet_ids[1], &busy_ids[2], &busy_ids[3]];
+        assert_eq!(expected.into_iter().cloned().collect::<Vec<_>>(),
+                   entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_subscribe_raw() {
+        let mut fixture = Fixture::start();
+        let options = SubscribeOptions::new(vec!["mystream".to_string()]);
+        let subscribe = fixture.connect().subscribe(options);
+        let subscribe = fixture.block_on(subscribe).unwrap().raw();
+
+        let fields = vec![(vec![0xff, 0xfe], "binary"), (b"key".to_vec(), "1"), (b"key".to_vec(), "2")];
+        let send = fixture.connect().send_entry(SendEntryOptions::new("mystream".to_string()), fields.clone());
+        let (_, id) = fixture.block_on(send).unwrap();
+
+        let (entries, _) = fixture.next(subscribe);
+        assert_eq!(1, entries.len());
+        assert_eq!(id, entries[0].id);
+        let expected = fields.into_iter()
+            .map(|(name, value)| (name, RedisValue::BulkString(value.as_bytes().to_vec())))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, entries[0].fields);
+    }
+
+    #[test]
+    fn test_windowed_share() {
+        let streams = |options: &SubscribeOptions| options.streams.iter()
+            .map(|(stream, _)| stream.clone())
+            .collect::<Vec<_>>();
+        let options = SubscribeOptions::new(vec!["first".to_string(), "second".to_string(), "third".to_string()])
+            .with_count(100);
+
+        let request = options.next_request(Some(10), 0);
+        assert_eq!((Some(3), 3), (request.count, request.streams.len()));
+        // there is less space than the streams, so the total COUNT does not exceed the space
+        let request = options.next_request(Some(1), 0);
+        assert_eq!((Some(1), vec!["first".to_string()]), (request.count, streams(&request)));
+        let request = options.next_request(Some(2), 4);
+        assert_eq!((Some(1), vec!["second".to_string(), "third".to_string()]), (request.count, streams(&request)));
+        assert_eq!(3, options.next_request(None, 1).streams.len());
+    }
+
+    #[test]
+    fn test_range_iter() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let ids = fixture.populate(&stream, 5);
+
+        // the last page is complete, so the iterator requests an empty page
+        let range_options = RangeOptions::new(stream.clone(), 1, RangeType::Any).unwrap();
+        let entries = fixture.connect().range_iter(range_options).collect();
+        let entries = fixture.block_on(entries).unwrap();
+        assert_eq!(ids, entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+
+        let range = RangeType::GreaterLessThan(ids[1], ids[3]);
+        let range_options = RangeOptions::new(stream.clone(), 2, range).unwrap();
+        let entries = fixture.connect().range_iter(range_options).collect();
+        let entries = fixture.block_on(entries).unwrap();
+        assert_eq!(ids[1..4].to_vec(), entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_range_bounds() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let ids = fixture.populate(&stream, 4);
+
+        let range = RangeType::Between(RangeBound::Exclusive(ids[0]),
+                                       RangeBound::Exclusive(ids[3]));
+        let range_options = RangeOptions::new(stream.clone(), 10, range).unwrap();
+        let entries = fixture.connect().range(range_options);
+        let (_, entries) = fixture.block_on(entries).unwrap();
+        assert_eq!(ids[1..3].to_vec(), entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+
+        // the timestamp-only bounds include all of the entries with the timestamps
+        let range = RangeType::between_times(UNIX_EPOCH, SystemTime::now());
+        let range_options = RangeOptions::new(stream.clone(), 10, range).unwrap();
+        let entries = fixture.connect().range(range_options);
+        let (_, entries) = fixture.block_on(entries).unwrap();
+        assert_eq!(ids, entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+    }
 }
@@ -0,0 +1,266 @@
+use super::{EntryId, StreamEntry};
+use crate::{RedisValue, RedisResult, RedisError, RedisErrorKind, RedisArgument, IntoRedisArgument};
+use std::collections::HashMap;
+use std::time::Duration;
+
+
+/// Field of a dead-letter entry that contains the original stream name
+pub const DEAD_LETTER_STREAM: &str = "dead_letter:stream";
+/// Field of a dead-letter entry that contains the original entry ID
+pub const DEAD_LETTER_ID: &str = "dead_letter:id";
+/// Field of a dead-letter entry that contains the consumer that failed the entry
+pub const DEAD_LETTER_CONSUMER: &str = "dead_letter:consumer";
+/// Field of a dead-letter entry that contains the error reason
+pub const DEAD_LETTER_REASON: &str = "dead_letter:reason";
+
+/// Set of options that are required by `RedisStream::settle_entry()`,
+/// `RedisStream::dead_letter_entry()` and `RedisStream::dead_letter_exceeded()`.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use redis_asio::stream::DeadLetterOptions;
+///
+/// // move the entries of "mystream" that have been delivered more than 5 times into "mystream:dead"
+/// let options = DeadLetterOptions::new("mystream".to_string(),
+///                                      "mygroup".to_string(),
+///                                      "Alice".to_string(),
+///                                      "mystream:dead".to_string(),
+///                                      5)
+///     .with_min_idle_time(Duration::from_secs(60));
+/// ```
+#[derive(Clone)]
+pub struct DeadLetterOptions {
+    pub(crate) stream: String,
+    pub(crate) group: String,
+    /// The consumer that processes the entries
+    pub(crate) consumer: String,
+    /// Stream that the failed entries are moved into
+    pub(crate) dead_letter_stream: String,
+    /// An entry is moved into the dead-letter stream once its delivery count exceeds the number
+    pub(crate) max_deliveries: u64,
+    /// `RedisStream::dead_letter_exceeded()` checks the entries that are idle
+    /// for at least the time only, so the entries that are being processed are skipped
+    pub(crate) min_idle_time: Duration,
+    /// Max count of entries checked by `RedisStream::dead_letter_exceeded()`
+    pub(crate) count: u16,
+}
+
+/// Structure that wraps a result of `RedisStream::settle_entry()`.
+#[derive(PartialEq, Debug, Clone)]
+pub enum SettleResponse {
+    /// The entry has been processed and acknowledged
+    Acked,
+    /// The entry has failed and it is left pending to be delivered again,
+    /// the delivery count is 0 if the entry is not pending
+    Retry { delivery_count: u64 },
+    /// The entry has failed and its delivery count exceeds the max deliveries,
+    /// it has been moved into the dead-letter stream with the ID and acknowledged
+    DeadLettered(EntryId),
+}
+
+/// Structure that wraps a result of `RedisStream::dead_letter_exceeded()`.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ExceededEntries {
+    /// IDs of the original entries that have been moved into the dead-letter stream
+    pub moved: Vec<EntryId>,
+    /// IDs of the exceeded entries that were deleted from the stream,
+    /// there is nothing to move, so they are just acknowledged
+    pub deleted: Vec<EntryId>,
+}
+
+impl DeadLetterOptions {
+    pub fn new(stream: String, group: String, consumer: String, dead_letter_stream: String,
+               max_deliveries: u64) -> DeadLetterOptions {
+        const DEFAULT_COUNT: u16 = 100;
+
+        DeadLetterOptions {
+            stream,
+            group,
+            consumer,
+            dead_letter_stream,
+            max_deliveries,
+            min_idle_time: Duration::from_millis(0),
+            count: DEFAULT_COUNT,
+        }
+    }
+
+    pub fn with_min_idle_time(mut self, min_idle_time: Duration) -> DeadLetterOptions {
+        self.min_idle_time = min_idle_time;
+        self
+    }
+
+    pub fn with_count(mut self, count: u16) -> DeadLetterOptions {
+        self.count = count;
+        self
+    }
+}
+
+/// Make key-values of a dead-letter entry: the original fields and the metadata fields.
+/// The metadata fields replace the original fields with the same names.
+pub(crate) fn dead_letter_values(entry: StreamEntry, consumer: String, reason: String)
+                                 -> RedisResult<HashMap<String, RedisArgument>> {
+    let mut key_values: HashMap<String, RedisArgument> = HashMap::with_capacity(entry.values.len() + 4);
+    for (key, value) in entry.values.into_iter() {
+        key_values.insert(key, value_to_argument(value)?);
+    }
+
+    key_values.insert(DEAD_LETTER_STREAM.to_string(), entry.stream.into_redis_argument());
+    key_values.insert(DEAD_LETTER_ID.to_string(), entry.id.to_string().into_redis_argument());
+    key_values.insert(DEAD_LETTER_CONSUMER.to_string(), consumer.into_redis_argument());
+    key_values.insert(DEAD_LETTER_REASON.to_string(), reason.into_redis_argument());
+    Ok(key_values)
+}
+
+/// Convert a stream entry value back into an argument,
+/// the values are received as Bulk Strings usually.
+fn value_to_argument(value: RedisValue) -> RedisResult<RedisArgument> {
+    match value {
+        RedisValue::BulkString(x) => Ok(RedisArgument::Bytes(x)),
+        RedisValue::Int(x) => Ok(RedisArgument::Int(x)),
+        RedisValue::Status(x) => Ok(RedisArgument::String(x)),
+        RedisValue::Ok => Ok(RedisArgument::String("OK".to_string())),
+        _ => Err(RedisError::new(
+            RedisErrorKind::IncorrectConversion,
+            format!("{:?} is not convertible to a stream entry value", value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::{SubscribeOptions, RedisGroup, TouchGroupOptions, PendingOptions,
+                        PendingSummaryOptions, RangeOptions, RangeType};
+    use crate::stream::fixture::Fixture;
+    use crate::command;
+
+    #[test]
+    fn test_dead_letter_values() {
+        let mut values = HashMap::new();
+        values.insert("data".to_string(), RedisValue::BulkString(b"payload".to_vec()));
+        values.insert("type".to_string(), RedisValue::Int(3));
+        let entry = StreamEntry::new("mystream".to_string(), EntryId::new(1, 2), values);
+
+        let key_values = dead_letter_values(entry.clone(), "Alice".to_string(), "timeout".to_string())
+            .unwrap();
+        let as_bytes = |key: &str| match key_values[key].clone() {
+            RedisArgument::Bytes(x) => x,
+            RedisArgument::String(x) => x.into_bytes(),
+            RedisArgument::Int(x) => x.to_string().into_bytes(),
+        };
+        assert_eq!(6, key_values.len());
+        assert_eq!(b"payload".to_vec(), as_bytes("data"));
+        assert_eq!(b"3".to_vec(), as_bytes("type"));
+        assert_eq!(b"mystream".to_vec(), as_bytes(DEAD_LETTER_STREAM));
+        assert_eq!(b"1-2".to_vec(), as_bytes(DEAD_LETTER_ID));
+        assert_eq!(b"Alice".to_vec(), as_bytes(DEAD_LETTER_CONSUMER));
+        assert_eq!(b"timeout".to_vec(), as_bytes(DEAD_LETTER_REASON));
+
+        let mut entry = entry;
+        entry.values.insert("nested".to_string(), RedisValue::Array(Vec::new()));
+        assert!(dead_letter_values(entry, "Alice".to_string(), "timeout".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_dead_letter() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let group = "mygroup".to_string();
+        let options = DeadLetterOptions::new(stream.clone(), group.clone(), "Alice".to_string(),
+                                             "mystream:dead".to_string(), 1);
+
+        let touch = fixture.connect().touch_group(TouchGroupOptions::new(stream.clone(), group.clone()));
+        fixture.block_on(touch).unwrap();
+        fixture.populate(&stream, 2);
+
+        let read_options = SubscribeOptions::with_group(
+            vec![stream.clone()], RedisGroup::new(group.clone(), "Alice".to_string()));
+        let subscribe = fixture.connect().subscribe(read_options);
+        let subscribe = fixture.block_on(subscribe).unwrap();
+        let (entries, _) = fixture.next(subscribe);
+        assert_eq!(2, entries.len());
+
+        let settle = fixture.connect()
+            .settle_entry(options.clone(), entries[0].clone(), Err("failure".to_string()));
+        let (connection, response) = fixture.block_on(settle).unwrap();
+        assert_eq!(SettleResponse::Retry { delivery_count: 1 }, response);
+
+        // every reading of the pending entries is a delivery
+        let pending_options = PendingOptions::new(
+            stream.clone(), group.clone(), "Alice".to_string(), EntryId::new(0, 0)).unwrap();
+        let (connection, entries) = fixture.block_on(connection.pending_entries(pending_options)).unwrap();
+        // any displayable error is the reason
+        let error = "failure".parse::<u64>().unwrap_err();
+        let settle = connection.settle_entry(options.clone(), entries[0].clone(), Err(error));
+        let (connection, response) = fixture.block_on(settle).unwrap();
+        let dead_letter_id = match response {
+            SettleResponse::DeadLettered(id) => id,
+            _ => panic!("Expected the entry is dead-lettered, found {:?}", response),
+        };
+
+        let range_options = RangeOptions::new("mystream:dead".to_string(), 10, RangeType::Any).unwrap();
+        let (connection, dead_letters) = fixture.block_on(connection.range(range_options)).unwrap();
+        assert_eq!(1, dead_letters.len());
+        assert_eq!(dead_letter_id, dead_letters[0].id);
+        assert_eq!(RedisValue::BulkString(b"0".to_vec()), dead_letters[0].values["key"]);
+        assert_eq!(RedisValue::BulkString(entries[0].id.to_string().into_bytes()),
+                   dead_letters[0].values[DEAD_LETTER_ID]);
+        assert_eq!(RedisValue::BulkString(b"invalid digit found in string".to_vec()),
+                   dead_letters[0].values[DEAD_LETTER_REASON]);
+
+        // the second entry has been delivered twice, i.e. more than the max deliveries
+        let (connection, exceeded) = fixture.block_on(connection.dead_letter_exceeded(options.clone())).unwrap();
+        assert_eq!(ExceededEntries { moved: vec![entries[1].id], deleted: Vec::new() }, exceeded);
+
+        let summary_options = PendingSummaryOptions::new(stream.clone(), group.clone());
+        let (_, summary) = fixture.block_on(connection.pending_summary(summary_options)).unwrap();
+        assert_eq!(0, summary.count);
+    }
+
+    #[test]
+    fn test_dead_letter_exceeded_deleted() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let group = "mygroup".to_string();
+        let options = DeadLetterOptions::new(stream.clone(), group.clone(), "Bob".to_string(),
+                                             "mystream:dead".to_string(), 1);
+
+        let touch = fixture.connect().touch_group(TouchGroupOptions::new(stream.clone(), group.clone()));
+        fixture.block_on(touch).unwrap();
+        let ids = fixture.populate(&stream, 2);
+
+        // the entries are delivered twice
+        let read_options = SubscribeOptions::with_group(
+            vec![stream.clone()], RedisGroup::new(group.clone(), "Alice".to_string()));
+        let subscribe = fixture.connect().subscribe(read_options);
+        let subscribe = fixture.block_on(subscribe).unwrap();
+        fixture.next(subscribe);
+        let pending_options = PendingOptions::new(
+            stream.clone(), group.clone(), "Alice".to_string(), EntryId::new(0, 0)).unwrap();
+        let pending = fixture.connect().pending_entries(pending_options);
+        let (connection, _) = fixture.block_on(pending).unwrap();
+
+        // the second entry is deleted but still pending
+        let (connection, _) = fixture.block_on(connection.delete_entries(stream.clone(), vec![ids[1]])).unwrap();
+
+        // Redis before 7.0 replies a deleted entry of XCLAIM as [id, Nil]
+        let entry = RedisValue::Array(vec![
+            RedisValue::BulkString(ids[0].to_string().into_bytes()),
+            RedisValue::Array(vec![RedisValue::BulkString(b"key".to_vec()),
+                                   RedisValue::BulkString(b"0".to_vec())])]);
+        let deleted_entry = RedisValue::Array(vec![
+            RedisValue::BulkString(ids[1].to_string().into_bytes()), RedisValue::Nil]);
+        fixture.server.expect(command("XCLAIM").arg(stream.clone()).arg(group.clone()).arg("Bob").arg(0)
+                                  .arg(ids[0].to_string()).arg(ids[1].to_string()),
+                              RedisValue::Array(vec![entry, deleted_entry]));
+
+        let (connection, exceeded) = fixture.block_on(connection.dead_letter_exceeded(options)).unwrap();
+        assert_eq!(ExceededEntries { moved: vec![ids[0]], deleted: vec![ids[1]] }, exceeded);
+        assert!(fixture.server.is_satisfied());
+
+        // the deleted entry is acknowledged, so it is not left pending forever
+        let summary_options = PendingSummaryOptions::new(stream.clone(), group.clone());
+        let (_, summary) = fixture.block_on(connection.pending_summary(summary_options)).unwrap();
+        assert_eq!(0, summary.count);
+    }
+}
@@ -1,12 +1,47 @@
 use crate::{RedisValue, RedisResult, RedisError, RedisErrorKind, FromRedisValue, from_redis_value};
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::{self, Visitor};
 use std::num::ParseIntError;
-use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 use std::collections::HashMap;
-
-#[derive(Clone, PartialEq, PartialOrd)]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Stream entry ID "<milliseconds>-<sequence number>".
+/// IDs are ordered as the entries within a stream.
+///
+/// # Example
+/// ```
+/// use redis_asio::stream::EntryId;
+///
+/// let id: EntryId = "1581870410019-1".parse().unwrap();
+/// assert_eq!(EntryId::new(1581870410019, 1), id);
+/// assert_eq!("1581870410019-1", id.to_string());
+/// assert!(id < id.next().unwrap());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct EntryId((u64, u64));
 
+/// ID argument of the stream commands that is either an explicit entry ID or a special one.
+/// Note each of the special IDs is applicable to the specific commands only.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StreamId {
+    /// Explicit "<ms>-<seq>" ID
+    Explicit(EntryId),
+    /// "*" - the ID is generated by the server on XADD
+    Auto,
+    /// "<ms>-*" - the sequence number for the timestamp is generated by the server on XADD (Redis 7.0+)
+    AutoSeq(u64),
+    /// "$" - the last ID of the stream on XREAD, XGROUP CREATE and XGROUP SETID
+    Last,
+    /// ">" - the entries that were never delivered to other consumers on XREADGROUP
+    Undelivered,
+    /// "-" - the lowest ID on XRANGE and XREVRANGE
+    Min,
+    /// "+" - the highest ID on XRANGE and XREVRANGE, the last entry on XREAD (Redis 7.4+)
+    Max,
+}
+
 /// Structure that wraps a entry received on XREAD/XREADGROUP request.
 #[derive(Clone, PartialEq)]
 pub struct StreamEntry {
@@ -15,18 +50,35 @@ pub struct StreamEntry {
     /// Stream entry id is a simple string "milliseconds-id"
     pub id: EntryId,
     /// Note Redis allows to use key as a binary Bulk String
-    /// but in the library it is forbidden for easy of use API.
-    /// Value may be any of the RedisValue types
+    /// but in the library it is forbidden for easy of use API
+    /// (see `RawStreamEntry` to read binary keys).
+    /// Value may be any of the RedisValue types.
+    /// The values are empty if the entry is deleted (see `StreamEntry::is_deleted()`).
     pub values: HashMap<String, RedisValue>,
 }
 
+/// Structure that wraps a entry with the fields as they are stored in the stream:
+/// the field names may be binary, the order and the duplicates of the fields are kept.
+/// It is received on the "raw" requests, e.g. `RedisStream::range_raw()` or `Subscribe::raw()`.
+#[derive(Clone, PartialEq)]
+pub struct RawStreamEntry {
+    /// Stream name
+    pub stream: String,
+    /// Stream entry id is a simple string "milliseconds-id"
+    pub id: EntryId,
+    /// Pairs of field names and values in the order they were added,
+    /// the fields are empty if the entry is deleted (see `RawStreamEntry::is_deleted()`)
+    pub fields: Vec<(Vec<u8>, RedisValue)>,
+}
+
 /// Structure that wraps an range entry received on XRANGE request.
 #[derive(Clone, PartialEq)]
 pub struct RangeEntry {
     /// Stream entry id is a simple string "milliseconds-id"
     pub id: EntryId,
     /// Note Redis allows to use key as a binary Bulk String
-    /// but in the library it is forbidden for easy of use API.
+    /// but in the library it is forbidden for easy of use API
+    /// (see `RawStreamEntry` to read binary keys).
     /// Value may be any of the RedisValue types
     pub values: HashMap<String, RedisValue>,
 }
@@ -39,6 +91,44 @@ impl StreamEntry {
             values,
         }
     }
+
+    /// Check if the entry is deleted from the stream while it is still pending,
+    /// such an entry is received within the pending history of a consumer without the values.
+    /// It should be acknowledged to be removed from the pending entries list.
+    pub fn is_deleted(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl RawStreamEntry {
+    pub(crate) fn new(stream: String, id: EntryId, fields: Vec<(Vec<u8>, RedisValue)>) -> Self {
+        RawStreamEntry {
+            stream,
+            id,
+            fields,
+        }
+    }
+
+    /// Check if the entry is deleted from the stream while it is still pending,
+    /// such an entry is received within the pending history of a consumer without the fields.
+    /// It should be acknowledged to be removed from the pending entries list.
+    pub fn is_deleted(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Get the value of the first field with the `name`.
+    pub fn get(&self, name: &[u8]) -> Option<&RedisValue> {
+        self.fields.iter()
+            .find(|(field, _)| field.as_slice() == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Convert into the `StreamEntry`, the last value of a duplicated field wins.
+    /// Returns an error if there is a binary field name.
+    pub fn into_entry(self) -> RedisResult<StreamEntry> {
+        let RawStreamEntry { stream, id, fields } = self;
+        Ok(StreamEntry::new(stream, id, fields_to_map(fields)?))
+    }
 }
 
 impl RangeEntry {
@@ -52,6 +142,14 @@ impl RangeEntry {
 
 /// Parse XREAD/XREADGROUP result: RedisValue to vec of StreamEntry
 pub(crate) fn parse_stream_entries(value: RedisValue) -> RedisResult<Vec<StreamEntry>> {
+    parse_raw_stream_entries(value)?
+        .into_iter()
+        .map(RawStreamEntry::into_entry)
+        .collect()
+}
+
+/// Parse XREAD/XREADGROUP result: RedisValue to vec of RawStreamEntry
+pub(crate) fn parse_raw_stream_entries(value: RedisValue) -> RedisResult<Vec<RawStreamEntry>> {
     // usually count of entries within one stream is 1,
     // because in finally case we subscribe on only new messages
     const LEN_FACTOR: usize = 1;
@@ -59,13 +157,13 @@ pub(crate) fn parse_stream_entries(value: RedisValue) -> RedisResult<Vec<StreamE
     let streams: Vec<StreamInfo> = from_redis_value(&value)?;
 
     let capacity = streams.len() * LEN_FACTOR;
-    let mut stream_entries: Vec<StreamEntry> = Vec::with_capacity(capacity);
+    let mut stream_entries: Vec<RawStreamEntry> = Vec::with_capacity(capacity);
 
-    // transform Vec<EntryInfo> to Vec<StreamEntry>
+    // transform Vec<EntryInfo> to Vec<RawStreamEntry>
     for StreamInfo { id, entries } in streams.into_iter() {
         for entry in entries.into_iter() {
             let stream_entry =
-                StreamEntry::new(id.clone(), EntryId::from_string(entry.id)?, entry.key_values);
+                RawStreamEntry::new(id.clone(), EntryId::from_string(entry.id)?, entry.fields);
 
             stream_entries.push(stream_entry);
         }
@@ -83,7 +181,7 @@ pub fn parse_range_entries(value: RedisValue) -> RedisResult<Vec<RangeEntry>> {
     // transform the Vec<EntryInfo> to Vec<RangeEntry>
     for entry in entries.into_iter() {
         let entry =
-            RangeEntry::new(EntryId::from_string(entry.id)?, entry.key_values);
+            RangeEntry::new(EntryId::from_string(entry.id)?, fields_to_map(entry.fields)?);
 
         result_entries.push(entry);
     }
@@ -91,6 +189,48 @@ pub fn parse_range_entries(value: RedisValue) -> RedisResult<Vec<RangeEntry>> {
     Ok(result_entries)
 }
 
+/// Parse XRANGE result of the `stream`: RedisValue to vec of RawStreamEntry
+pub(crate) fn parse_raw_range_entries(stream: &str, value: RedisValue) -> RedisResult<Vec<RawStreamEntry>> {
+    let entries: Vec<EntryInfo> = from_redis_value(&value)?;
+
+    let mut result_entries: Vec<RawStreamEntry> = Vec::with_capacity(entries.len());
+    for entry in entries.into_iter() {
+        let entry =
+            RawStreamEntry::new(stream.to_string(), EntryId::from_string(entry.id)?, entry.fields);
+
+        result_entries.push(entry);
+    }
+
+    Ok(result_entries)
+}
+
+/// Parse XCLAIM/XAUTOCLAIM entries: RedisValue to vec of StreamEntry
+/// and IDs of the entries that were deleted from the stream.
+/// Depending on Redis version a deleted entry is replied as Nil or as [id, Nil].
+pub(crate) fn parse_claimed_entries(stream: &str, value: &RedisValue)
+                                    -> RedisResult<(Vec<StreamEntry>, Vec<EntryId>)> {
+    let values: Vec<RedisValue> = from_redis_value(value)?;
+
+    let mut entries: Vec<StreamEntry> = Vec::with_capacity(values.len());
+    let mut deleted: Vec<EntryId> = Vec::new();
+    for value in values.into_iter() {
+        match value {
+            RedisValue::Nil => continue,
+            RedisValue::Array(ref pair) if pair.len() == 2 && pair[1] == RedisValue::Nil => {
+                deleted.push(EntryId::from_string(from_redis_value(&pair[0])?)?);
+            }
+            _ => {
+                let entry: EntryInfo = from_redis_value(&value)?;
+                entries.push(
+                    StreamEntry::new(stream.to_string(), EntryId::from_string(entry.id)?,
+                                     fields_to_map(entry.fields)?));
+            }
+        }
+    }
+
+    Ok((entries, deleted))
+}
+
 /// Internal structure is used to parse RedisValue into StreamEntry
 struct StreamInfo {
     id: String,
@@ -101,7 +241,21 @@ struct StreamInfo {
 #[derive(Debug)]
 struct EntryInfo {
     id: String,
-    key_values: HashMap<String, RedisValue>,
+    fields: Vec<(Vec<u8>, RedisValue)>,
+}
+
+/// Collect the fields into the map, the last value of a duplicated field wins.
+fn fields_to_map(fields: Vec<(Vec<u8>, RedisValue)>) -> RedisResult<HashMap<String, RedisValue>> {
+    let mut result = HashMap::with_capacity(fields.len());
+    for (name, value) in fields.into_iter() {
+        let name = String::from_utf8(name)
+            .map_err(|err| RedisError::new(
+                RedisErrorKind::IncorrectConversion,
+                format!("Binary field name {:?} is not convertible to String, consider raw entries",
+                        err.as_bytes())))?;
+        result.insert(name, value);
+    }
+    Ok(result)
 }
 
 #[derive(Clone)]
@@ -110,13 +264,50 @@ pub enum RangeType {
     GreaterThan(EntryId),
     LessThan(EntryId),
     GreaterLessThan(EntryId, EntryId),
+    /// Range with the left and right bounds that may be inclusive, exclusive or timestamp-only.
+    Between(RangeBound, RangeBound),
+}
+
+/// Bound of the `RangeType::Between` range.
+#[derive(Clone)]
+pub enum RangeBound {
+    /// Special "-" ID on the left and "+" ID on the right
+    Unbounded,
+    /// "<ms>-<id>" bound, the entry with the ID is included
+    Inclusive(EntryId),
+    /// "(<ms>-<id>" bound, the entry with the ID is excluded
+    Exclusive(EntryId),
+    /// Millisecond-only "<ms>" bound that is equal to "<ms>-0" on the left
+    /// and "<ms>-<max>" on the right, i.e. all of the entries with the timestamp are included
+    Timestamp(u64),
 }
 
 impl RangeType {
+    /// Make the range of entries that were added between the `from` and `to` times inclusively.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use redis_asio::stream::RangeType;
+    ///
+    /// // entries that were added within the last hour
+    /// let now = SystemTime::now();
+    /// let range = RangeType::between_times(now - Duration::from_secs(3600), now);
+    /// assert!(range.is_valid());
+    /// ```
+    pub fn between_times(from: SystemTime, to: SystemTime) -> RangeType {
+        RangeType::Between(RangeBound::from_time(from), RangeBound::from_time(to))
+    }
+
     /// Check if the left bound is less than the right bound
     pub fn is_valid(&self) -> bool {
         match self {
             RangeType::GreaterLessThan(left, right) => left < right,
+            RangeType::Between(left, right) =>
+                match (left.lowest_id(), right.highest_id()) {
+                    (Some(left), Some(right)) => left <= right,
+                    _ => false,
+                }
             _ => true
         }
     }
@@ -127,6 +318,44 @@ impl RangeType {
             RangeType::GreaterThan(left) => (left.to_string(), "+".to_string()),
             RangeType::LessThan(right) => ("-".to_string(), right.to_string()),
             RangeType::GreaterLessThan(left, right) => (left.to_string(), right.to_string()),
+            RangeType::Between(left, right) => (left.to_arg("-"), right.to_arg("+")),
+        }
+    }
+}
+
+impl RangeBound {
+    /// Make the timestamp-only bound from the time.
+    /// Note a time before the UNIX epoch is treated as the epoch.
+    pub fn from_time(time: SystemTime) -> RangeBound {
+        RangeBound::Timestamp(EntryId::from_time(time).ms())
+    }
+
+    fn to_arg(&self, unbounded: &str) -> String {
+        match self {
+            RangeBound::Unbounded => unbounded.to_string(),
+            RangeBound::Inclusive(id) => id.to_string(),
+            RangeBound::Exclusive(id) => format!("({}", id),
+            RangeBound::Timestamp(ms) => ms.to_string(),
+        }
+    }
+
+    /// Get the lowest ID that is within the range if the bound is the left one.
+    fn lowest_id(&self) -> Option<EntryId> {
+        match self {
+            RangeBound::Unbounded => Some(EntryId::MIN),
+            RangeBound::Inclusive(id) => Some(*id),
+            RangeBound::Exclusive(id) => id.next(),
+            RangeBound::Timestamp(ms) => Some(EntryId::new(*ms, 0)),
+        }
+    }
+
+    /// Get the highest ID that is within the range if the bound is the right one.
+    fn highest_id(&self) -> Option<EntryId> {
+        match self {
+            RangeBound::Unbounded => Some(EntryId::MAX),
+            RangeBound::Inclusive(id) => Some(*id),
+            RangeBound::Exclusive(id) => id.prev(),
+            RangeBound::Timestamp(ms) => Some(EntryId::new(*ms, u64::MAX)),
         }
     }
 }
@@ -138,6 +367,16 @@ impl fmt::Debug for StreamEntry {
     }
 }
 
+impl fmt::Debug for RawStreamEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<(String, &RedisValue)> = self.fields.iter()
+            .map(|(name, value)| (String::from_utf8_lossy(name).into_owned(), value))
+            .collect();
+        write!(f, "(stream={}, id=\"{:?}\", {:?})", self.stream, self.id, fields)?;
+        Ok(())
+    }
+}
+
 impl fmt::Debug for RangeEntry {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "(id=\"{:?}\", {:?})", self.id, self.values)?;
@@ -147,51 +386,202 @@ impl fmt::Debug for RangeEntry {
 
 impl fmt::Debug for EntryId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string())?;
+        write!(f, "{}", self)?;
         Ok(())
     }
 }
 
 impl EntryId {
-    pub fn new(ms: u64, id: u64) -> EntryId {
+    /// The lowest ID "0-0".
+    pub const MIN: EntryId = EntryId((0, 0));
+    /// The highest ID "<u64::MAX>-<u64::MAX>".
+    pub const MAX: EntryId = EntryId((u64::MAX, u64::MAX));
+
+    pub const fn new(ms: u64, id: u64) -> EntryId {
         EntryId((ms, id))
     }
 
+    /// Make the lowest ID of the time, i.e. "<ms>-0".
+    /// Note a time before the UNIX epoch is treated as the epoch.
+    pub fn from_time(time: SystemTime) -> EntryId {
+        let ms = time.duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+        EntryId::new(ms, 0)
+    }
+
+    /// Milliseconds part of the ID.
+    pub fn ms(&self) -> u64 {
+        (self.0).0
+    }
+
+    /// Sequence number part of the ID.
+    pub fn seq(&self) -> u64 {
+        (self.0).1
+    }
+
+    /// Time the entry was added at, if the ID was generated by the server.
+    pub fn time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.ms())
+    }
+
+    /// Get the lowest ID that is greater than the ID,
+    /// e.g. to continue paging after the last received entry.
+    /// Returns None if the ID is `EntryId::MAX`.
+    pub fn next(&self) -> Option<EntryId> {
+        let (ms, seq) = self.0;
+        match seq.checked_add(1) {
+            Some(seq) => Some(EntryId::new(ms, seq)),
+            _ => ms.checked_add(1).map(|ms| EntryId::new(ms, 0)),
+        }
+    }
+
+    /// Get the highest ID that is less than the ID.
+    /// Returns None if the ID is `EntryId::MIN`.
+    pub fn prev(&self) -> Option<EntryId> {
+        let (ms, seq) = self.0;
+        match seq.checked_sub(1) {
+            Some(seq) => Some(EntryId::new(ms, seq)),
+            _ => ms.checked_sub(1).map(|ms| EntryId::new(ms, u64::MAX)),
+        }
+    }
+
     // Parse the Redis Stream Entry as pair: <milliseconds, id>
     pub(crate) fn from_string(id: String) -> RedisResult<EntryId> {
-        const ENTRY_ID_CHUNK_LEN: usize = 2;
-        const ENTRY_ID_MS_POS: usize = 0;
-        const ENTRY_ID_ID_POS: usize = 1;
-
-        let tokens: Vec<&str>
-            = id.split('-').filter(|token| !token.is_empty()).collect();
-        if tokens.len() != ENTRY_ID_CHUNK_LEN {
-            return Err(
-                RedisError::new(
-                    RedisErrorKind::ParseError,
-                    format!("Couldn't parse a Redis entry id: {:?}", &id))
-            );
+        id.parse()
+    }
+}
+
+impl FromStr for EntryId {
+    type Err = RedisError;
+
+    fn from_str(id: &str) -> RedisResult<EntryId> {
+        let parse_error = || RedisError::new(
+            RedisErrorKind::ParseError,
+            format!("Couldn't parse a Redis entry id: {:?}", id));
+        // u64::from_str() accepts a leading '+', so the digits are checked explicitly
+        let is_number = |token: &str| !token.is_empty() && token.bytes().all(|x| x.is_ascii_digit());
+
+        let (ms, seq) = id.split_once('-').ok_or_else(parse_error)?;
+        if !is_number(ms) || !is_number(seq) {
+            return Err(parse_error());
+        }
+
+        let ms = ms.parse::<u64>().map_err(&to_redis_error)?;
+        let seq = seq.parse::<u64>().map_err(&to_redis_error)?;
+        Ok(Self((ms, seq)))
+    }
+}
+
+impl fmt::Display for EntryId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", (self.0).0, (self.0).1)
+    }
+}
+
+impl Serialize for EntryId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EntryId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<EntryId, D::Error> {
+        struct EntryIdVisitor;
+
+        impl<'de> Visitor<'de> for EntryIdVisitor {
+            type Value = EntryId;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a stream entry ID \"<ms>-<seq>\"")
+            }
+
+            fn visit_str<E: de::Error>(self, id: &str) -> Result<EntryId, E> {
+                id.parse().map_err(|err: RedisError| E::custom(err))
+            }
         }
 
-        let ms = tokens[ENTRY_ID_MS_POS].parse::<u64>().map_err(&to_redis_error)?;
-        let id = tokens[ENTRY_ID_ID_POS].parse::<u64>().map_err(&to_redis_error)?;
-        Ok(Self((ms, id)))
+        deserializer.deserialize_str(EntryIdVisitor)
+    }
+}
+
+impl From<EntryId> for StreamId {
+    fn from(id: EntryId) -> StreamId {
+        StreamId::Explicit(id)
     }
+}
 
-    pub fn to_string(&self) -> String {
-        format!("{}-{}", (self.0).0, (self.0).1)
+impl FromStr for StreamId {
+    type Err = RedisError;
+
+    fn from_str(id: &str) -> RedisResult<StreamId> {
+        match id {
+            "*" => Ok(StreamId::Auto),
+            "$" => Ok(StreamId::Last),
+            ">" => Ok(StreamId::Undelivered),
+            "-" => Ok(StreamId::Min),
+            "+" => Ok(StreamId::Max),
+            _ if id.ends_with("-*") => id[..id.len() - 2].parse::<u64>()
+                .map(StreamId::AutoSeq)
+                .map_err(to_redis_error),
+            _ => id.parse().map(StreamId::Explicit),
+        }
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamId::Explicit(id) => write!(f, "{}", id),
+            StreamId::Auto => write!(f, "*"),
+            StreamId::AutoSeq(ms) => write!(f, "{}-*", ms),
+            StreamId::Last => write!(f, "$"),
+            StreamId::Undelivered => write!(f, ">"),
+            StreamId::Min => write!(f, "-"),
+            StreamId::Max => write!(f, "+"),
+        }
     }
 }
 
 fn to_redis_error(err: ParseIntError) -> RedisError {
-    RedisError::new(RedisErrorKind::ParseError, err.description().to_string())
+    RedisError::new(RedisErrorKind::ParseError, err.to_string())
 }
 
 impl FromRedisValue for EntryInfo {
     fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
-        let (id, key_values): (String, HashMap<String, RedisValue>) = from_redis_value(value)?;
+        const FIELD_VALUE_CHUNK_LEN: usize = 2;
+
+        // the fields are Nil if the entry is deleted but still pending (XREADGROUP of the history),
+        // the entry is kept anyway, so the cursor of the history moves after it
+        let (id, values): (String, Option<Vec<RedisValue>>) = from_redis_value(value)?;
+        let values = values.unwrap_or_default();
+        // count of field names and values should be even
+        if values.len() % FIELD_VALUE_CHUNK_LEN != 0 {
+            return Err(RedisError::new(RedisErrorKind::IncorrectConversion,
+                                       format!("{:?} is not convertible to entry fields", values)));
+        }
+
+        let mut fields = Vec::with_capacity(values.len() / FIELD_VALUE_CHUNK_LEN);
+        let mut values = values.into_iter();
+        while let (Some(name), Some(value)) = (values.next(), values.next()) {
+            let name: Vec<u8> = from_redis_value(&name)?;
+            fields.push((name, value));
+        }
+
+        Ok(EntryInfo { id, fields })
+    }
+}
+
+impl FromRedisValue for EntryId {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        EntryId::from_string(from_redis_value(value)?)
+    }
+}
 
-        Ok(EntryInfo { id, key_values })
+impl FromRedisValue for RangeEntry {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        let entry: EntryInfo = from_redis_value(value)?;
+        Ok(RangeEntry::new(EntryId::from_string(entry.id)?, fields_to_map(entry.fields)?))
     }
 }
 
@@ -317,6 +707,47 @@ mod tests {
         assert!(parse_stream_entries(value).is_err(), "Expect an parse error");
     }
 
+    #[test]
+    fn test_parse_raw_entry() {
+        let entry = RedisValue::Array(vec![
+            RedisValue::BulkString(b"1581855076637-0".to_vec()),
+            RedisValue::Array(vec![
+                RedisValue::BulkString(b"key".to_vec()),
+                RedisValue::BulkString(b"value1".to_vec()),
+                RedisValue::BulkString(vec![0xff, 0x00]),
+                RedisValue::BulkString(b"binary".to_vec()),
+                RedisValue::BulkString(b"key".to_vec()),
+                RedisValue::BulkString(b"value2".to_vec()),
+            ])
+        ]);
+
+        let stream = RedisValue::Array(vec![
+            RedisValue::BulkString(b"stream".to_vec()),
+            RedisValue::Array(vec![entry])
+        ]);
+
+        let value = RedisValue::Array(vec![stream]);
+
+        // the order, the duplicates and the binary names are kept
+        let entries = parse_raw_stream_entries(value.clone()).unwrap();
+        assert_eq!(1, entries.len());
+        let fields = vec![
+            (b"key".to_vec(), RedisValue::BulkString(b"value1".to_vec())),
+            (vec![0xff, 0x00], RedisValue::BulkString(b"binary".to_vec())),
+            (b"key".to_vec(), RedisValue::BulkString(b"value2".to_vec())),
+        ];
+        assert_eq!(fields, entries[0].fields);
+        assert_eq!(Some(&RedisValue::BulkString(b"value1".to_vec())), entries[0].get(b"key"));
+
+        // the binary name is not convertible into the StreamEntry
+        assert!(parse_stream_entries(value).is_err(), "Expect an parse error");
+
+        let mut entry = entries[0].clone();
+        entry.fields.remove(1);
+        let entry = entry.into_entry().unwrap();
+        assert_eq!(RedisValue::BulkString(b"value2".to_vec()), entry.values["key"]);
+    }
+
     #[test]
     fn test_invalid_entry_structure() {
         let entry = RedisValue::Array(vec![
@@ -333,4 +764,88 @@ mod tests {
 
         assert!(parse_stream_entries(value).is_err(), "Expect an parse error");
     }
+
+    #[test]
+    fn test_range_bounds() {
+        let range = RangeType::Between(RangeBound::Exclusive(EntryId::new(1, 2)),
+                                       RangeBound::Timestamp(3));
+        assert_eq!(("(1-2".to_string(), "3".to_string()), range.to_left_right());
+        assert!(range.is_valid());
+
+        let range = RangeType::Between(RangeBound::Unbounded, RangeBound::Inclusive(EntryId::new(1, 0)));
+        assert_eq!(("-".to_string(), "1-0".to_string()), range.to_left_right());
+
+        // both of the bounds are on the same timestamp
+        assert!(RangeType::Between(RangeBound::Timestamp(1), RangeBound::Timestamp(1)).is_valid());
+        // there are no IDs between the exclusive bounds
+        assert!(!RangeType::Between(RangeBound::Exclusive(EntryId::new(1, 0)),
+                                    RangeBound::Exclusive(EntryId::new(1, 1))).is_valid());
+        assert!(!RangeType::Between(RangeBound::Timestamp(2), RangeBound::Timestamp(1)).is_valid());
+
+        let time = UNIX_EPOCH + std::time::Duration::from_millis(1581870410019);
+        assert_eq!(("1581870410019".to_string(), "1581870410019".to_string()),
+                   RangeType::between_times(time, time).to_left_right());
+    }
+
+    #[test]
+    fn test_entry_id() {
+        let id: EntryId = "1581870410019-1".parse().unwrap();
+        assert_eq!(EntryId::new(1581870410019, 1), id);
+        assert_eq!(id, id.to_string().parse().unwrap());
+        assert!("1581870410019".parse::<EntryId>().is_err());
+        assert!("1581870410019-x".parse::<EntryId>().is_err());
+        for malformed in &["1--2", "-1-2", "1-2-", "+1-+2", "1-", "-2", "-", "", " 1-2", "1-2 "] {
+            assert!(malformed.parse::<EntryId>().is_err(), "expected Err on {:?}", malformed);
+        }
+        // the value does not fit u64
+        assert!("18446744073709551616-0".parse::<EntryId>().is_err());
+
+        // IDs are ordered by the milliseconds then by the sequence numbers
+        let ids: std::collections::BTreeSet<EntryId> =
+            vec![EntryId::new(2, 0), EntryId::new(1, 5), EntryId::new(1, 0)].into_iter().collect();
+        assert_eq!(vec![EntryId::new(1, 0), EntryId::new(1, 5), EntryId::new(2, 0)],
+                   ids.into_iter().collect::<Vec<_>>());
+
+        assert_eq!(Some(EntryId::new(1, 2)), EntryId::new(1, 1).next());
+        assert_eq!(Some(EntryId::new(2, 0)), EntryId::new(1, u64::MAX).next());
+        assert_eq!(None, EntryId::MAX.next());
+        assert_eq!(Some(EntryId::new(0, u64::MAX)), EntryId::new(1, 0).prev());
+        assert_eq!(None, EntryId::MIN.prev());
+
+        let time = UNIX_EPOCH + std::time::Duration::from_millis(1581870410019);
+        assert_eq!(EntryId::new(1581870410019, 0), EntryId::from_time(time));
+        assert_eq!(time, id.time());
+    }
+
+    #[test]
+    fn test_stream_id() {
+        let ids = vec![
+            ("1-2", StreamId::Explicit(EntryId::new(1, 2))),
+            ("*", StreamId::Auto),
+            ("15-*", StreamId::AutoSeq(15)),
+            ("$", StreamId::Last),
+            (">", StreamId::Undelivered),
+            ("-", StreamId::Min),
+            ("+", StreamId::Max),
+        ];
+        for (arg, id) in ids.into_iter() {
+            assert_eq!(id, arg.parse().unwrap());
+            assert_eq!(arg, id.to_string());
+        }
+        assert!("x-*".parse::<StreamId>().is_err());
+    }
+
+    #[test]
+    fn test_entry_id_serde() {
+        use serde::de::IntoDeserializer;
+        use serde::de::value::{Error, StrDeserializer};
+
+        let deserializer: StrDeserializer<Error> = "1581870410019-1".into_deserializer();
+        assert_eq!(EntryId::new(1581870410019, 1), EntryId::deserialize(deserializer).unwrap());
+
+        for invalid in &["invalid", "1--2", "+1-+2"] {
+            let deserializer: StrDeserializer<Error> = invalid.into_deserializer();
+            assert!(EntryId::deserialize(deserializer).is_err());
+        }
+    }
 }
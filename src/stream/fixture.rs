@@ -0,0 +1,57 @@
+//! Fixture of the stream tests that are run against the mock server.
+
+use super::{RedisStream, SendEntryOptions, EntryId};
+use crate::mock::MockServer;
+use tokio::runtime::Runtime;
+use futures::{Future, Stream};
+use std::collections::HashMap;
+
+
+pub(crate) struct Fixture {
+    pub server: MockServer,
+    pub runtime: Runtime,
+}
+
+impl Fixture {
+    /// Start the mock server and the runtime to run the test futures within.
+    pub fn start() -> Fixture {
+        Fixture { server: MockServer::start().unwrap(), runtime: Runtime::new().unwrap() }
+    }
+
+    pub fn connect(&mut self) -> RedisStream {
+        let address = self.server.address();
+        self.block_on(RedisStream::connect(&address)).unwrap()
+    }
+
+    pub fn block_on<F>(&mut self, future: F) -> Result<F::Item, F::Error>
+        where F: Future + Send + 'static,
+              F::Item: Send + 'static,
+              F::Error: Send + 'static {
+        self.runtime.block_on(future)
+    }
+
+    /// Receive the next item of the subscription.
+    pub fn next<S>(&mut self, subscribe: S) -> (S::Item, S)
+        where S: Stream + Send + 'static,
+              S::Item: Send + 'static,
+              S::Error: Send + std::fmt::Debug + 'static {
+        let (item, subscribe) = self.block_on(subscribe.into_future().map_err(|(err, _)| err)).unwrap();
+        (item.expect("Expected the subscription is not finished"), subscribe)
+    }
+
+    /// Send `n` entries {"key": <index>} into the `stream` by a separate connection.
+    /// The response is the IDs of the sent entries.
+    pub fn populate(&mut self, stream: &str, n: usize) -> Vec<EntryId> {
+        let mut connection = self.connect();
+        let mut ids = Vec::new();
+        for i in 0..n {
+            let mut values = HashMap::new();
+            values.insert("key".to_string(), i);
+            let send_options = SendEntryOptions::new(stream.to_string());
+            let (con, id) = self.block_on(connection.send_entry(send_options, values)).unwrap();
+            connection = con;
+            ids.push(id);
+        }
+        ids
+    }
+}
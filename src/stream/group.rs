@@ -0,0 +1,219 @@
+use super::EntryId;
+use crate::{RedisCommand, command};
+
+
+/// ID of the last entry delivered to a group,
+/// the group delivers the entries with greater IDs.
+#[derive(Clone, PartialEq, Debug)]
+pub enum GroupStart {
+    /// "0" - deliver all of the stream entries
+    All,
+    /// "$" - deliver the entries added after the group creation (SETID) only
+    New,
+    /// Deliver the entries with IDs greater than the specified one
+    Id(EntryId),
+}
+
+/// Set of options that are required by `RedisStream::create_group()`
+///
+/// # Example
+/// ```
+/// use redis_asio::stream::{CreateGroupOptions, GroupStart};
+///
+/// // "XGROUP CREATE mystream mygroup 0 MKSTREAM ENTRIESREAD 0"
+/// let options = CreateGroupOptions::new("mystream".to_string(),
+///                                       "mygroup".to_string(),
+///                                       GroupStart::All)
+///     .with_make_stream()
+///     .with_entries_read(0);
+/// ```
+#[derive(Clone)]
+pub struct CreateGroupOptions {
+    pub(crate) stream: String,
+    pub(crate) group: String,
+    pub(crate) start: GroupStart,
+    /// MKSTREAM - make an empty stream if there is no such one yet
+    pub(crate) make_stream: bool,
+    /// ENTRIESREAD - the number of entries read by the group, it is used to calculate the lag
+    pub(crate) entries_read: Option<u64>,
+}
+
+/// Set of options that are required by `RedisStream::set_group_id()`
+#[derive(Clone)]
+pub struct SetGroupIdOptions {
+    pub(crate) stream: String,
+    pub(crate) group: String,
+    pub(crate) start: GroupStart,
+    /// ENTRIESREAD - the number of entries read by the group, it is used to calculate the lag
+    pub(crate) entries_read: Option<u64>,
+}
+
+/// Set of options that are required by `RedisStream::destroy_group()`
+#[derive(Clone)]
+pub struct DestroyGroupOptions {
+    pub(crate) stream: String,
+    pub(crate) group: String,
+}
+
+/// Set of options that are required by `RedisStream::create_consumer()`
+/// and `RedisStream::delete_consumer()`
+#[derive(Clone)]
+pub struct ConsumerOptions {
+    pub(crate) stream: String,
+    pub(crate) group: String,
+    pub(crate) consumer: String,
+}
+
+impl GroupStart {
+    fn to_arg(&self) -> String {
+        match self {
+            GroupStart::All => "0".to_string(),
+            GroupStart::New => "$".to_string(),
+            GroupStart::Id(id) => id.to_string(),
+        }
+    }
+}
+
+impl CreateGroupOptions {
+    pub fn new(stream: String, group: String, start: GroupStart) -> CreateGroupOptions {
+        CreateGroupOptions { stream, group, start, make_stream: false, entries_read: None }
+    }
+
+    pub fn with_make_stream(mut self) -> CreateGroupOptions {
+        self.make_stream = true;
+        self
+    }
+
+    pub fn with_entries_read(mut self, entries_read: u64) -> CreateGroupOptions {
+        self.entries_read = Some(entries_read);
+        self
+    }
+}
+
+impl SetGroupIdOptions {
+    pub fn new(stream: String, group: String, start: GroupStart) -> SetGroupIdOptions {
+        SetGroupIdOptions { stream, group, start, entries_read: None }
+    }
+
+    pub fn with_entries_read(mut self, entries_read: u64) -> SetGroupIdOptions {
+        self.entries_read = Some(entries_read);
+        self
+    }
+}
+
+impl DestroyGroupOptions {
+    pub fn new(stream: String, group: String) -> DestroyGroupOptions {
+        DestroyGroupOptions { stream, group }
+    }
+}
+
+impl ConsumerOptions {
+    pub fn new(stream: String, group: String, consumer: String) -> ConsumerOptions {
+        ConsumerOptions { stream, group, consumer }
+    }
+}
+
+pub(crate) fn create_group_command(options: CreateGroupOptions) -> RedisCommand {
+    let mut cmd = command("XGROUP")
+        .arg("CREATE")
+        .arg(options.stream)
+        .arg(options.group)
+        .arg(options.start.to_arg());
+    if options.make_stream {
+        cmd.arg_mut("MKSTREAM");
+    }
+    if let Some(entries_read) = options.entries_read {
+        cmd.append(command("ENTRIESREAD").arg(entries_read));
+    }
+    cmd
+}
+
+pub(crate) fn set_group_id_command(options: SetGroupIdOptions) -> RedisCommand {
+    let mut cmd = command("XGROUP")
+        .arg("SETID")
+        .arg(options.stream)
+        .arg(options.group)
+        .arg(options.start.to_arg());
+    if let Some(entries_read) = options.entries_read {
+        cmd.append(command("ENTRIESREAD").arg(entries_read));
+    }
+    cmd
+}
+
+pub(crate) fn destroy_group_command(options: DestroyGroupOptions) -> RedisCommand {
+    command("XGROUP")
+        .arg("DESTROY")
+        .arg(options.stream)
+        .arg(options.group)
+}
+
+pub(crate) fn create_consumer_command(options: ConsumerOptions) -> RedisCommand {
+    command("XGROUP")
+        .arg("CREATECONSUMER")
+        .arg(options.stream)
+        .arg(options.group)
+        .arg(options.consumer)
+}
+
+pub(crate) fn delete_consumer_command(options: ConsumerOptions) -> RedisCommand {
+    command("XGROUP")
+        .arg("DELCONSUMER")
+        .arg(options.stream)
+        .arg(options.group)
+        .arg(options.consumer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::{SubscribeOptions, RedisGroup};
+    use crate::stream::fixture::Fixture;
+
+    #[test]
+    fn test_group_management() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let group = "mygroup".to_string();
+        let create_options = CreateGroupOptions::new(stream.clone(), group.clone(), GroupStart::All)
+            .with_make_stream()
+            .with_entries_read(0);
+        let read_options = SubscribeOptions::with_group(
+            vec![stream.clone()], RedisGroup::new(group.clone(), "Alice".to_string()));
+
+        let ids = fixture.populate(&stream, 1);
+        let connection = fixture.connect();
+        let (connection, is_created) = fixture.block_on(connection.create_group(create_options.clone())).unwrap();
+        assert!(is_created);
+        // the BUSYGROUP error is tolerated and the connection is kept
+        let (connection, is_created) = fixture.block_on(connection.create_group(create_options)).unwrap();
+        assert!(!is_created);
+
+        let consumer_options = ConsumerOptions::new(stream.clone(), group.clone(), "Bob".to_string());
+        let (connection, is_created) = fixture.block_on(connection.create_consumer(consumer_options.clone()))
+            .unwrap();
+        assert!(is_created);
+
+        // the group starts from the beginning of the stream
+        let subscribe = fixture.connect().subscribe(read_options);
+        let subscribe = fixture.block_on(subscribe).unwrap();
+        let (entries, _) = fixture.next(subscribe);
+        assert_eq!(ids, entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+
+        let (connection, dropped) = fixture.block_on(connection.delete_consumer(consumer_options)).unwrap();
+        assert_eq!(0, dropped);
+        let alice_options = ConsumerOptions::new(stream.clone(), group.clone(), "Alice".to_string());
+        let (connection, dropped) = fixture.block_on(connection.delete_consumer(alice_options)).unwrap();
+        assert_eq!(1, dropped);
+
+        let set_id_options = SetGroupIdOptions::new(stream.clone(), group.clone(), GroupStart::Id(ids[0]))
+            .with_entries_read(1);
+        let connection = fixture.block_on(connection.set_group_id(set_id_options)).unwrap();
+
+        let destroy_options = DestroyGroupOptions::new(stream.clone(), group.clone());
+        let (connection, is_destroyed) = fixture.block_on(connection.destroy_group(destroy_options.clone()))
+            .unwrap();
+        assert!(is_destroyed);
+        let (_, is_destroyed) = fixture.block_on(connection.destroy_group(destroy_options)).unwrap();
+        assert!(!is_destroyed);
+    }
+}
@@ -0,0 +1,531 @@
+use super::{EntryId, RangeEntry};
+use crate::{RedisValue, RedisCommand, RedisResult, RedisError, RedisErrorKind, FromRedisValue,
+            command, from_redis_value};
+use std::collections::HashMap;
+
+
+/// Set of options that are required by `RedisStream::info_stream()`
+/// and `RedisStream::info_stream_full()`
+#[derive(Clone)]
+pub struct StreamInfoOptions {
+    pub(crate) stream: String,
+    /// COUNT - max count of entries and pending entries in the FULL mode.
+    /// Redis uses 10 if the value is None, 0 means all of the entries.
+    pub(crate) count: Option<u16>,
+}
+
+/// Set of options that are required by `RedisStream::info_groups()`
+#[derive(Clone)]
+pub struct GroupsInfoOptions {
+    pub(crate) stream: String,
+}
+
+/// Set of options that are required by `RedisStream::info_consumers()`
+#[derive(Clone)]
+pub struct ConsumersInfoOptions {
+    pub(crate) stream: String,
+    pub(crate) group: String,
+}
+
+/// Structure that wraps a response on XINFO STREAM request.
+/// The optional fields are replied by Redis >= 7.0 only.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StreamInfo {
+    /// Count of entries within the stream
+    pub length: u64,
+    pub radix_tree_keys: u64,
+    pub radix_tree_nodes: u64,
+    pub last_generated_id: EntryId,
+    pub max_deleted_entry_id: Option<EntryId>,
+    /// Count of entries added to the stream during its lifetime
+    pub entries_added: Option<u64>,
+    pub recorded_first_entry_id: Option<EntryId>,
+    /// Count of groups of the stream
+    pub groups: u64,
+    /// None if the stream is empty
+    pub first_entry: Option<RangeEntry>,
+    /// None if the stream is empty
+    pub last_entry: Option<RangeEntry>,
+}
+
+/// Structure that wraps a response on XINFO STREAM FULL request.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StreamFullInfo {
+    pub length: u64,
+    pub radix_tree_keys: u64,
+    pub radix_tree_nodes: u64,
+    pub last_generated_id: EntryId,
+    pub max_deleted_entry_id: Option<EntryId>,
+    pub entries_added: Option<u64>,
+    pub recorded_first_entry_id: Option<EntryId>,
+    /// Up to COUNT first entries of the stream
+    pub entries: Vec<RangeEntry>,
+    pub groups: Vec<GroupFullInfo>,
+}
+
+/// Structure that wraps a group received on XINFO GROUPS request.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GroupInfo {
+    pub name: String,
+    /// Count of consumers within the group
+    pub consumers: u64,
+    /// Count of pending entries of the group
+    pub pending: u64,
+    pub last_delivered_id: EntryId,
+    /// Count of entries delivered to the group, Redis >= 7.0
+    pub entries_read: Option<u64>,
+    /// Count of entries that are not delivered to the group yet, Redis >= 7.0.
+    /// None if Redis cannot calculate the lag.
+    pub lag: Option<u64>,
+}
+
+/// Structure that wraps a consumer received on XINFO CONSUMERS request.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConsumerInfo {
+    pub name: String,
+    /// Count of pending entries of the consumer
+    pub pending: u64,
+    /// Milliseconds elapsed since the last interaction of the consumer
+    pub idle_ms: u64,
+    /// Milliseconds elapsed since the last successful interaction of the consumer, Redis >= 7.2.
+    /// None if the consumer has never read or claimed an entry.
+    pub inactive_ms: Option<u64>,
+}
+
+/// Structure that wraps a group received on XINFO STREAM FULL request.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GroupFullInfo {
+    pub name: String,
+    pub last_delivered_id: EntryId,
+    pub entries_read: Option<u64>,
+    pub lag: Option<u64>,
+    /// Count of pending entries of the group
+    pub pel_count: u64,
+    /// Up to COUNT pending entries of the group
+    pub pending: Vec<GroupPendingEntry>,
+    pub consumers: Vec<ConsumerFullInfo>,
+}
+
+/// Structure that wraps a consumer received on XINFO STREAM FULL request.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConsumerFullInfo {
+    pub name: String,
+    /// Unix time of the last interaction of the consumer in milliseconds
+    pub seen_time: u64,
+    /// Unix time of the last successful interaction of the consumer in milliseconds, Redis >= 7.2
+    pub active_time: Option<u64>,
+    /// Count of pending entries of the consumer
+    pub pel_count: u64,
+    /// Up to COUNT pending entries of the consumer
+    pub pending: Vec<ConsumerPendingEntry>,
+}
+
+/// Pending entry of a group received on XINFO STREAM FULL request.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GroupPendingEntry {
+    pub id: EntryId,
+    pub consumer: String,
+    /// Unix time of the last delivery in milliseconds
+    pub delivery_time: u64,
+    pub delivery_count: u64,
+}
+
+/// Pending entry of a consumer received on XINFO STREAM FULL request.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConsumerPendingEntry {
+    pub id: EntryId,
+    /// Unix time of the last delivery in milliseconds
+    pub delivery_time: u64,
+    pub delivery_count: u64,
+}
+
+impl StreamInfoOptions {
+    pub fn new(stream: String) -> StreamInfoOptions {
+        StreamInfoOptions { stream, count: None }
+    }
+
+    /// The COUNT is used by `RedisStream::info_stream_full()` only.
+    pub fn with_count(mut self, count: u16) -> StreamInfoOptions {
+        self.count = Some(count);
+        self
+    }
+}
+
+impl GroupsInfoOptions {
+    pub fn new(stream: String) -> GroupsInfoOptions {
+        GroupsInfoOptions { stream }
+    }
+}
+
+impl ConsumersInfoOptions {
+    pub fn new(stream: String, group: String) -> ConsumersInfoOptions {
+        ConsumersInfoOptions { stream, group }
+    }
+}
+
+pub(crate) fn info_stream_command(options: StreamInfoOptions, full: bool) -> RedisCommand {
+    let mut cmd = command("XINFO")
+        .arg("STREAM")
+        .arg(options.stream);
+    if full {
+        cmd.arg_mut("FULL");
+        if let Some(count) = options.count {
+            cmd.append(command("COUNT").arg(count));
+        }
+    }
+    cmd
+}
+
+pub(crate) fn info_groups_command(options: GroupsInfoOptions) -> RedisCommand {
+    command("XINFO")
+        .arg("GROUPS")
+        .arg(options.stream)
+}
+
+pub(crate) fn info_consumers_command(options: ConsumersInfoOptions) -> RedisCommand {
+    command("XINFO")
+        .arg("CONSUMERS")
+        .arg(options.stream)
+        .arg(options.group)
+}
+
+/// Parse the last generated ID of the stream from XINFO STREAM result,
+/// the other fields are skipped.
+pub(crate) fn parse_last_generated_id(value: &RedisValue) -> RedisResult<EntryId> {
+    let fields: InfoFields = from_redis_value(value)?;
+    fields.get("last-generated-id")
+}
+
+/// Internal structure is used to parse XINFO replies
+/// that are flat arrays of field-value pairs.
+struct InfoFields(HashMap<String, RedisValue>);
+
+impl InfoFields {
+    fn get<T: FromRedisValue>(&self, field: &str) -> RedisResult<T> {
+        match self.0.get(field) {
+            Some(value) => from_redis_value(value),
+            _ => Err(RedisError::new(
+                RedisErrorKind::ParseError,
+                format!("Couldn't find the \"{}\" field within the XINFO response", field))),
+        }
+    }
+
+    /// Get the field that is not replied by the previous Redis versions.
+    fn get_opt<T: FromRedisValue>(&self, field: &str) -> RedisResult<Option<T>> {
+        match self.0.get(field) {
+            Some(value) => from_redis_value(value),
+            _ => Ok(None),
+        }
+    }
+
+    /// Get the time that is replied as -1 if there is no such event.
+    fn get_time(&self, field: &str) -> RedisResult<Option<u64>> {
+        let time: Option<i64> = self.get_opt(field)?;
+        Ok(time.filter(|time| *time >= 0).map(|time| time as u64))
+    }
+}
+
+impl FromRedisValue for InfoFields {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        Ok(InfoFields(from_redis_value(value)?))
+    }
+}
+
+impl FromRedisValue for StreamInfo {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        let fields: InfoFields = from_redis_value(value)?;
+        Ok(StreamInfo {
+            length: fields.get("length")?,
+            radix_tree_keys: fields.get("radix-tree-keys")?,
+            radix_tree_nodes: fields.get("radix-tree-nodes")?,
+            last_generated_id: fields.get("last-generated-id")?,
+            max_deleted_entry_id: fields.get_opt("max-deleted-entry-id")?,
+            entries_added: fields.get_opt("entries-added")?,
+            recorded_first_entry_id: fields.get_opt("recorded-first-entry-id")?,
+            groups: fields.get("groups")?,
+            first_entry: fields.get("first-entry")?,
+            last_entry: fields.get("last-entry")?,
+        })
+    }
+}
+
+impl FromRedisValue for StreamFullInfo {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        let fields: InfoFields = from_redis_value(value)?;
+        Ok(StreamFullInfo {
+            length: fields.get("length")?,
+            radix_tree_keys: fields.get("radix-tree-keys")?,
+            radix_tree_nodes: fields.get("radix-tree-nodes")?,
+            last_generated_id: fields.get("last-generated-id")?,
+            max_deleted_entry_id: fields.get_opt("max-deleted-entry-id")?,
+            entries_added: fields.get_opt("entries-added")?,
+            recorded_first_entry_id: fields.get_opt("recorded-first-entry-id")?,
+            entries: fields.get("entries")?,
+            groups: fields.get("groups")?,
+        })
+    }
+}
+
+impl FromRedisValue for GroupInfo {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        let fields: InfoFields = from_redis_value(value)?;
+        Ok(GroupInfo {
+            name: fields.get("name")?,
+            consumers: fields.get("consumers")?,
+            pending: fields.get("pending")?,
+            last_delivered_id: fields.get("last-delivered-id")?,
+            entries_read: fields.get_opt("entries-read")?,
+            lag: fields.get_opt("lag")?,
+        })
+    }
+}
+
+impl FromRedisValue for ConsumerInfo {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        let fields: InfoFields = from_redis_value(value)?;
+        Ok(ConsumerInfo {
+            name: fields.get("name")?,
+            pending: fields.get("pending")?,
+            idle_ms: fields.get("idle")?,
+            inactive_ms: fields.get_time("inactive")?,
+        })
+    }
+}
+
+impl FromRedisValue for GroupFullInfo {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        let fields: InfoFields = from_redis_value(value)?;
+        Ok(GroupFullInfo {
+            name: fields.get("name")?,
+            last_delivered_id: fields.get("last-delivered-id")?,
+            entries_read: fields.get_opt("entries-read")?,
+            lag: fields.get_opt("lag")?,
+            pel_count: fields.get("pel-count")?,
+            pending: fields.get("pending")?,
+            consumers: fields.get("consumers")?,
+        })
+    }
+}
+
+impl FromRedisValue for ConsumerFullInfo {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        let fields: InfoFields = from_redis_value(value)?;
+        Ok(ConsumerFullInfo {
+            name: fields.get("name")?,
+            seen_time: fields.get("seen-time")?,
+            active_time: fields.get_time("active-time")?,
+            pel_count: fields.get("pel-count")?,
+            pending: fields.get("pending")?,
+        })
+    }
+}
+
+impl FromRedisValue for GroupPendingEntry {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        let (id, consumer, delivery_time, delivery_count) = pending_entry_fields(value, 4)?;
+        Ok(GroupPendingEntry { id, consumer: from_redis_value(&consumer)?, delivery_time, delivery_count })
+    }
+}
+
+impl FromRedisValue for ConsumerPendingEntry {
+    fn from_redis_value(value: &RedisValue) -> RedisResult<Self> {
+        let (id, _, delivery_time, delivery_count) = pending_entry_fields(value, 3)?;
+        Ok(ConsumerPendingEntry { id, delivery_time, delivery_count })
+    }
+}
+
+/// Parse a pending entry: [id, (consumer), delivery time, delivery count].
+/// The consumer is Nil if the entry has no consumer field.
+fn pending_entry_fields(value: &RedisValue, len: usize) -> RedisResult<(EntryId, RedisValue, u64, u64)> {
+    let values: Vec<RedisValue> = from_redis_value(value)?;
+    if values.len() != len {
+        return Err(RedisError::new(
+            RedisErrorKind::ParseError,
+            format!("Couldn't parse a XINFO pending entry: {:?}", values)));
+    }
+
+    let consumer = match len {
+        4 => values[1].clone(),
+        _ => RedisValue::Nil,
+    };
+    Ok((from_redis_value(&values[0])?,
+        consumer,
+        from_redis_value(&values[len - 2])?,
+        from_redis_value(&values[len - 1])?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::{SubscribeOptions, RedisGroup, CreateGroupOptions, GroupStart};
+    use crate::stream::fixture::Fixture;
+
+    fn bulk(data: &str) -> RedisValue {
+        RedisValue::BulkString(data.as_bytes().to_vec())
+    }
+
+    fn entry(id: &str) -> RedisValue {
+        RedisValue::Array(vec![bulk(id), RedisValue::Array(vec![bulk("key"), bulk("value")])])
+    }
+
+    #[test]
+    fn test_parse_stream_info() {
+        let value = RedisValue::Array(vec![
+            bulk("length"), RedisValue::Int(2),
+            bulk("radix-tree-keys"), RedisValue::Int(1),
+            bulk("radix-tree-nodes"), RedisValue::Int(2),
+            bulk("last-generated-id"), bulk("2-0"),
+            bulk("max-deleted-entry-id"), bulk("0-0"),
+            bulk("entries-added"), RedisValue::Int(2),
+            bulk("recorded-first-entry-id"), bulk("1-0"),
+            bulk("groups"), RedisValue::Int(1),
+            bulk("first-entry"), entry("1-0"),
+            bulk("last-entry"), entry("2-0"),
+        ]);
+        let info: StreamInfo = from_redis_value(&value).unwrap();
+        assert_eq!(2, info.length);
+        assert_eq!(EntryId::new(2, 0), info.last_generated_id);
+        assert_eq!(Some(2), info.entries_added);
+        assert_eq!(Some(EntryId::new(1, 0)), info.first_entry.map(|entry| entry.id));
+        assert_eq!(RedisValue::BulkString(b"value".to_vec()), info.last_entry.unwrap().values["key"]);
+
+        // Redis < 7.0 and an empty stream
+        let value = RedisValue::Array(vec![
+            bulk("length"), RedisValue::Int(0),
+            bulk("radix-tree-keys"), RedisValue::Int(0),
+            bulk("radix-tree-nodes"), RedisValue::Int(1),
+            bulk("last-generated-id"), bulk("0-0"),
+            bulk("groups"), RedisValue::Int(0),
+            bulk("first-entry"), RedisValue::Nil,
+            bulk("last-entry"), RedisValue::Nil,
+        ]);
+        let info: StreamInfo = from_redis_value(&value).unwrap();
+        assert_eq!(None, info.max_deleted_entry_id);
+        assert_eq!(None, info.first_entry);
+
+        let invalid = RedisValue::Array(vec![bulk("length"), RedisValue::Int(0)]);
+        assert!(from_redis_value::<StreamInfo>(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_parse_groups_and_consumers_info() {
+        let value = RedisValue::Array(vec![RedisValue::Array(vec![
+            bulk("name"), bulk("mygroup"),
+            bulk("consumers"), RedisValue::Int(2),
+            bulk("pending"), RedisValue::Int(3),
+            bulk("last-delivered-id"), bulk("3-0"),
+            bulk("entries-read"), RedisValue::Int(3),
+            bulk("lag"), RedisValue::Nil,
+        ])]);
+        let expected = vec![GroupInfo {
+            name: "mygroup".to_string(),
+            consumers: 2,
+            pending: 3,
+            last_delivered_id: EntryId::new(3, 0),
+            entries_read: Some(3),
+            lag: None,
+        }];
+        assert_eq!(expected, from_redis_value::<Vec<GroupInfo>>(&value).unwrap());
+
+        let value = RedisValue::Array(vec![RedisValue::Array(vec![
+            bulk("name"), bulk("Alice"),
+            bulk("pending"), RedisValue::Int(1),
+            bulk("idle"), RedisValue::Int(100),
+            bulk("inactive"), RedisValue::Int(-1),
+        ])]);
+        let expected = vec![ConsumerInfo {
+            name: "Alice".to_string(),
+            pending: 1,
+            idle_ms: 100,
+            inactive_ms: None,
+        }];
+        assert_eq!(expected, from_redis_value::<Vec<ConsumerInfo>>(&value).unwrap());
+    }
+
+    #[test]
+    fn test_parse_stream_full_info() {
+        let consumer = RedisValue::Array(vec![
+            bulk("name"), bulk("Alice"),
+            bulk("seen-time"), RedisValue::Int(1000),
+            bulk("active-time"), RedisValue::Int(900),
+            bulk("pel-count"), RedisValue::Int(1),
+            bulk("pending"), RedisValue::Array(vec![
+                RedisValue::Array(vec![bulk("1-0"), RedisValue::Int(900), RedisValue::Int(1)])]),
+        ]);
+        let group = RedisValue::Array(vec![
+            bulk("name"), bulk("mygroup"),
+            bulk("last-delivered-id"), bulk("1-0"),
+            bulk("entries-read"), RedisValue::Int(1),
+            bulk("lag"), RedisValue::Int(0),
+            bulk("pel-count"), RedisValue::Int(1),
+            bulk("pending"), RedisValue::Array(vec![
+                RedisValue::Array(vec![bulk("1-0"), bulk("Alice"), RedisValue::Int(900), RedisValue::Int(1)])]),
+            bulk("consumers"), RedisValue::Array(vec![consumer]),
+        ]);
+        let value = RedisValue::Array(vec![
+            bulk("length"), RedisValue::Int(1),
+            bulk("radix-tree-keys"), RedisValue::Int(1),
+            bulk("radix-tree-nodes"), RedisValue::Int(2),
+            bulk("last-generated-id"), bulk("1-0"),
+            bulk("entries"), RedisValue::Array(vec![entry("1-0")]),
+            bulk("groups"), RedisValue::Array(vec![group]),
+        ]);
+
+        let info: StreamFullInfo = from_redis_value(&value).unwrap();
+        assert_eq!(1, info.entries.len());
+        assert_eq!(1, info.groups.len());
+        let group = &info.groups[0];
+        assert_eq!(Some(0), group.lag);
+        assert_eq!(vec![GroupPendingEntry {
+            id: EntryId::new(1, 0),
+            consumer: "Alice".to_string(),
+            delivery_time: 900,
+            delivery_count: 1,
+        }], group.pending);
+        assert_eq!(Some(900), group.consumers[0].active_time);
+        assert_eq!(vec![ConsumerPendingEntry { id: EntryId::new(1, 0), delivery_time: 900, delivery_count: 1 }],
+                   group.consumers[0].pending);
+    }
+
+    #[test]
+    fn test_info() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let group = "mygroup".to_string();
+        let create_options = CreateGroupOptions::new(stream.clone(), group.clone(), GroupStart::All)
+            .with_make_stream();
+        let read_options = SubscribeOptions::with_group(
+            vec![stream.clone()], RedisGroup::new(group.clone(), "Alice".to_string()));
+
+        let create = fixture.connect().create_group(create_options);
+        let connection = fixture.block_on(create).unwrap().0;
+        let id = fixture.populate(&stream, 1).remove(0);
+        let subscribe = fixture.connect().subscribe(read_options);
+        let subscribe = fixture.block_on(subscribe).unwrap();
+        fixture.next(subscribe);
+
+        let (connection, info) = fixture.block_on(
+            connection.info_stream(StreamInfoOptions::new(stream.clone()))).unwrap();
+        assert_eq!(1, info.length);
+        assert_eq!(id, info.last_generated_id);
+        assert_eq!(Some(id), info.first_entry.map(|entry| entry.id));
+
+        let (connection, groups) = fixture.block_on(
+            connection.info_groups(GroupsInfoOptions::new(stream.clone()))).unwrap();
+        assert_eq!(1, groups.len());
+        assert_eq!(group, groups[0].name);
+        assert_eq!(1, groups[0].pending);
+        assert_eq!(id, groups[0].last_delivered_id);
+
+        let (connection, consumers) = fixture.block_on(
+            connection.info_consumers(ConsumersInfoOptions::new(stream.clone(), group.clone()))).unwrap();
+        assert_eq!(vec!["Alice".to_string()], consumers.iter().map(|x| x.name.clone()).collect::<Vec<_>>());
+        assert!(consumers[0].inactive_ms.is_some());
+
+        let (_, info) = fixture.block_on(
+            connection.info_stream_full(StreamInfoOptions::new(stream.clone()).with_count(0))).unwrap();
+        assert_eq!(1, info.entries.len());
+        assert_eq!(1, info.groups[0].pel_count);
+        assert_eq!(id, info.groups[0].consumers[0].pending[0].id);
+    }
+}
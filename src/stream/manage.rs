@@ -1,5 +1,6 @@
 use super::EntryId;
-use crate::{RedisCommand, RedisResult, command};
+use crate::{RedisCommand, RedisResult, RedisValue, RedisError, RedisErrorKind, command,
+            from_redis_value};
 
 
 /// Set of options that are required by `RedisStream::pending_entries()`
@@ -22,12 +23,24 @@ pub struct TouchGroupOptions {
     pub(crate) group: String,
 }
 
-/// Set of options that are required by `RedisStream::ack_entry()`
+/// Set of options that are required by `RedisStream::ack_entry()`,
+/// `RedisStream::ack_entries()` and `RedisStream::ack_delete_entries()`
+///
+/// # Example
+/// ```
+/// use redis_asio::stream::{AckOptions, EntryId};
+///
+/// // "XACK mystream mygroup 1-0 2-0 3-0"
+/// let mut options = AckOptions::with_entries("mystream".to_string(),
+///                                            "mygroup".to_string(),
+///                                            vec![EntryId::new(1, 0), EntryId::new(2, 0)]);
+/// options.add_entry(EntryId::new(3, 0));
+/// ```
 #[derive(Clone)]
 pub struct AckOptions {
     pub(crate) stream: String,
     pub(crate) group: String,
-    pub(crate) entry_id: EntryId,
+    pub(crate) entry_ids: Vec<EntryId>,
 }
 
 /// Structure that wraps a response on XACK request.
@@ -37,11 +50,93 @@ pub enum AckResponse {
     NotExists,
 }
 
+/// Policy of the consumer group references of the entries deleted
+/// by XACKDEL and XDELEX requests (Redis 8.2 and later).
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum DeletePolicy {
+    /// KEEPREF - delete the entries but keep them within the PELs of the other groups
+    KeepRef,
+    /// DELREF - delete the entries and remove them from the PELs of all of the groups
+    DelRef,
+    /// ACKED - delete the entries that are acknowledged by all of the groups only
+    Acked,
+}
+
+/// Structure that wraps a per-entry response on XACKDEL and XDELEX requests.
+#[derive(PartialEq, Debug, Clone)]
+pub enum DeleteResponse {
+    /// There is no such entry within the stream
+    NotExists,
+    /// The entry has been deleted
+    Deleted,
+    /// The entry has not been deleted since it is still pending within a group (`DeletePolicy::Acked`)
+    Referenced,
+}
+
 pub(crate) fn ack_entry_command(options: AckOptions) -> RedisCommand {
-    command("XACK")
-        .arg(options.stream)
-        .arg(options.group)
-        .arg(options.entry_id.to_string())
+    ack_entries_command(options.stream, options.group, options.entry_ids)
+}
+
+pub(crate) fn ack_entries_command(stream: String, group: String, ids: Vec<EntryId>) -> RedisCommand {
+    let mut cmd = command("XACK").arg(stream).arg(group);
+    for id in ids.into_iter() {
+        cmd.arg_mut(id.to_string());
+    }
+    cmd
+}
+
+pub(crate) fn ack_delete_command(options: AckOptions, policy: DeletePolicy) -> RedisCommand {
+    let mut cmd = command("XACKDEL").arg(options.stream).arg(options.group);
+    cmd.append(delete_ids_args(options.entry_ids, policy));
+    cmd
+}
+
+pub(crate) fn delete_entries_ex_command(stream: String, ids: Vec<EntryId>, policy: DeletePolicy)
+                                        -> RedisCommand {
+    let mut cmd = command("XDELEX").arg(stream);
+    cmd.append(delete_ids_args(ids, policy));
+    cmd
+}
+
+fn delete_ids_args(ids: Vec<EntryId>, policy: DeletePolicy) -> RedisCommand {
+    let mut cmd = match policy {
+        DeletePolicy::KeepRef => command("KEEPREF"),
+        DeletePolicy::DelRef => command("DELREF"),
+        DeletePolicy::Acked => command("ACKED"),
+    };
+
+    cmd.arg_mut("IDS");
+    cmd.arg_mut(ids.len() as i64);
+    for id in ids.into_iter() {
+        cmd.arg_mut(id.to_string());
+    }
+    cmd
+}
+
+/// Parse XACKDEL/XDELEX result: the array of the per-entry codes.
+pub(crate) fn parse_delete_responses(value: &RedisValue) -> RedisResult<Vec<DeleteResponse>> {
+    let codes: Vec<i64> = from_redis_value(value)?;
+    codes.into_iter()
+        .map(|code| match code {
+            -1 => Ok(DeleteResponse::NotExists),
+            1 => Ok(DeleteResponse::Deleted),
+            2 => Ok(DeleteResponse::Referenced),
+            _ => Err(RedisError::new(RedisErrorKind::ParseError,
+                                     format!("Unexpected XACKDEL/XDELEX code: {}", code))),
+        })
+        .collect()
+}
+
+pub(crate) fn delete_entries_command(stream: String, ids: Vec<EntryId>) -> RedisCommand {
+    let mut cmd = command("XDEL").arg(stream);
+    for id in ids.into_iter() {
+        cmd.arg_mut(id.to_string());
+    }
+    cmd
+}
+
+pub(crate) fn len_command(stream: String) -> RedisCommand {
+    command("XLEN").arg(stream)
 }
 
 pub(crate) fn pending_list_command(options: PendingOptions) -> RedisCommand {
@@ -50,7 +145,7 @@ pub(crate) fn pending_list_command(options: PendingOptions) -> RedisCommand {
         .arg(options.group)
         .arg(options.consumer);
     if let Some(count) = options.count {
-        cmd.arg_mut(count);
+        cmd.append(command("COUNT").arg(count));
     }
 
     cmd.arg_mut("STREAMS");
@@ -64,15 +159,6 @@ pub(crate) fn pending_list_command(options: PendingOptions) -> RedisCommand {
     cmd
 }
 
-pub(crate) fn touch_group_command(options: TouchGroupOptions) -> RedisCommand {
-    command("XGROUP")
-        .arg("CREATE")
-        .arg(options.stream)
-        .arg(options.group)
-        .arg("$")
-        .arg("MKSTREAM") // make an empty stream if there is no such one yet
-}
-
 impl AckResponse {
     pub(crate) fn new(count_acknowledged: i64) -> Self {
         match count_acknowledged {
@@ -110,6 +196,91 @@ impl TouchGroupOptions {
 
 impl AckOptions {
     pub fn new(stream: String, group: String, entry_id: EntryId) -> Self {
-        AckOptions { stream, group, entry_id }
+        AckOptions::with_entries(stream, group, vec![entry_id])
+    }
+
+    pub fn with_entries(stream: String, group: String, entry_ids: Vec<EntryId>) -> Self {
+        AckOptions { stream, group, entry_ids }
+    }
+
+    pub fn add_entry(&mut self, entry_id: EntryId) {
+        self.entry_ids.push(entry_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::{RangeOptions, RangeType, SubscribeOptions, RedisGroup};
+    use crate::stream::fixture::Fixture;
+
+    #[test]
+    fn test_rev_range_and_delete() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let ids = fixture.populate(&stream, 3);
+
+        let range_options = RangeOptions::new(stream.clone(), 2, RangeType::Any).unwrap();
+        let rev_range = fixture.connect().rev_range(range_options);
+        let (connection, entries) = fixture.block_on(rev_range).unwrap();
+        assert_eq!(vec![ids[2], ids[1]],
+                   entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+
+        let (connection, deleted) = fixture.block_on(
+            connection.delete_entries(stream.clone(), vec![ids[0], EntryId::new(0, 1)])).unwrap();
+        assert_eq!(1, deleted);
+        let (connection, deleted) = fixture.block_on(connection.delete_entries(stream.clone(), Vec::new()))
+            .unwrap();
+        assert_eq!(0, deleted);
+
+        let (connection, len) = fixture.block_on(connection.len(stream.clone())).unwrap();
+        assert_eq!(2, len);
+        let (_, len) = fixture.block_on(connection.len("unknown".to_string())).unwrap();
+        assert_eq!(0, len);
+    }
+
+    #[test]
+    fn test_ack_and_delete_entries() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let group = "mygroup".to_string();
+
+        let touch = fixture.connect().touch_group(TouchGroupOptions::new(stream.clone(), group.clone()));
+        fixture.block_on(touch).unwrap();
+        let ids = fixture.populate(&stream, 4);
+        let read_options = SubscribeOptions::with_group(
+            vec![stream.clone()], RedisGroup::new(group.clone(), "Alice".to_string()));
+        let subscribe = fixture.connect().subscribe(read_options);
+        let subscribe = fixture.block_on(subscribe).unwrap();
+        fixture.next(subscribe);
+
+        let mut ack_options = AckOptions::with_entries(stream.clone(), group.clone(), ids[..2].to_vec());
+        ack_options.add_entry(EntryId::new(0, 1));
+        // the single entry acknowledgement could not report the partial one
+        let ack = fixture.connect().ack_entry(ack_options.clone());
+        assert_eq!(RedisErrorKind::InvalidOptions, fixture.block_on(ack).err().unwrap().error);
+        let ack = fixture.connect().ack_entry(AckOptions::with_entries(stream.clone(), group.clone(), Vec::new()));
+        assert_eq!(RedisErrorKind::InvalidOptions, fixture.block_on(ack).err().unwrap().error);
+
+        let ack = fixture.connect().ack_entries(ack_options);
+        let (connection, acked) = fixture.block_on(ack).unwrap();
+        assert_eq!(2, acked);
+        let ack = connection.ack_entries(AckOptions::with_entries(stream.clone(), group.clone(), Vec::new()));
+        let (connection, acked) = fixture.block_on(ack).unwrap();
+        assert_eq!(0, acked);
+
+        // the entry 3 is still pending
+        let delete = connection.delete_entries_ex(stream.clone(), vec![ids[0], ids[2]],
+                                                  DeletePolicy::Acked);
+        let (connection, responses) = fixture.block_on(delete).unwrap();
+        assert_eq!(vec![DeleteResponse::Deleted, DeleteResponse::Referenced], responses);
+
+        let ack_options = AckOptions::with_entries(stream.clone(), group.clone(), ids[2..].to_vec());
+        let ack_delete = connection.ack_delete_entries(ack_options, DeletePolicy::KeepRef);
+        let (connection, responses) = fixture.block_on(ack_delete).unwrap();
+        assert_eq!(vec![DeleteResponse::Deleted, DeleteResponse::Deleted], responses);
+
+        let (_, len) = fixture.block_on(connection.len(stream.clone())).unwrap();
+        assert_eq!(1, len);
     }
 }
@@ -2,18 +2,48 @@
 //! for work with Redis-Stream "https://redis.io/topics/streams-intro".
 
 mod entry;
+#[allow(clippy::module_inception)]
 mod stream;
 mod produce;
 mod consume;
 mod manage;
+mod claim;
+mod pending;
+mod dead_letter;
+mod group;
+mod info;
+mod process;
+#[cfg(test)]
+mod fixture;
 
-pub use entry::{StreamEntry, EntryId, RangeEntry, RangeType};
+pub use entry::{StreamEntry, RawStreamEntry, EntryId, StreamId, RangeEntry, RangeType, RangeBound};
 pub use stream::RedisStream;
-pub use produce::SendEntryOptions;
-pub use consume::{SubscribeOptions, ReadExplicitOptions, RangeOptions, RedisGroup, Subscribe};
-pub use manage::{AckOptions, PendingOptions, TouchGroupOptions, AckResponse};
+pub use produce::{SendEntryOptions, TrimOptions, TrimStrategy, IdGenerator, GeneratedIdOptions};
+pub use consume::{SubscribeOptions, SubscribeStart, ReadExplicitOptions, RangeOptions, RedisGroup,
+                  Subscribe, PhasedSubscribe, RawSubscribe, SubscribePhase, SubscribeControl,
+                  PrefetchWindow};
+pub use manage::{AckOptions, PendingOptions, TouchGroupOptions, AckResponse, DeletePolicy, DeleteResponse};
+pub use claim::{ClaimOptions, AutoClaimOptions, ClaimedEntries, AutoClaimedEntries};
+pub use pending::{PendingSummaryOptions, PendingInfoOptions, PendingSummary, PendingEntryInfo};
+pub use dead_letter::{DeadLetterOptions, SettleResponse, ExceededEntries, DEAD_LETTER_STREAM, DEAD_LETTER_ID,
+                      DEAD_LETTER_CONSUMER, DEAD_LETTER_REASON};
+pub use group::{GroupStart, CreateGroupOptions, SetGroupIdOptions, DestroyGroupOptions, ConsumerOptions};
+pub use info::{StreamInfoOptions, GroupsInfoOptions, ConsumersInfoOptions, StreamInfo, StreamFullInfo,
+               GroupInfo, ConsumerInfo, GroupFullInfo, ConsumerFullInfo, GroupPendingEntry,
+               ConsumerPendingEntry};
+pub use process::{ProcessOptions, ProcessedBatch};
 
-use entry::{parse_stream_entries, parse_range_entries};
-use produce::add_command;
-use consume::{subscribe, subscribe_cmd, read_explicit_cmd, range_cmd};
-use manage::{ack_entry_command, pending_list_command, touch_group_command};
+use entry::{parse_stream_entries, parse_raw_stream_entries, parse_range_entries, parse_raw_range_entries,
+            parse_claimed_entries};
+use produce::{add_command, trim_command};
+use consume::{subscribe, subscribe_cmd, read_explicit_cmd, range_cmd, range_page_cmd,
+              rev_range_cmd, parse_raw_subscribe_entries, last_ids};
+use manage::{ack_entry_command, ack_entries_command, ack_delete_command, pending_list_command,
+             delete_entries_command, delete_entries_ex_command, len_command, parse_delete_responses};
+use claim::{claim_command, auto_claim_command, parse_claimed_ids, parse_auto_claim};
+use pending::{pending_summary_command, pending_info_command, parse_pending_summary,
+              parse_pending_info};
+use dead_letter::dead_letter_values;
+use group::{create_group_command, set_group_id_command, destroy_group_command, create_consumer_command,
+            delete_consumer_command};
+use info::{info_stream_command, info_groups_command, info_consumers_command, parse_last_generated_id};
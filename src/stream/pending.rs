@@ -0,0 +1,222 @@
+use super::{EntryId, RangeType};
+use crate::{RedisValue, RedisCommand, RedisResult, RedisError, RedisErrorKind, command,
+            from_redis_value};
+use std::time::Duration;
+
+
+/// Set of options that are required by `RedisStream::pending_summary()`
+#[derive(Clone)]
+pub struct PendingSummaryOptions {
+    pub(crate) stream: String,
+    pub(crate) group: String,
+}
+
+/// Set of options that are required by `RedisStream::pending_info()`
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use redis_asio::stream::{PendingInfoOptions, RangeType, EntryId};
+///
+/// // "XPENDING mystream mygroup IDLE 60000 1-0 + 10 Alice"
+/// let options = PendingInfoOptions::new("mystream".to_string(), "mygroup".to_string(), 10)
+///     .with_range(RangeType::GreaterThan(EntryId::new(1, 0)))
+///     .with_consumer("Alice".to_string())
+///     .with_min_idle_time(Duration::from_secs(60));
+/// ```
+#[derive(Clone)]
+pub struct PendingInfoOptions {
+    pub(crate) stream: String,
+    pub(crate) group: String,
+    /// Get the pending entries within the range only
+    pub(crate) range: RangeType,
+    /// Max count of entries
+    pub(crate) count: u16,
+    /// Get the pending entries of the consumer only
+    pub(crate) consumer: Option<String>,
+    /// IDLE - get the entries that are idle for at least the time only
+    pub(crate) min_idle_time: Option<Duration>,
+}
+
+/// Structure that wraps a response on XPENDING request in the summary form.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PendingSummary {
+    /// Count of the pending entries within the group
+    pub count: u64,
+    /// The smallest ID among the pending entries, None if there are no pending entries
+    pub min_id: Option<EntryId>,
+    /// The greatest ID among the pending entries, None if there are no pending entries
+    pub max_id: Option<EntryId>,
+    /// Consumers that have at least one pending entry and count of their entries
+    pub consumers: Vec<(String, u64)>,
+}
+
+/// Structure that wraps a pending entry received on XPENDING request in the extended form.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PendingEntryInfo {
+    pub id: EntryId,
+    /// The consumer that owns the entry
+    pub consumer: String,
+    /// Milliseconds elapsed since the last delivery of the entry
+    pub idle_ms: u64,
+    /// Number of times the entry was delivered
+    pub delivery_count: u64,
+}
+
+impl PendingSummaryOptions {
+    pub fn new(stream: String, group: String) -> PendingSummaryOptions {
+        PendingSummaryOptions { stream, group }
+    }
+}
+
+impl PendingInfoOptions {
+    /// Get up to `count` pending entries of the group with any IDs.
+    pub fn new(stream: String, group: String, count: u16) -> PendingInfoOptions {
+        PendingInfoOptions {
+            stream,
+            group,
+            range: RangeType::Any,
+            count,
+            consumer: None,
+            min_idle_time: None,
+        }
+    }
+
+    pub fn with_range(mut self, range: RangeType) -> PendingInfoOptions {
+        self.range = range;
+        self
+    }
+
+    pub fn with_consumer(mut self, consumer: String) -> PendingInfoOptions {
+        self.consumer = Some(consumer);
+        self
+    }
+
+    pub fn with_min_idle_time(mut self, min_idle_time: Duration) -> PendingInfoOptions {
+        self.min_idle_time = Some(min_idle_time);
+        self
+    }
+}
+
+pub(crate) fn pending_summary_command(options: PendingSummaryOptions) -> RedisCommand {
+    command("XPENDING")
+        .arg(options.stream)
+        .arg(options.group)
+}
+
+pub(crate) fn pending_info_command(options: PendingInfoOptions) -> RedisCommand {
+    let mut cmd = command("XPENDING")
+        .arg(options.stream)
+        .arg(options.group);
+    if let Some(min_idle_time) = options.min_idle_time {
+        cmd.append(command("IDLE").arg(min_idle_time.as_millis() as u64));
+    }
+
+    let (left, right) = options.range.to_left_right();
+    cmd.arg_mut(left);
+    cmd.arg_mut(right);
+    cmd.arg_mut(options.count);
+    if let Some(consumer) = options.consumer {
+        cmd.arg_mut(consumer);
+    }
+    cmd
+}
+
+/// Parse XPENDING result in the summary form: [count, min ID, max ID, [[consumer, count], ...]].
+/// The IDs and the consumers are Nil if there are no pending entries.
+pub(crate) fn parse_pending_summary(value: RedisValue) -> RedisResult<PendingSummary> {
+    let values: Vec<RedisValue> = from_redis_value(&value)?;
+    if values.len() != 4 {
+        return Err(RedisError::new(
+            RedisErrorKind::ParseError,
+            format!("Couldn't parse a XPENDING summary response: {:?}", values)));
+    }
+
+    let count: u64 = from_redis_value(&values[0])?;
+    let min_id: Option<String> = from_redis_value(&values[1])?;
+    let max_id: Option<String> = from_redis_value(&values[2])?;
+    let consumers: Option<Vec<(String, u64)>> = from_redis_value(&values[3])?;
+
+    Ok(PendingSummary {
+        count,
+        min_id: min_id.map(EntryId::from_string).transpose()?,
+        max_id: max_id.map(EntryId::from_string).transpose()?,
+        consumers: consumers.unwrap_or_default(),
+    })
+}
+
+/// Parse XPENDING result in the extended form: [[id, consumer, idle, delivery count], ...].
+pub(crate) fn parse_pending_info(value: RedisValue) -> RedisResult<Vec<PendingEntryInfo>> {
+    const ENTRY_INFO_LEN: usize = 4;
+
+    let entries: Vec<Vec<RedisValue>> = from_redis_value(&value)?;
+    let mut result = Vec::with_capacity(entries.len());
+    for entry in entries.into_iter() {
+        if entry.len() != ENTRY_INFO_LEN {
+            return Err(RedisError::new(
+                RedisErrorKind::ParseError,
+                format!("Couldn't parse a XPENDING entry: {:?}", entry)));
+        }
+
+        result.push(PendingEntryInfo {
+            id: EntryId::from_string(from_redis_value(&entry[0])?)?,
+            consumer: from_redis_value(&entry[1])?,
+            idle_ms: from_redis_value(&entry[2])?,
+            delivery_count: from_redis_value(&entry[3])?,
+        });
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(data: &str) -> RedisValue {
+        RedisValue::BulkString(data.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_pending_summary() {
+        let value = RedisValue::Array(vec![
+            RedisValue::Int(3),
+            bulk("1-0"),
+            bulk("3-0"),
+            RedisValue::Array(vec![
+                RedisValue::Array(vec![bulk("Alice"), bulk("2")]),
+                RedisValue::Array(vec![bulk("Bob"), bulk("1")]),
+            ]),
+        ]);
+        let expected = PendingSummary {
+            count: 3,
+            min_id: Some(EntryId::new(1, 0)),
+            max_id: Some(EntryId::new(3, 0)),
+            consumers: vec![("Alice".to_string(), 2), ("Bob".to_string(), 1)],
+        };
+        assert_eq!(expected, parse_pending_summary(value).unwrap());
+
+        let empty = RedisValue::Array(
+            vec![RedisValue::Int(0), RedisValue::Nil, RedisValue::Nil, RedisValue::Nil]);
+        let expected = PendingSummary { count: 0, min_id: None, max_id: None, consumers: Vec::new() };
+        assert_eq!(expected, parse_pending_summary(empty).unwrap());
+
+        assert!(parse_pending_summary(RedisValue::Array(vec![RedisValue::Int(0)])).is_err());
+    }
+
+    #[test]
+    fn test_parse_pending_info() {
+        let value = RedisValue::Array(vec![
+            RedisValue::Array(vec![bulk("1-0"), bulk("Alice"), RedisValue::Int(500), RedisValue::Int(2)]),
+        ]);
+        let expected = vec![PendingEntryInfo {
+            id: EntryId::new(1, 0),
+            consumer: "Alice".to_string(),
+            idle_ms: 500,
+            delivery_count: 2,
+        }];
+        assert_eq!(expected, parse_pending_info(value).unwrap());
+
+        let invalid = RedisValue::Array(vec![RedisValue::Array(vec![bulk("1-0"), bulk("Alice")])]);
+        assert!(parse_pending_info(invalid).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use super::{SubscribeOptions, StreamEntry, EntryId};
+use crate::{RedisResult, RedisError, RedisErrorKind};
+
+
+/// Set of options that are required by `RedisStream::process()`
+///
+/// # Example
+/// ```
+/// use redis_asio::stream::{ProcessOptions, SubscribeOptions, RedisGroup};
+///
+/// // handle at most 10 entries of "mystream" at the same time
+/// let group = RedisGroup::new("mygroup".to_string(), "Bob".to_string());
+/// let subscribe_options = SubscribeOptions::with_group(vec!["mystream".to_string()], group)
+///     .with_count(10);
+/// let options = ProcessOptions::new(subscribe_options, 10).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct ProcessOptions {
+    /// Options of the XREADGROUP requests
+    pub(crate) subscribe: SubscribeOptions,
+    /// Group the entries are acknowledged within
+    pub(crate) group: String,
+    /// Max count of the handlers that are run at the same time
+    pub(crate) concurrency: usize,
+}
+
+/// Structure that wraps a result of processing of one received batch by `RedisStream::process()`.
+/// The batch is empty if the BLOCK timeout is expired and there are no new entries.
+#[derive(Debug)]
+pub struct ProcessedBatch<E> {
+    /// The entries that have been handled successfully and acknowledged
+    pub acked: Vec<StreamEntry>,
+    /// The entries that have failed with the errors,
+    /// they are left pending to be delivered again or claimed by another consumer
+    pub failed: Vec<(StreamEntry, E)>,
+}
+
+impl ProcessOptions {
+    pub fn new(subscribe: SubscribeOptions, concurrency: usize) -> RedisResult<ProcessOptions> {
+        let group = match &subscribe.group {
+            Some(group) => group.group.clone(),
+            _ => return Err(
+                RedisError::new(RedisErrorKind::InvalidOptions,
+                                "Entries can be acknowledged within a group only".to_string())),
+        };
+
+        if subscribe.no_ack {
+            return Err(
+                RedisError::new(RedisErrorKind::InvalidOptions,
+                                "Entries received with NOACK cannot be left pending".to_string()));
+        }
+
+        if concurrency == 0 {
+            return Err(
+                RedisError::new(RedisErrorKind::InvalidOptions,
+                                "Concurrency should be greater than 0".to_string()));
+        }
+
+        Ok(ProcessOptions { subscribe, group, concurrency })
+    }
+}
+
+impl<E> ProcessedBatch<E> {
+    /// Split the handled entries into the successful and the failed ones.
+    pub(crate) fn new(results: Vec<(StreamEntry, Result<(), E>)>) -> ProcessedBatch<E> {
+        let mut acked = Vec::new();
+        let mut failed = Vec::new();
+        for (entry, result) in results.into_iter() {
+            match result {
+                Ok(()) => acked.push(entry),
+                Err(err) => failed.push((entry, err)),
+            }
+        }
+        ProcessedBatch { acked, failed }
+    }
+
+    /// Group IDs of the acked entries by the streams, order of the entries is kept.
+    pub(crate) fn acked_ids(&self) -> Vec<(String, Vec<EntryId>)> {
+        let mut streams: Vec<(String, Vec<EntryId>)> = Vec::new();
+        for entry in self.acked.iter() {
+            let id = entry.id;
+            match streams.iter_mut().find(|(stream, _)| *stream == entry.stream) {
+                Some((_, ids)) => ids.push(id),
+                _ => streams.push((entry.stream.clone(), vec![id])),
+            }
+        }
+        streams
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RedisValue;
+    use crate::stream::{RedisGroup, TouchGroupOptions, PendingSummaryOptions};
+    use crate::stream::fixture::Fixture;
+
+    #[test]
+    fn test_process_options() {
+        let group = RedisGroup::new("mygroup".to_string(), "Bob".to_string());
+        let with_group = SubscribeOptions::with_group(vec!["mystream".to_string()], group);
+        assert!(ProcessOptions::new(with_group.clone(), 1).is_ok());
+        assert!(ProcessOptions::new(with_group.clone(), 0).is_err());
+        assert!(ProcessOptions::new(with_group.with_no_ack(), 1).is_err());
+        assert!(ProcessOptions::new(SubscribeOptions::new(vec!["mystream".to_string()]), 1).is_err());
+    }
+
+    #[test]
+    fn test_process() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let group = "mygroup".to_string();
+
+        let touch = fixture.connect().touch_group(TouchGroupOptions::new(stream.clone(), group.clone()));
+        fixture.block_on(touch).unwrap();
+        let ids = fixture.populate(&stream, 4);
+
+        let subscribe_options = SubscribeOptions::with_group(
+            vec![stream.clone()], RedisGroup::new(group.clone(), "Alice".to_string()));
+        let options = ProcessOptions::new(subscribe_options, 2).unwrap();
+        // the entries with odd values fail
+        let process = fixture.connect().process(options, |entry: StreamEntry| {
+            match entry.values["key"] {
+                RedisValue::BulkString(ref value) if value == b"1" || value == b"3" =>
+                    Err(format!("{:?} has failed", entry.id)),
+                _ => Ok(()),
+            }
+        });
+
+        let (batch, _) = fixture.next(process);
+        let mut acked: Vec<_> = batch.acked.into_iter().map(|entry| entry.id).collect();
+        acked.sort_by(|left, right| left.partial_cmp(right).unwrap());
+        assert_eq!(vec![ids[0], ids[2]], acked);
+        assert_eq!(2, batch.failed.len());
+
+        // the failed entries are left pending only
+        let summary_options = PendingSummaryOptions::new(stream.clone(), group.clone());
+        let summary = fixture.connect().pending_summary(summary_options);
+        let (_, summary) = fixture.block_on(summary).unwrap();
+        assert_eq!(2, summary.count);
+        assert_eq!(Some(ids[1]), summary.min_id);
+        assert_eq!(Some(ids[3]), summary.max_id);
+    }
+}
@@ -1,42 +1,416 @@
-use super::EntryId;
-use crate::{RedisCommand, IntoRedisArgument, command};
-use std::collections::HashMap;
+use super::{EntryId, StreamId};
+use crate::{RedisCommand, RedisResult, RedisError, RedisErrorKind, IntoRedisArgument, command};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 
 /// Set of options that are required by `RedisStream::send_entry()`
+/// and `RedisStream::send_entry_if_exists()`.
 #[derive(Clone)]
 pub struct SendEntryOptions {
     /// Stream name
     pub(crate) stream: String,
-    /// Optional explicit entry id
-    pub(crate) entry_id: Option<EntryId>,
+    /// Entry id: "*", "<ms>-*" or an explicit one
+    pub(crate) entry_id: StreamId,
+    /// Optional trimming of the stream on the entry adding
+    pub(crate) trim: Option<TrimOptions>,
+    /// NOMKSTREAM - do not make the stream if there is no such one,
+    /// it is set by `RedisStream::send_entry_if_exists()`
+    pub(crate) no_make_stream: bool,
+}
+
+/// Strategy of the stream trimming.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TrimStrategy {
+    /// MAXLEN - evict the oldest entries while the stream length exceeds the value
+    MaxLen(u64),
+    /// MINID - evict the entries with IDs lower than the value
+    MinId(EntryId),
+}
+
+/// Set of options of the stream trimming that are used by `RedisStream::trim()`
+/// and `SendEntryOptions::with_trim()`.
+///
+/// # Example
+/// ```
+/// use redis_asio::stream::{SendEntryOptions, TrimOptions};
+///
+/// // "XADD mystream MAXLEN ~ 1000 LIMIT 100 * ..."
+/// let options = SendEntryOptions::new("mystream".to_string())
+///     .with_trim(TrimOptions::max_len(1000).with_limit(100));
+/// ```
+#[derive(Clone)]
+pub struct TrimOptions {
+    pub(crate) strategy: TrimStrategy,
+    /// "~" - Redis evicts whole macro nodes only, it is much more efficient than the exact trimming
+    pub(crate) approximate: bool,
+    /// LIMIT - max count of entries evicted by the approximate trimming
+    pub(crate) limit: Option<u64>,
 }
 
 impl SendEntryOptions {
     pub fn new(stream: String) -> SendEntryOptions {
-        let entry_id: Option<EntryId> = None;
-        SendEntryOptions { stream, entry_id }
+        let entry_id = StreamId::Auto;
+        SendEntryOptions { stream, entry_id, trim: None, no_make_stream: false }
     }
 
     pub fn with_id(stream: String, entry_id: EntryId) -> SendEntryOptions {
-        let entry_id = Some(entry_id);
-        SendEntryOptions { stream, entry_id }
+        let entry_id = StreamId::Explicit(entry_id);
+        SendEntryOptions { stream, entry_id, trim: None, no_make_stream: false }
+    }
+
+    /// Send the entry with the "<ms>-*" partial ID,
+    /// so the server picks the sequence number for the `ms` timestamp (Redis 7.0+).
+    pub fn with_partial_id(stream: String, ms: u64) -> SendEntryOptions {
+        let entry_id = StreamId::AutoSeq(ms);
+        SendEntryOptions { stream, entry_id, trim: None, no_make_stream: false }
+    }
+
+    /// Trim the stream on the entry adding.
+    pub fn with_trim(mut self, trim: TrimOptions) -> SendEntryOptions {
+        self.trim = Some(trim);
+        self
+    }
+}
+
+/// Generator of strictly increasing entry IDs that are based on a clock,
+/// e.g. for idempotent producers that should know the entry ID before it is sent.
+/// Each ID is "<clock ms>-0" unless the clock has not moved forward since the previous ID,
+/// in this case the next ID after the previous one is generated.
+///
+/// The generator may be cloned, the clones share the last generated ID.
+///
+/// # Example
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use redis_asio::stream::{IdGenerator, EntryId};
+///
+/// let generator = IdGenerator::with_clock(|| UNIX_EPOCH + Duration::from_millis(1000));
+/// assert_eq!(EntryId::new(1000, 0), generator.next_id().unwrap());
+/// assert_eq!(EntryId::new(1000, 1), generator.next_id().unwrap());
+/// ```
+#[derive(Clone)]
+pub struct IdGenerator {
+    clock: Arc<dyn Fn() -> SystemTime + Send + Sync>,
+    last: Arc<Mutex<Option<EntryId>>>,
+}
+
+/// Set of options that are required by `RedisStream::send_generated_entry()`
+/// and `RedisStream::send_generated_entry_if_exists()`.
+#[derive(Clone)]
+pub struct GeneratedIdOptions {
+    /// Options of the entry, the ID is generated by the `generator`
+    pub(crate) entry: SendEntryOptions,
+    pub(crate) generator: IdGenerator,
+    /// Max count of XADD attempts, the next attempt is made if the generated ID
+    /// is equal or smaller than the stream top entry ID (e.g. it was added by another producer)
+    pub(crate) max_attempts: u32,
+}
+
+impl IdGenerator {
+    /// Make the generator that is based on the system clock.
+    pub fn new() -> IdGenerator {
+        IdGenerator::with_clock(SystemTime::now)
+    }
+
+    /// Make the generator that is based on the `clock`.
+    pub fn with_clock<F>(clock: F) -> IdGenerator
+        where F: Fn() -> SystemTime + Send + Sync + 'static {
+        IdGenerator { clock: Arc::new(clock), last: Arc::new(Mutex::new(None)) }
+    }
+
+    /// Generate the ID that is greater than the previous generated or observed ones.
+    /// Returns an error if there are no IDs greater than the previous one.
+    pub fn next_id(&self) -> RedisResult<EntryId> {
+        // "0-0" is not allowed by XADD
+        const LOWEST_ID: EntryId = EntryId::new(0, 1);
+
+        let mut last = self.last.lock().unwrap();
+        let mut id = EntryId::from_time((self.clock)()).max(LOWEST_ID);
+        if let Some(last) = *last {
+            let next = last.next()
+                .ok_or_else(|| RedisError::new(RedisErrorKind::InternalError,
+                                               format!("There is no entry ID greater than {}", last)))?;
+            id = id.max(next);
+        }
+
+        *last = Some(id);
+        Ok(id)
+    }
+
+    /// Make the next generated IDs greater than the `id`, e.g. the stream top entry ID.
+    pub fn observe(&self, id: EntryId) {
+        let mut last = self.last.lock().unwrap();
+        *last = Some(last.map_or(id, |last| last.max(id)));
+    }
+}
+
+impl Default for IdGenerator {
+    fn default() -> Self {
+        IdGenerator::new()
     }
 }
 
-pub(crate) fn add_command<T>(options: SendEntryOptions, key_values: HashMap<String, T>) -> RedisCommand
-    where T: IntoRedisArgument {
+impl fmt::Debug for IdGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IdGenerator(last={:?})", self.last.lock().unwrap())
+    }
+}
+
+impl GeneratedIdOptions {
+    /// Send the entry with the ID generated by the `generator`,
+    /// the XADD is made at most `max_attempts` times.
+    /// Note the ID of the `entry` options should not be specified.
+    pub fn new(entry: SendEntryOptions, generator: IdGenerator, max_attempts: u32)
+               -> RedisResult<GeneratedIdOptions> {
+        if entry.entry_id != StreamId::Auto {
+            return Err(RedisError::new(
+                RedisErrorKind::InvalidOptions,
+                format!("The entry ID {} is specified while it should be generated", entry.entry_id)));
+        }
+
+        if max_attempts == 0 {
+            return Err(RedisError::new(
+                RedisErrorKind::InvalidOptions,
+                "Expected at least one attempt".to_string()));
+        }
+
+        Ok(GeneratedIdOptions { entry, generator, max_attempts })
+    }
+}
+
+impl TrimOptions {
+    pub fn new(strategy: TrimStrategy) -> TrimOptions {
+        TrimOptions { strategy, approximate: false, limit: None }
+    }
+
+    /// Keep the `max_len` newest entries.
+    pub fn max_len(max_len: u64) -> TrimOptions {
+        TrimOptions::new(TrimStrategy::MaxLen(max_len))
+    }
+
+    /// Evict the entries with IDs lower than `min_id`.
+    pub fn min_id(min_id: EntryId) -> TrimOptions {
+        TrimOptions::new(TrimStrategy::MinId(min_id))
+    }
+
+    /// Trim the stream approximately ("~"), Redis may leave a few more entries than requested.
+    pub fn approximate(mut self) -> TrimOptions {
+        self.approximate = true;
+        self
+    }
+
+    /// Note the LIMIT is applicable to the approximate trimming only,
+    /// so the trimming becomes approximate.
+    pub fn with_limit(mut self, limit: u64) -> TrimOptions {
+        self.approximate = true;
+        self.limit = Some(limit);
+        self
+    }
+}
+
+/// Make XADD command of the field-value pairs that are sent in the iteration order.
+pub(crate) fn add_command<I, K, V>(options: SendEntryOptions, fields: I) -> RedisCommand
+    where I: IntoIterator<Item=(K, V)>,
+          K: IntoRedisArgument,
+          V: IntoRedisArgument {
     let mut cmd = command("XADD").arg(options.stream);
 
-    match options.entry_id {
-        Some(entry_id) => cmd.arg_mut(entry_id.to_string()),
-        _ => cmd.arg_mut("*")
+    if options.no_make_stream {
+        cmd.arg_mut("NOMKSTREAM");
+    }
+
+    if let Some(trim) = options.trim {
+        cmd.append(trim_args(trim));
     }
 
-    for (key, value) in key_values.into_iter() {
+    cmd.arg_mut(options.entry_id.to_string());
+
+    for (key, value) in fields.into_iter() {
         cmd.arg_mut(key);
         cmd.arg_mut(value);
     }
 
     cmd
 }
+
+pub(crate) fn trim_command(stream: String, options: TrimOptions) -> RedisCommand {
+    let mut cmd = command("XTRIM").arg(stream);
+    cmd.append(trim_args(options));
+    cmd
+}
+
+fn trim_args(options: TrimOptions) -> RedisCommand {
+    let mut cmd = match options.strategy {
+        TrimStrategy::MaxLen(_) => command("MAXLEN"),
+        TrimStrategy::MinId(_) => command("MINID"),
+    };
+
+    if options.approximate {
+        cmd.arg_mut("~");
+    }
+
+    match options.strategy {
+        TrimStrategy::MaxLen(max_len) => cmd.arg_mut(max_len),
+        TrimStrategy::MinId(min_id) => cmd.arg_mut(min_id.to_string()),
+    }
+
+    if let Some(limit) = options.limit {
+        cmd.append(command("LIMIT").arg(limit));
+    }
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RedisErrorKind, RedisValue};
+    use crate::stream::{RangeOptions, RangeType};
+    use crate::stream::fixture::Fixture;
+    use std::collections::HashMap;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_trim() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let values = || {
+            let mut values = HashMap::new();
+            values.insert("key".to_string(), "value".to_string());
+            values
+        };
+
+        // there is no such stream, so the entry is not added
+        let send = fixture.connect().send_entry_if_exists(SendEntryOptions::new(stream.clone()), values());
+        let (mut connection, id) = fixture.block_on(send).unwrap();
+        assert_eq!(None, id);
+
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            let send_options = SendEntryOptions::new(stream.clone())
+                .with_trim(TrimOptions::max_len(2));
+            let (con, id) = fixture.block_on(connection.send_entry(send_options, values())).unwrap();
+            connection = con;
+            ids.push(id);
+        }
+
+        let range_options = RangeOptions::new(stream.clone(), 10, RangeType::Any).unwrap();
+        let (connection, entries) = fixture.block_on(connection.range(range_options)).unwrap();
+        assert_eq!(ids[1..].to_vec(), entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>());
+
+        let (connection, evicted) = fixture.block_on(
+            connection.trim(stream.clone(), TrimOptions::min_id(ids[2]))).unwrap();
+        assert_eq!(1, evicted);
+        let (_, evicted) = fixture.block_on(
+            connection.trim(stream.clone(), TrimOptions::max_len(0).with_limit(10))).unwrap();
+        assert_eq!(1, evicted);
+    }
+
+    #[test]
+    fn test_send_ordered_fields() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let fields = vec![(b"b".to_vec(), "1"), (vec![0xff], "2"), (b"a".to_vec(), "3"), (b"b".to_vec(), "4")];
+
+        let send = fixture.connect().send_entry(SendEntryOptions::new(stream.clone()), fields.clone());
+        let (connection, id) = fixture.block_on(send).unwrap();
+
+        let range_options = RangeOptions::new(stream.clone(), 10, RangeType::Any).unwrap();
+        let (_, entries) = fixture.block_on(connection.range_raw(range_options)).unwrap();
+        assert_eq!(1, entries.len());
+        assert_eq!((stream, id), (entries[0].stream.clone(), entries[0].id));
+        let expected = fields.into_iter()
+            .map(|(name, value)| (name, RedisValue::BulkString(value.as_bytes().to_vec())))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, entries[0].fields);
+    }
+
+    #[test]
+    fn test_send_partial_id() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let values = vec![("key", "value")];
+
+        let send = fixture.connect().send_entry(SendEntryOptions::with_partial_id(stream.clone(), 10), values.clone());
+        let (connection, id) = fixture.block_on(send).unwrap();
+        assert_eq!(EntryId::new(10, 0), id);
+        let send = connection.send_entry(SendEntryOptions::with_partial_id(stream.clone(), 10), values);
+        let (_, id) = fixture.block_on(send).unwrap();
+        assert_eq!(EntryId::new(10, 1), id);
+    }
+
+    #[test]
+    fn test_id_generator() {
+        let time = Arc::new(Mutex::new(1000));
+        let clock_time = time.clone();
+        let generator = IdGenerator::with_clock(
+            move || UNIX_EPOCH + Duration::from_millis(*clock_time.lock().unwrap()));
+
+        assert_eq!(EntryId::new(1000, 0), generator.next_id().unwrap());
+        assert_eq!(EntryId::new(1000, 1), generator.next_id().unwrap());
+        // the clock moved backwards
+        *time.lock().unwrap() = 900;
+        assert_eq!(EntryId::new(1000, 2), generator.next_id().unwrap());
+        *time.lock().unwrap() = 1001;
+        assert_eq!(EntryId::new(1001, 0), generator.next_id().unwrap());
+
+        generator.observe(EntryId::new(2000, 5));
+        assert_eq!(EntryId::new(2000, 6), generator.next_id().unwrap());
+        generator.observe(EntryId::MAX);
+        assert!(generator.next_id().is_err());
+
+        let generator = IdGenerator::with_clock(|| UNIX_EPOCH);
+        assert_eq!(EntryId::new(0, 1), generator.next_id().unwrap());
+    }
+
+    #[test]
+    fn test_send_generated_entry() {
+        let mut fixture = Fixture::start();
+        let stream = "mystream".to_string();
+        let values = vec![("key", "value")];
+
+        // the stream is ahead of the generator
+        let send = fixture.connect().send_entry(SendEntryOptions::with_id(stream.clone(), EntryId::new(5000, 3)),
+                                                values.clone());
+        fixture.block_on(send).unwrap();
+
+        let generator = IdGenerator::with_clock(|| UNIX_EPOCH + Duration::from_millis(1000));
+        let options = GeneratedIdOptions::new(SendEntryOptions::new(stream.clone()), generator.clone(), 2).unwrap();
+        let send = fixture.connect().send_generated_entry(options.clone(), values.clone());
+        let (_, id) = fixture.block_on(send).unwrap();
+        assert_eq!(EntryId::new(5000, 4), id);
+
+        // the single attempt is not enough if the stream is ahead
+        let generator = IdGenerator::with_clock(|| UNIX_EPOCH + Duration::from_millis(1000));
+        let options = GeneratedIdOptions::new(SendEntryOptions::new(stream.clone()), generator, 1).unwrap();
+        let send = fixture.connect().send_generated_entry(options, values.clone());
+        assert_eq!(RedisErrorKind::ReceiveError, fixture.block_on(send).err().unwrap().error);
+
+        // there is no such stream, the connection is given back
+        let generator = IdGenerator::with_clock(|| UNIX_EPOCH + Duration::from_millis(1000));
+        let options = GeneratedIdOptions::new(SendEntryOptions::new("nostream".to_string()), generator.clone(), 1).unwrap();
+        let send = fixture.connect().send_generated_entry_if_exists(options, values.clone());
+        let (connection, id) = fixture.block_on(send).unwrap();
+        assert_eq!(None, id);
+        let options = GeneratedIdOptions::new(SendEntryOptions::new(stream.clone()), generator, 2).unwrap();
+        let (_, id) = fixture.block_on(connection.send_generated_entry_if_exists(options, values.clone())).unwrap();
+        assert_eq!(Some(EntryId::new(5000, 5)), id);
+
+        // the top entry is deleted, so the generator is moved after the last generated ID of the stream
+        let send = fixture.connect().send_entry(SendEntryOptions::with_id(stream.clone(), EntryId::new(6000, 0)),
+                                                values.clone());
+        let (connection, _) = fixture.block_on(send).unwrap();
+        let (connection, _) = fixture.block_on(connection.delete_entries(stream.clone(), vec![EntryId::new(6000, 0)])).unwrap();
+        let generator = IdGenerator::with_clock(|| UNIX_EPOCH + Duration::from_millis(1000));
+        let options = GeneratedIdOptions::new(SendEntryOptions::new(stream.clone()), generator, 2).unwrap();
+        let (_, id) = fixture.block_on(connection.send_generated_entry(options, values.clone())).unwrap();
+        assert_eq!(EntryId::new(6000, 1), id);
+
+        let options = SendEntryOptions::with_partial_id(stream.clone(), 1000);
+        let error = GeneratedIdOptions::new(options, IdGenerator::new(), 1).err().unwrap();
+        assert_eq!(RedisErrorKind::InvalidOptions, error.error);
+        let error = GeneratedIdOptions::new(SendEntryOptions::new(stream), IdGenerator::new(), 0).err().unwrap();
+        assert_eq!(RedisErrorKind::InvalidOptions, error.error);
+    }
+}
@@ -1,11 +1,12 @@
-use crate::{RedisValue, RedisCoreConnection, RedisError, RedisErrorKind,
-            IntoRedisArgument, from_redis_value};
+use crate::{RedisValue, RedisResult, RedisCoreConnection, RedisError, RedisErrorKind,
+            RedisArgument, IntoRedisArgument, from_redis_value};
 use super::*;
 
-use std::error::Error;
+use std::fmt;
 use std::net::SocketAddr;
-use std::collections::HashMap;
-use futures::{Future, Sink};
+use std::sync::Arc;
+use futures::{Future, IntoFuture, Stream, future, stream};
+use futures::future::{Either, Loop};
 
 
 /// The structure represents a Redis connection that provides interface for
@@ -30,6 +31,8 @@ impl RedisStream {
     }
 
     /// Send an entry that will be constructed by options and pairs of key-values.
+    /// The pairs may be any of the `IntoIterator` types, e.g. `HashMap<String, T>`
+    /// or `Vec<(Vec<u8>, T)>` to send binary field names in the specified order.
     ///
     /// # Example
     ///
@@ -58,11 +61,22 @@ impl RedisStream {
     ///     })
     ///     .map_err(|err| eprintln!("something went wrong: {}", err));
     /// tokio::run(future);
+    ///
+    /// // the field order is kept and the field names may be binary
+    /// let send_options = SendEntryOptions::new("mystream".to_string());
+    /// let fields = vec![(b"\x00header".to_vec(), "v1"), (b"data".to_vec(), "v2")];
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| stream.send_entry(send_options, fields))
+    ///     .map(|_| ())
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
     /// ```
-    pub fn send_entry<T>(self, options: SendEntryOptions, key_values: HashMap<String, T>)
-                         -> impl Future<Item=(RedisStream, EntryId), Error=RedisError> + Send + 'static
-        where T: IntoRedisArgument {
-        self.connection.send(add_command(options, key_values))
+    pub fn send_entry<I, K, V>(self, options: SendEntryOptions, fields: I)
+                               -> impl Future<Item=(RedisStream, EntryId), Error=RedisError> + Send + 'static
+        where I: IntoIterator<Item=(K, V)>,
+              K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        self.connection.send(add_command(options, fields))
             .and_then(|(connection, response)| {
                 let entry_id_string = from_redis_value(&response)?;
                 let entry_id = EntryId::from_string(entry_id_string)?;
@@ -70,6 +84,172 @@ impl RedisStream {
             })
     }
 
+    /// Send an entry into the stream only if the stream exists (XADD NOMKSTREAM).
+    /// The response is the ID of the added entry or None if there is no such stream.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::Future;
+    /// use redis_asio::stream::{RedisStream, SendEntryOptions, EntryId};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let send_options = SendEntryOptions::new("mystream".to_string());
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| stream.send_entry_if_exists(send_options, vec![("key", "value")]))
+    ///     .map(|(_, id): (RedisStream, Option<EntryId>)| match id {
+    ///         Some(id) => println!("{} has sent", id),
+    ///         None => println!("there is no such stream"),
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn send_entry_if_exists<I, K, V>(self, mut options: SendEntryOptions, fields: I)
+                                         -> impl Future<Item=(RedisStream, Option<EntryId>), Error=RedisError> + Send + 'static
+        where I: IntoIterator<Item=(K, V)>,
+              K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        options.no_make_stream = true;
+        self.connection.send(add_command(options, fields))
+            .and_then(|(connection, response)| {
+                // Nil is returned if there is no such stream
+                let entry_id_string: Option<String> = from_redis_value(&response)?;
+                let entry_id = entry_id_string.map(EntryId::from_string).transpose()?;
+                Ok((Self { connection }, entry_id))
+            })
+    }
+
+    /// Send an entry with the ID generated on the client side (see `IdGenerator`).
+    /// If the generated ID is equal or smaller than the stream top item ID,
+    /// the generator is moved after the last ID generated by the stream and the next attempt is made.
+    /// The response is the ID of the added entry.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::Future;
+    /// use redis_asio::stream::{RedisStream, SendEntryOptions, GeneratedIdOptions, IdGenerator, EntryId};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let generator = IdGenerator::new();
+    /// let options = GeneratedIdOptions::new(
+    ///     SendEntryOptions::new("mystream".to_string()), generator.clone(), 3).unwrap();
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| stream.send_generated_entry(options, vec![("key", "value")]))
+    ///     .map(|(_, id): (RedisStream, EntryId)| println!("{} has sent", id))
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn send_generated_entry<I, K, V>(self, options: GeneratedIdOptions, fields: I)
+                                         -> impl Future<Item=(RedisStream, EntryId), Error=RedisError> + Send + 'static
+        where I: IntoIterator<Item=(K, V)>,
+              K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        self.send_generated(options, fields)
+            .and_then(|(stream, id)| {
+                let id = id.ok_or_else(|| RedisError::new(
+                    RedisErrorKind::ParseError, "Unexpected Nil response on XADD".to_string()))?;
+                Ok((stream, id))
+            })
+    }
+
+    /// Send an entry with the ID generated on the client side (see `IdGenerator`)
+    /// only if the stream exists (XADD NOMKSTREAM).
+    /// The response is the ID of the added entry or None if there is no such stream.
+    pub fn send_generated_entry_if_exists<I, K, V>(self, mut options: GeneratedIdOptions, fields: I)
+                                                   -> impl Future<Item=(RedisStream, Option<EntryId>), Error=RedisError> + Send + 'static
+        where I: IntoIterator<Item=(K, V)>,
+              K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        options.entry.no_make_stream = true;
+        self.send_generated(options, fields)
+    }
+
+    fn send_generated<I, K, V>(self, options: GeneratedIdOptions, fields: I)
+                               -> impl Future<Item=(RedisStream, Option<EntryId>), Error=RedisError> + Send + 'static
+        where I: IntoIterator<Item=(K, V)>,
+              K: IntoRedisArgument,
+              V: IntoRedisArgument {
+        // the fields are sent on each attempt
+        let fields: Vec<(RedisArgument, RedisArgument)> = fields.into_iter()
+            .map(|(key, value)| (key.into_redis_argument(), value.into_redis_argument()))
+            .collect();
+        let GeneratedIdOptions { entry, generator, max_attempts } = options;
+
+        future::loop_fn((self, 1), move |(stream, attempt)| {
+            let id = match generator.next_id() {
+                Ok(id) => id,
+                Err(err) => return Either::A(future::err(err)),
+            };
+
+            let mut send_options = entry.clone();
+            send_options.entry_id = StreamId::Explicit(id);
+            let stream_name = entry.stream.clone();
+            let generator = generator.clone();
+
+            let future = stream.connection.try_send(add_command(send_options, fields.clone()))
+                .and_then(move |(connection, response)| {
+                    let err = match response {
+                        // Nil is returned if NOMKSTREAM is specified and there is no such stream
+                        Ok(response) => {
+                            let result = from_redis_value::<Option<String>>(&response)
+                                .and_then(|id| id.map(EntryId::from_string).transpose())
+                                .map(|id| Loop::Break((RedisStream { connection }, id)));
+                            return Either::A(result.into_future());
+                        }
+                        Err(err) => err,
+                    };
+
+                    let is_conflict = err.error == RedisErrorKind::ReceiveError
+                        && err.desc.contains("equal or smaller than the target stream top item");
+                    if !is_conflict || attempt >= max_attempts {
+                        return Either::A(future::err(err));
+                    }
+
+                    // the stream is ahead of the generator, e.g. another producer has added entries.
+                    // The last generated ID is used rather than the top entry,
+                    // because the top entries could be deleted or trimmed
+                    let future = connection.send(info_stream_command(StreamInfoOptions::new(stream_name), false))
+                        .and_then(move |(connection, response)| {
+                            generator.observe(parse_last_generated_id(&response)?);
+                            Ok(Loop::Continue((RedisStream { connection }, attempt + 1)))
+                        });
+                    Either::B(future)
+                });
+            Either::B(future)
+        })
+    }
+
+    /// Trim the stream by the MAXLEN or MINID strategy.
+    /// The response is the number of evicted entries.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::Future;
+    /// use redis_asio::stream::{RedisStream, TrimOptions};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// // "XTRIM mystream MAXLEN ~ 1000"
+    /// let options = TrimOptions::max_len(1000).approximate();
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| stream.trim("mystream".to_string(), options))
+    ///     .map(|(_, evicted): (RedisStream, u64)| {
+    ///         println!("{} entries are evicted", evicted);
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn trim(self, stream: String, options: TrimOptions)
+                -> impl Future<Item=(Self, u64), Error=RedisError> + Send + 'static {
+        self.connection.send(trim_command(stream, options))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, from_redis_value(&response)?)))
+    }
+
     /// Read entries with IDs greater than specified `start_id`.
     ///
     /// # Example
@@ -110,6 +290,19 @@ impl RedisStream {
             )
     }
 
+    /// Same as `read_explicit()` but the entries are received as they are stored in the streams:
+    /// with binary field names, the order and the duplicates of the fields are kept.
+    pub fn read_explicit_raw(self, options: ReadExplicitOptions)
+                             -> impl Future<
+                                 Item=(RedisStream, Vec<RawStreamEntry>),
+                                 Error=RedisError>
+                             + Send + 'static {
+        self.connection.send(read_explicit_cmd(options))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, parse_raw_stream_entries(response)?))
+            )
+    }
+
     /// Get entries in specified range.
     ///
     /// # Example
@@ -146,6 +339,148 @@ impl RedisStream {
             )
     }
 
+    /// Same as `range()` but the entries are received as they are stored in the stream:
+    /// with binary field names, the order and the duplicates of the fields are kept.
+    pub fn range_raw(self, options: RangeOptions)
+                     -> impl Future<
+                         Item=(RedisStream, Vec<RawStreamEntry>),
+                         Error=RedisError>
+                     + Send + 'static {
+        let stream = options.stream.clone();
+        self.connection.send(range_cmd(options))
+            .and_then(move |(connection, response)|
+                Ok((RedisStream { connection }, parse_raw_range_entries(&stream, response)?))
+            )
+    }
+
+    /// Get all of the entries in specified range page by page.
+    /// The `count` of the options is the page size,
+    /// each next page is requested starting after the last received entry
+    /// until the end of the range.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::{Future, Stream};
+    /// use redis_asio::stream::{RedisStream, RangeOptions, RangeType, RangeEntry};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// // request the entries by 100 per page
+    /// let range_options =
+    ///     RangeOptions::new("stream1".to_string(), 100, RangeType::Any).unwrap();
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| {
+    ///         stream.range_iter(range_options)
+    ///             .for_each(|entry: RangeEntry| {
+    ///                 println!("Received: {:?}", entry);
+    ///                 Ok(())
+    ///             })
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn range_iter(self, options: RangeOptions)
+                      -> impl Stream<Item=RangeEntry, Error=RedisError> + Send + 'static {
+        let state = Some((self.connection, options, None));
+        stream::unfold(state, |state| {
+            let (connection, options, last_id) = state?;
+            let future = connection.send(range_page_cmd(options.clone(), last_id))
+                .and_then(move |(connection, response)| {
+                    let page = parse_range_entries(response)?;
+                    // an incomplete page means the end of the range is reached
+                    let state = match page.last() {
+                        Some(last) if page.len() >= options.count as usize && options.count > 0 =>
+                            Some((connection, options, Some(last.id))),
+                        _ => None,
+                    };
+                    Ok((stream::iter_ok(page), state))
+                });
+            Some(future)
+        })
+            .flatten()
+    }
+
+    /// Get entries in specified range in the reverse order,
+    /// i.e. the `count` newest entries of the range starting from the greatest ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::Future;
+    /// use redis_asio::stream::{RedisStream, RangeOptions, RangeType, RangeEntry};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// // get the last 10 entries
+    /// let range_options =
+    ///     RangeOptions::new("stream1".to_string(), 10, RangeType::Any).unwrap();
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| {
+    ///         stream.rev_range(range_options)
+    ///     })
+    ///     .map(|(_, entries): (RedisStream, Vec<RangeEntry>)| {
+    ///         for entry in entries.into_iter() {
+    ///             println!("Received: {:?}", entry);
+    ///         }
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn rev_range(self, options: RangeOptions)
+                     -> impl Future<
+                         Item=(RedisStream, Vec<RangeEntry>),
+                         Error=RedisError>
+                     + Send + 'static {
+        self.connection.send(rev_range_cmd(options))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, parse_range_entries(response)?))
+            )
+    }
+
+    /// Delete entries from the stream by their IDs.
+    /// The response is the number of deleted entries,
+    /// the IDs that do not exist in the stream are ignored.
+    ///
+    /// Note the deleted entries are kept within the pending entries lists of the groups.
+    pub fn delete_entries(self, stream: String, ids: Vec<EntryId>)
+                          -> impl Future<Item=(Self, u64), Error=RedisError> + Send + 'static {
+        // XDEL requires at least one ID
+        if ids.is_empty() {
+            return Either::A(future::ok((self, 0)));
+        }
+
+        Either::B(self.connection.send(delete_entries_command(stream, ids))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, from_redis_value(&response)?))))
+    }
+
+    /// Delete entries from the stream by their IDs with the `policy`
+    /// of the consumer group references (XDELEX, Redis 8.2 and later).
+    /// The response is the result of each of the entries in the order of the IDs.
+    pub fn delete_entries_ex(self, stream: String, ids: Vec<EntryId>, policy: DeletePolicy)
+                             -> impl Future<Item=(Self, Vec<DeleteResponse>), Error=RedisError> + Send + 'static {
+        // XDELEX requires at least one ID
+        if ids.is_empty() {
+            return Either::A(future::ok((self, Vec::new())));
+        }
+
+        Either::B(self.connection.send(delete_entries_ex_command(stream, ids, policy))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, parse_delete_responses(&response)?))))
+    }
+
+    /// Get the number of entries within the stream,
+    /// the response is 0 if there is no such stream.
+    pub fn len(self, stream: String)
+               -> impl Future<Item=(Self, u64), Error=RedisError> + Send + 'static {
+        self.connection.send(len_command(stream))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, from_redis_value(&response)?)))
+    }
+
     /// Subscribe to a Redis stream and process all incoming entries.
     /// Redis Streams requires to send XREAD/XREADGROUP requests every time
     /// the client receives a response on the previous,
@@ -155,8 +490,15 @@ impl RedisStream {
     /// In the Crate the subscription is possible by hidden requests sending
     /// within the Crate engine.
     ///
+    /// Each next request is sent once the previous entries are taken from the `Subscribe`
+    /// (and the prefetch window has free space, see `SubscribeOptions::with_prefetch()`),
+    /// and continues from the last entry received from each stream,
+    /// so no entries are missed between two requests.
+    /// Note the "$" start of a stream is resolved to the ID of the stream last entry
+    /// before the subscription.
+    ///
     /// Request that will be sent to get new entries in the following example:
-    /// "XREADGROUP GROUP mygroup Bob BLOCK 0 STREAMS mystream <"
+    /// "XREADGROUP GROUP mygroup Bob BLOCK 0 STREAMS mystream >"
     ///
     /// # Example
     ///
@@ -189,19 +531,98 @@ impl RedisStream {
     pub fn subscribe(self, options: SubscribeOptions)
                      -> impl Future<Item=Subscribe, Error=RedisError> + Send + 'static {
         let RedisCoreConnection { sender, receiver } = self.connection;
+        // run recursive server message processing,
+        // the first request is sent once the "$" starts are resolved
+        subscribe(receiver, sender, options).start()
+    }
 
-        // send first subscription request
-        sender
-            .send(subscribe_cmd(options.clone()))
-            .map(move |sender| {
-                // run recursive server message processing
-                Subscribe {
-                    stream: Box::new(subscribe(receiver, sender, options))
-                }
-            })
+    /// Receive entries within a group and handle each of them by the `handler`
+    /// with at most `concurrency` handlers run at the same time (at-least-once processing).
+    /// The successfully handled entries of a batch are acknowledged by one XACK per stream
+    /// on the same connection before the next batch is requested,
+    /// the failed entries are left pending.
+    /// The response is the stream of the processed batches.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::{Future, Stream};
+    /// use redis_asio::stream::{RedisStream, SubscribeOptions, StreamEntry, RedisGroup,
+    ///                          ProcessOptions, ProcessedBatch};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let group = RedisGroup::new("mygroup".to_string(), "Bob".to_string());
+    /// let subscribe_options = SubscribeOptions::with_group(vec!["mystream".to_string()], group);
+    /// let options = ProcessOptions::new(subscribe_options, 10).unwrap();
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| {
+    ///         stream.process(options, |entry: StreamEntry| {
+    ///             println!("Received: {:?}", entry);
+    ///             Ok::<(), String>(())
+    ///         })
+    ///             .for_each(|batch: ProcessedBatch<String>| {
+    ///                 for (entry, err) in batch.failed.iter() {
+    ///                     eprintln!("{:?} has failed: {}", entry.id, err);
+    ///                 }
+    ///                 Ok(())
+    ///             })
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn process<F, R, E>(self, options: ProcessOptions, handler: F)
+                            -> impl Stream<Item=ProcessedBatch<E>, Error=RedisError> + Send + 'static
+        where F: Fn(StreamEntry) -> R + Send + Sync + 'static,
+              R: IntoFuture<Item=(), Error=E>,
+              R::Future: Send + 'static,
+              E: Send + 'static {
+        let ProcessOptions { subscribe, group, concurrency } = options;
+        let handler = Arc::new(handler);
+        let state = Some((self, subscribe));
+
+        stream::unfold(state, move |state| {
+            let (stream, options) = state?;
+            let handler = handler.clone();
+            let group = group.clone();
+
+            let requested = options.next_request(None, 0);
+            let future = stream.connection.send(subscribe_cmd(requested.clone()))
+                .and_then(|(connection, response)| {
+                    let entries = parse_raw_subscribe_entries(response)?;
+                    let last_ids = last_ids(&entries);
+                    let entries = entries.into_iter()
+                        .map(RawStreamEntry::into_entry)
+                        .collect::<RedisResult<Vec<StreamEntry>>>()?;
+                    Ok((connection, entries, last_ids))
+                })
+                .and_then(move |(connection, entries, last_ids)| {
+                    let options = options.advance(&requested, &last_ids);
+                    stream::iter_ok(entries)
+                        .map(move |entry| {
+                            handler(entry.clone()).into_future()
+                                .then(move |result| Ok((entry, result)))
+                        })
+                        .buffer_unordered(concurrency)
+                        .collect()
+                        .map(move |results| (connection, options, ProcessedBatch::new(results)))
+                })
+                .and_then(move |(connection, options, batch)| {
+                    stream::iter_ok(batch.acked_ids())
+                        .fold(connection, move |connection, (stream, ids)| {
+                            connection.send(ack_entries_command(stream, group.clone(), ids))
+                                .map(|(connection, _)| connection)
+                        })
+                        .map(move |connection| (batch, Some((RedisStream { connection }, options))))
+                });
+            Some(future)
+        })
     }
 
     /// Acknowledge an entry by its ID.
+    /// The options should contain exactly one entry ID,
+    /// use `RedisStream::ack_entries()` to acknowledge several entries.
     ///
     /// # Example
     /// ```rust,no_run
@@ -228,17 +649,79 @@ impl RedisStream {
     /// ```
     pub fn ack_entry(self, options: AckOptions)
                      -> impl Future<Item=(Self, AckResponse), Error=RedisError> + Send + 'static {
-        self.connection.send(ack_entry_command(options))
+        // a count of the acknowledged entries could not be expressed by AckResponse
+        if options.entry_ids.len() != 1 {
+            return Either::A(future::err(RedisError::new(
+                RedisErrorKind::InvalidOptions,
+                format!("Expected exactly one entry ID to acknowledge, got {}", options.entry_ids.len()))));
+        }
+
+        Either::B(self.connection.send(ack_entry_command(options))
             .and_then(|(connection, response)| {
                 let response = match response {
                     RedisValue::Int(x) => AckResponse::new(x),
                     _ => return Err(RedisError::new(RedisErrorKind::ParseError, "Expect integer reply on XACK request".to_string())),
                 };
                 Ok((RedisStream { connection }, response))
-            })
+            }))
+    }
+
+    /// Acknowledge the entries of one stream within one group by a single XACK request.
+    /// The response is the number of acknowledged entries,
+    /// the entries that are not pending are ignored.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::Future;
+    /// use redis_asio::stream::{RedisStream, AckOptions, EntryId};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let ack_options =
+    ///     AckOptions::with_entries("mystream".to_string(),
+    ///                              "mygroup".to_string(),
+    ///                              vec![EntryId::new(1, 0), EntryId::new(2, 0)]);
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| {
+    ///         stream.ack_entries(ack_options)
+    ///     })
+    ///     .map(|(_, acked): (RedisStream, u64)| {
+    ///         println!("{} entries are acknowledged", acked);
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn ack_entries(self, options: AckOptions)
+                       -> impl Future<Item=(Self, u64), Error=RedisError> + Send + 'static {
+        // XACK requires at least one ID
+        if options.entry_ids.is_empty() {
+            return Either::A(future::ok((self, 0)));
+        }
+
+        Either::B(self.connection.send(ack_entry_command(options))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, from_redis_value(&response)?))))
+    }
+
+    /// Acknowledge the entries and delete them from the stream with the `policy`
+    /// of the other consumer group references (XACKDEL, Redis 8.2 and later).
+    /// The response is the result of each of the entries in the order of the IDs.
+    pub fn ack_delete_entries(self, options: AckOptions, policy: DeletePolicy)
+                              -> impl Future<Item=(Self, Vec<DeleteResponse>), Error=RedisError> + Send + 'static {
+        // XACKDEL requires at least one ID
+        if options.entry_ids.is_empty() {
+            return Either::A(future::ok((self, Vec::new())));
+        }
+
+        Either::B(self.connection.send(ack_delete_command(options, policy))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, parse_delete_responses(&response)?))))
     }
 
     /// Get entries that was not acknowledged but was sent to specified consumer.
+    /// Note the entries are re-delivered by XREADGROUP with an explicit ID,
+    /// use `RedisStream::pending_info()` to inspect the pending entries without delivery.
     ///
     /// # Example
     /// ```rust,no_run
@@ -274,6 +757,322 @@ impl RedisStream {
             })
     }
 
+    /// Get the summary of the pending entries of the group (XPENDING):
+    /// count of the entries, the smallest and the greatest IDs
+    /// and count of the entries per consumer.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::Future;
+    /// use redis_asio::stream::{RedisStream, PendingSummaryOptions, PendingSummary};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let options = PendingSummaryOptions::new("mystream".to_string(), "mygroup".to_string());
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| stream.pending_summary(options))
+    ///     .map(|(_, summary): (RedisStream, PendingSummary)| {
+    ///         for (consumer, count) in summary.consumers.iter() {
+    ///             println!("{} has {} pending entries", consumer, count);
+    ///         }
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn pending_summary(self, options: PendingSummaryOptions)
+                           -> impl Future<Item=(Self, PendingSummary), Error=RedisError> + Send + 'static {
+        self.connection.send(pending_summary_command(options))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, parse_pending_summary(response)?)))
+    }
+
+    /// Get the pending entries of the group with their owners, idle times and
+    /// delivery counts (XPENDING in the extended form).
+    /// Unlike `RedisStream::pending_entries()` the entries are not re-delivered.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use std::time::Duration;
+    /// use futures::Future;
+    /// use redis_asio::stream::{RedisStream, PendingInfoOptions, PendingEntryInfo};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let options = PendingInfoOptions::new("mystream".to_string(), "mygroup".to_string(), 10)
+    ///     .with_min_idle_time(Duration::from_secs(60));
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| stream.pending_info(options))
+    ///     .map(|(_, entries): (RedisStream, Vec<PendingEntryInfo>)| {
+    ///         for entry in entries.into_iter() {
+    ///             println!("{:?} is delivered {} times", entry.id, entry.delivery_count);
+    ///         }
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn pending_info(self, options: PendingInfoOptions)
+                        -> impl Future<Item=(Self, Vec<PendingEntryInfo>), Error=RedisError> + Send + 'static {
+        self.connection.send(pending_info_command(options))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, parse_pending_info(response)?)))
+    }
+
+    /// Change the ownership of the pending entries to the specified consumer
+    /// if they are idle for at least `min_idle_time`.
+    /// It allows to take over the entries left behind by a dead consumer.
+    ///
+    /// The IDs of the entries that were deleted from the stream are returned separately,
+    /// Redis removes them from the pending entries list.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use std::time::Duration;
+    /// use futures::Future;
+    /// use redis_asio::stream::{RedisStream, ClaimOptions, ClaimedEntries, EntryId};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let claim_options = ClaimOptions::new("mystream".to_string(),
+    ///                                       "mygroup".to_string(),
+    ///                                       "Alice".to_string(),
+    ///                                       Duration::from_secs(60),
+    ///                                       vec![EntryId::new(1, 0)]);
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| stream.claim(claim_options))
+    ///     .map(|(_, claimed): (RedisStream, ClaimedEntries)| {
+    ///         for entry in claimed.entries.into_iter() {
+    ///             println!("Claimed: {:?}", entry);
+    ///         }
+    ///         println!("Deleted: {:?}", claimed.deleted);
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn claim(self, options: ClaimOptions)
+                 -> impl Future<Item=(Self, ClaimedEntries), Error=RedisError> + Send + 'static {
+        let stream = options.stream.clone();
+        self.connection.send(claim_command(options, false))
+            .and_then(move |(connection, response)| {
+                let (entries, deleted) = parse_claimed_entries(&stream, &response)?;
+                Ok((RedisStream { connection }, ClaimedEntries { entries, deleted }))
+            })
+    }
+
+    /// The same as `RedisStream::claim()` but returns IDs of the claimed entries only (JUSTID).
+    /// Note Redis does not increment the delivery count of the entries in this case.
+    pub fn claim_ids(self, options: ClaimOptions)
+                     -> impl Future<Item=(Self, Vec<EntryId>), Error=RedisError> + Send + 'static {
+        self.connection.send(claim_command(options, true))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, parse_claimed_ids(response)?)))
+    }
+
+    /// Scan a part of the pending entries list and claim the entries that are idle
+    /// for at least `min_idle_time` (XAUTOCLAIM).
+    /// The response contains the cursor the scanning is continued from by the next call,
+    /// it is None once the whole list has been scanned.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use std::time::Duration;
+    /// use futures::Future;
+    /// use futures::future::{self, Loop};
+    /// use redis_asio::stream::{RedisStream, AutoClaimOptions, AutoClaimedEntries};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let claim_options = AutoClaimOptions::new("mystream".to_string(),
+    ///                                           "mygroup".to_string(),
+    ///                                           "Alice".to_string(),
+    ///                                           Duration::from_secs(60))
+    ///     .with_count(10);
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| {
+    ///         future::loop_fn((stream, claim_options), |(stream, options)| {
+    ///             stream.auto_claim(options.clone())
+    ///                 .map(move |(stream, page): (RedisStream, AutoClaimedEntries)| {
+    ///                     let claimed = page.claimed;
+    ///                     println!("Claimed: {:?}, deleted: {:?}", claimed.entries, claimed.deleted);
+    ///                     match page.next {
+    ///                         Some(next) => Loop::Continue((stream, options.with_start(next))),
+    ///                         _ => Loop::Break(stream),
+    ///                     }
+    ///                 })
+    ///         })
+    ///     })
+    ///     .map(|_| ())
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn auto_claim(self, options: AutoClaimOptions)
+                      -> impl Future<Item=(Self, AutoClaimedEntries), Error=RedisError> + Send + 'static {
+        let stream = options.stream.clone();
+        self.connection.send(auto_claim_command(options))
+            .and_then(move |(connection, response)|
+                Ok((RedisStream { connection }, parse_auto_claim(&stream, response)?)))
+    }
+
+    /// Complete the processing of an entry received within a group:
+    /// acknowledge the entry if the `result` is Ok,
+    /// otherwise (the error is the reason of the failure) leave the entry pending to be delivered again
+    /// or move it into the dead-letter stream if the entry has been delivered
+    /// more than `max_deliveries` times.
+    ///
+    /// The dead-letter entry contains the original fields and the metadata:
+    /// the original stream, the entry ID, the consumer and the error reason.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::{Future, Stream, stream};
+    /// use redis_asio::RedisError;
+    /// use redis_asio::stream::{RedisStream, SubscribeOptions, RedisGroup, StreamEntry,
+    ///                          DeadLetterOptions};
+    ///
+    /// fn process(entry: &StreamEntry) -> Result<(), std::io::Error> {
+    ///     println!("Received: {:?}", entry);
+    ///     Ok(())
+    /// }
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let options = DeadLetterOptions::new("mystream".to_string(),
+    ///                                      "mygroup".to_string(),
+    ///                                      "Bob".to_string(),
+    ///                                      "mystream:dead".to_string(),
+    ///                                      5);
+    /// let group = RedisGroup::new("mygroup".to_string(), "Bob".to_string());
+    /// let subscribe_options = SubscribeOptions::with_group(vec!["mystream".to_string()], group);
+    ///
+    /// // the first connection receives the entries, the second settles them
+    /// let future = RedisStream::connect(address)
+    ///     .join(RedisStream::connect(address))
+    ///     .and_then(move |(consumer, manager)| {
+    ///         consumer.subscribe(subscribe_options)
+    ///             .and_then(move |subscribe| subscribe.fold(manager, move |manager, entries| {
+    ///                 let options = options.clone();
+    ///                 stream::iter_ok::<_, RedisError>(entries).fold(manager, move |manager, entry| {
+    ///                     let result = process(&entry);
+    ///                     manager.settle_entry(options.clone(), entry, result)
+    ///                         .map(|(manager, _)| manager)
+    ///                 })
+    ///             }))
+    ///     })
+    ///     .map(|_| ())
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn settle_entry<E>(self, options: DeadLetterOptions, entry: StreamEntry, result: Result<(), E>)
+                           -> impl Future<Item=(Self, SettleResponse), Error=RedisError> + Send + 'static
+        where E: fmt::Display {
+        let reason = match result {
+            Ok(()) => {
+                let ack_options = AckOptions::new(entry.stream.clone(), options.group, entry.id);
+                return Either::A(self.ack_entry(ack_options)
+                    .map(|(stream, _)| (stream, SettleResponse::Acked)));
+            }
+            Err(err) => err.to_string(),
+        };
+
+        let range = RangeType::GreaterLessThan(entry.id, entry.id);
+        let pending_options = PendingInfoOptions::new(entry.stream.clone(), options.group.clone(), 1)
+            .with_range(range);
+        let future = self.pending_info(pending_options)
+            .and_then(move |(stream, pending)| {
+                let delivery_count = pending.first().map(|info| info.delivery_count).unwrap_or(0);
+                if delivery_count <= options.max_deliveries {
+                    return Either::A(future::ok((stream, SettleResponse::Retry { delivery_count })));
+                }
+
+                let consumer = options.consumer.clone();
+                Either::B(stream.dead_letter_entry(options, entry, consumer, reason)
+                    .map(|(stream, id)| (stream, SettleResponse::DeadLettered(id))))
+            });
+        Either::B(future)
+    }
+
+    /// Move an entry into the dead-letter stream with the metadata:
+    /// the original stream, the entry ID, the `consumer` and the error `reason`,
+    /// then acknowledge the original entry.
+    /// The response is the ID of the dead-letter entry.
+    pub fn dead_letter_entry(self, options: DeadLetterOptions, entry: StreamEntry, consumer: String,
+                             reason: String)
+                             -> impl Future<Item=(Self, EntryId), Error=RedisError> + Send + 'static {
+        let ack_options = AckOptions::new(entry.stream.clone(), options.group, entry.id);
+        let key_values = match dead_letter_values(entry, consumer, reason) {
+            Ok(x) => x,
+            Err(err) => return Either::A(future::err(err)),
+        };
+
+        let send_options = SendEntryOptions::new(options.dead_letter_stream);
+        let future = self.send_entry(send_options, key_values)
+            .and_then(move |(stream, dead_letter_id)|
+                stream.ack_entry(ack_options)
+                    .map(move |(stream, _)| (stream, dead_letter_id)));
+        Either::B(future)
+    }
+
+    /// Move the pending entries that have been delivered more than `max_deliveries` times
+    /// and are idle for at least `min_idle_time` into the dead-letter stream.
+    /// It allows to get rid of the entries that crash their consumers.
+    ///
+    /// The entries are claimed by the consumer before the moving,
+    /// so the entries are not moved twice by the concurrent consumers.
+    /// Up to `count` pending entries are checked per call.
+    /// The response is the IDs of the original entries that have been moved
+    /// and the IDs of the entries that were deleted from the stream and have been acknowledged.
+    pub fn dead_letter_exceeded(self, options: DeadLetterOptions)
+                                -> impl Future<Item=(Self, ExceededEntries), Error=RedisError> + Send + 'static {
+        let pending_options =
+            PendingInfoOptions::new(options.stream.clone(), options.group.clone(), options.count)
+                .with_min_idle_time(options.min_idle_time);
+
+        self.pending_info(pending_options)
+            .and_then(move |(stream, pending)| {
+                let exceeded: Vec<PendingEntryInfo> = pending.into_iter()
+                    .filter(|info| info.delivery_count > options.max_deliveries)
+                    .collect();
+                if exceeded.is_empty() {
+                    return Either::A(future::ok((stream, ExceededEntries::default())));
+                }
+
+                let ids = exceeded.iter().map(|info| info.id).collect();
+                let claim_options = ClaimOptions::new(options.stream.clone(), options.group.clone(),
+                                                      options.consumer.clone(), options.min_idle_time, ids);
+                let group = options.group.clone();
+                let future = stream.claim(claim_options)
+                    // the deleted entries are still pending if Redis < 7.0, there is nothing to move
+                    .and_then(move |(stream, claimed)| {
+                        let ack_options = AckOptions::with_entries(options.stream.clone(), group, claimed.deleted.clone());
+                        stream.ack_entries(ack_options)
+                            .map(move |(stream, _)| (stream, claimed, options))
+                    })
+                    .and_then(move |(stream, claimed, options)| {
+                        let deleted = claimed.deleted;
+                        stream::iter_ok(claimed.entries)
+                            .fold((stream, Vec::new()), move |(stream, mut moved), entry| {
+                                // the entry is claimed, so the previous owner is the failed consumer
+                                let (consumer, reason) = match exceeded.iter().find(|info| info.id == entry.id) {
+                                    Some(info) => (info.consumer.clone(),
+                                                   format!("delivered {} times", info.delivery_count)),
+                                    _ => (options.consumer.clone(), "delivered too many times".to_string()),
+                                };
+                                let id = entry.id;
+                                stream.dead_letter_entry(options.clone(), entry, consumer, reason)
+                                    .map(move |(stream, _)| {
+                                        moved.push(id);
+                                        (stream, moved)
+                                    })
+                            })
+                            .map(move |(stream, moved)| (stream, ExceededEntries { moved, deleted }))
+                    });
+                Either::B(future)
+            })
+    }
+
     /// Try to create a group. If the group exists already, do not return an error.
     ///
     /// # Example
@@ -297,19 +1096,161 @@ impl RedisStream {
     /// ```
     pub fn touch_group(self, options: TouchGroupOptions)
                        -> impl Future<Item=(), Error=RedisError> + Send + 'static {
-        self.connection.send(touch_group_command(options))
-            .then(|res| {
-                match res {
-                    // do not keep the connection in anyway because we could receive BUSYGROUP from server
-                    Ok((_connection, _)) => Ok(()),
-                    Err(err) => {
-                        if err.error == RedisErrorKind::ReceiveError
-                            && err.description().contains("BUSYGROUP") {
-                            return Ok(());
-                        }
-                        Err(err)
-                    }
-                }
+        // make an empty stream if there is no such one yet
+        let options = CreateGroupOptions::new(options.stream, options.group, GroupStart::New)
+            .with_make_stream();
+        self.create_group(options).map(|_| ())
+    }
+
+    /// Create a group starting from the specified ID.
+    /// The response is false if the group exists already,
+    /// the BUSYGROUP error is not returned in this case.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::Future;
+    /// use redis_asio::stream::{RedisStream, CreateGroupOptions, GroupStart};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let options = CreateGroupOptions::new("mystream".to_string(),
+    ///                                       "mygroup".to_string(),
+    ///                                       GroupStart::All)
+    ///     .with_make_stream();
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| stream.create_group(options))
+    ///     .map(|(_, is_created): (RedisStream, bool)| {
+    ///         println!("the group is created: {}", is_created);
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn create_group(self, options: CreateGroupOptions)
+                        -> impl Future<Item=(Self, bool), Error=RedisError> + Send + 'static {
+        self.connection.try_send(create_group_command(options))
+            .and_then(|(connection, response)| {
+                let is_created = match response {
+                    Ok(_) => true,
+                    Err(ref err) if err.error == RedisErrorKind::ReceiveError
+                        && err.desc.contains("BUSYGROUP") => false,
+                    Err(err) => return Err(err),
+                };
+                Ok((RedisStream { connection }, is_created))
             })
     }
+
+    /// Set the last delivered ID of the group,
+    /// e.g. to re-process the stream entries from the beginning.
+    pub fn set_group_id(self, options: SetGroupIdOptions)
+                        -> impl Future<Item=Self, Error=RedisError> + Send + 'static {
+        self.connection.send(set_group_id_command(options))
+            .map(|(connection, _)| RedisStream { connection })
+    }
+
+    /// Destroy the group with its consumers and pending entries.
+    /// The response is false if there is no such group.
+    pub fn destroy_group(self, options: DestroyGroupOptions)
+                         -> impl Future<Item=(Self, bool), Error=RedisError> + Send + 'static {
+        self.connection.send(destroy_group_command(options))
+            .and_then(|(connection, response)| {
+                let destroyed: i64 = from_redis_value(&response)?;
+                Ok((RedisStream { connection }, destroyed > 0))
+            })
+    }
+
+    /// Create a consumer within the group.
+    /// The response is false if the consumer exists already.
+    ///
+    /// Note the consumers are created on the first reading automatically.
+    pub fn create_consumer(self, options: ConsumerOptions)
+                           -> impl Future<Item=(Self, bool), Error=RedisError> + Send + 'static {
+        self.connection.send(create_consumer_command(options))
+            .and_then(|(connection, response)| {
+                let created: i64 = from_redis_value(&response)?;
+                Ok((RedisStream { connection }, created > 0))
+            })
+    }
+
+    /// Delete a consumer from the group.
+    /// The response is the number of the consumer's pending entries that have been dropped,
+    /// claim them before the deletion to keep them being processed.
+    pub fn delete_consumer(self, options: ConsumerOptions)
+                           -> impl Future<Item=(Self, u64), Error=RedisError> + Send + 'static {
+        self.connection.send(delete_consumer_command(options))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, from_redis_value(&response)?)))
+    }
+
+    /// Get the general information about the stream (XINFO STREAM).
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::Future;
+    /// use redis_asio::stream::{RedisStream, StreamInfoOptions, StreamInfo};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let options = StreamInfoOptions::new("mystream".to_string());
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| stream.info_stream(options))
+    ///     .map(|(_, info): (RedisStream, StreamInfo)| {
+    ///         println!("mystream contains {} entries", info.length);
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn info_stream(self, options: StreamInfoOptions)
+                       -> impl Future<Item=(Self, StreamInfo), Error=RedisError> + Send + 'static {
+        self.connection.send(info_stream_command(options, false))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, from_redis_value(&response)?)))
+    }
+
+    /// Get the detailed information about the stream, its groups, consumers
+    /// and pending entries (XINFO STREAM FULL).
+    /// Up to COUNT entries and pending entries are replied.
+    pub fn info_stream_full(self, options: StreamInfoOptions)
+                            -> impl Future<Item=(Self, StreamFullInfo), Error=RedisError> + Send + 'static {
+        self.connection.send(info_stream_command(options, true))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, from_redis_value(&response)?)))
+    }
+
+    /// Get the groups of the stream (XINFO GROUPS).
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    /// use futures::Future;
+    /// use redis_asio::stream::{RedisStream, GroupsInfoOptions, GroupInfo};
+    ///
+    /// let address = &"127.0.0.1:6379".parse::<SocketAddr>().unwrap();
+    /// let options = GroupsInfoOptions::new("mystream".to_string());
+    ///
+    /// let future = RedisStream::connect(address)
+    ///     .and_then(move |stream: RedisStream| stream.info_groups(options))
+    ///     .map(|(_, groups): (RedisStream, Vec<GroupInfo>)| {
+    ///         for group in groups.iter() {
+    ///             println!("{} has lag {:?}", group.name, group.lag);
+    ///         }
+    ///     })
+    ///     .map_err(|err| eprintln!("something went wrong: {}", err));
+    /// tokio::run(future);
+    /// ```
+    pub fn info_groups(self, options: GroupsInfoOptions)
+                       -> impl Future<Item=(Self, Vec<GroupInfo>), Error=RedisError> + Send + 'static {
+        self.connection.send(info_groups_command(options))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, from_redis_value(&response)?)))
+    }
+
+    /// Get the consumers of the group (XINFO CONSUMERS).
+    pub fn info_consumers(self, options: ConsumersInfoOptions)
+                          -> impl Future<Item=(Self, Vec<ConsumerInfo>), Error=RedisError> + Send + 'static {
+        self.connection.send(info_consumers_command(options))
+            .and_then(|(connection, response)|
+                Ok((RedisStream { connection }, from_redis_value(&response)?)))
+    }
 }